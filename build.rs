@@ -30,7 +30,7 @@ fn main() {
 
         if fs::metadata(exe_path).is_ok() {
             let output = Command::new(exe_path)
-                .args(&["analyze", "--format", "html", "--output", "dist/health-report.html"])
+                .args(["analyze", "--format", "html", "--output", "dist/health-report.html"])
                 .output();
 
             if let Ok(result) = output {
@@ -3,6 +3,10 @@
  *
  * ■ 変更履歴 (要再デプロイ) ※新しい順
  * ─────────────────────────────────────
+ * 2026-10-18: updateDocUrl に source パラメータ追加
+ *             → 修正版採用（adopt_fixed_version）と最新ファイルへの自動切替（latest_file）を区別
+ * 2026-10-18: updateDocUrl で書類の変更履歴（history）に追記
+ *             → actor パラメータ（操作者名）を記録
  * 2026-01-03: getLatestFile に修正版ファイル優先ロジック追加
  *             → _修正済_YYYYMMDD.xlsx パターンを自動検出・優先採用
  *             → Excel(xlsx)ファイルにも対応
@@ -128,7 +132,7 @@ function doGet(e) {
       if (!contractorId || !docKey || !newFileId) {
         return jsonResponse({ error: 'contractorId, docKey, newFileId are required' });
       }
-      const result = updateDocUrl(contractorId, docKey, newFileId);
+      const result = updateDocUrl(contractorId, docKey, newFileId, e.parameter.actor || '', e.parameter.source);
       return jsonResponse(result);
     }
 
@@ -554,8 +558,15 @@ function fetchSpreadsheetData(spreadsheetId, gid) {
   }
 }
 
+// updateDocUrl で記録する変更経路（フロントエンドの DocChangeSource と同じ値）
+const DOC_URL_SOURCES = ['adopt_fixed_version', 'latest_file'];
+
 // ドキュメントURLを更新（fileId変更時にProjectDataを更新）
-function updateDocUrl(contractorId, docKey, newFileId) {
+// source: 'adopt_fixed_version'（修正版採用） / 'latest_file'（最新ファイルへの自動切替、省略時）
+function updateDocUrl(contractorId, docKey, newFileId, actor, source) {
+  if (DOC_URL_SOURCES.indexOf(source) < 0) {
+    source = 'latest_file';
+  }
   try {
     const ss = SpreadsheetApp.getActiveSpreadsheet();
     let dataSheet = ss.getSheetByName(CONFIG.DATA_SHEET);
@@ -599,7 +610,20 @@ function updateDocUrl(contractorId, docKey, newFileId) {
     let updated = false;
     for (const contractor of (project.contractors || [])) {
       if (contractor.id === contractorId && contractor.docs && contractor.docs[docKey]) {
-        contractor.docs[docKey].url = newUrl;
+        const doc = contractor.docs[docKey];
+        if (doc.url !== newUrl) {
+          // 変更履歴に追記（フロントエンドの DocChange と同じ形式）
+          doc.history = doc.history || [];
+          doc.history.push({
+            at: new Date().toISOString(),
+            actor: actor,
+            field: 'url',
+            old: doc.url || null,
+            new: newUrl,
+            source: source
+          });
+        }
+        doc.url = newUrl;
         updated = true;
         break;
       }
@@ -3,6 +3,8 @@ import { PdfViewer } from './components/PdfViewer'
 import { ApiKeySetup } from './components/ApiKeySetup'
 import { AiChecker } from './components/AiChecker'
 import { SpreadsheetChecker } from './components/SpreadsheetChecker'
+import { BatchCheckWorker } from './components/BatchCheckWorker'
+import { postToParent } from './utils/editorProtocol'
 
 function App() {
   const params = new URLSearchParams(window.location.search)
@@ -12,10 +14,10 @@ function App() {
     return (
       <ApiKeySetup
         onComplete={() => {
-          window.parent.postMessage({ type: 'apikey-setup-complete' }, '*')
+          postToParent({ type: 'apikey-setup-complete' })
         }}
         onCancel={() => {
-          window.parent.postMessage({ type: 'apikey-setup-cancel' }, '*')
+          postToParent({ type: 'apikey-setup-cancel' })
         }}
       />
     )
@@ -25,6 +27,10 @@ function App() {
     return <AiChecker />
   }
 
+  if (mode === 'batch-check') {
+    return <BatchCheckWorker />
+  }
+
   if (mode === 'spreadsheet-check') {
     return <SpreadsheetChecker />
   }
@@ -6,8 +6,8 @@ import { getDocument, GlobalWorkerOptions } from 'pdfjs-dist';
 import type { PDFDocumentProxy } from 'pdfjs-dist';
 import { checkDocumentImage, type CheckResult } from '../services/gemini';
 import { getApiKey } from '../services/apiKey';
-import { getCachedPdfAsync, setCachedPdf, isCacheValid, invalidateCache } from '../services/pdfCache';
-import { safeBase64ToArrayBuffer } from '../utils/base64';
+import { fetchDrivePdf } from '../services/drivePdf';
+import { postToParent } from '../utils/editorProtocol';
 import './AiChecker.css';
 
 GlobalWorkerOptions.workerSrc = new URL(
@@ -48,75 +48,13 @@ export function AiChecker() {
 
     const loadPdf = async () => {
       try {
-        let pdfBytes: ArrayBuffer | undefined;
-        let modifiedTime: string | undefined;
-
         // GAS URLが必要
         if (!gasUrl) {
           setError('シート連携が未設定です。メニュー → シート連携設定 からGAS URLを設定してください。');
           return;
         }
 
-        // GASから最新ファイル情報を取得（フォルダ内の同名or最新ファイルを探す）
-        let actualFileId = fileId;
-        try {
-          const infoRes = await fetch(`${gasUrl}?action=getLatestFile&fileId=${fileId}`, { cache: 'no-store' });
-          const info = await infoRes.json();
-          console.log('[AiChecker] GAS getLatestFile response:', info);
-          if (!info.error) {
-            modifiedTime = info.modifiedTime;
-            // ファイルIDが更新された場合は新しいIDを使用
-            if (info.wasUpdated && info.fileId) {
-              console.log('[AiChecker] File updated:', fileId, '->', info.fileId);
-              actualFileId = info.fileId;
-              // スプレッドシートのURLを更新（GETリクエスト）
-              if (contractorId && docKey) {
-                try {
-                  const updateUrl = `${gasUrl}?action=updateDocUrl&contractorId=${encodeURIComponent(contractorId)}&docKey=${encodeURIComponent(docKey)}&newFileId=${encodeURIComponent(info.fileId)}`;
-                  await fetch(updateUrl, { cache: 'no-store' });
-                  console.log('[AiChecker] Spreadsheet URL updated');
-                } catch (e) {
-                  console.error('[AiChecker] Failed to update spreadsheet URL:', e);
-                }
-              }
-            }
-          }
-        } catch {
-          // ファイル情報取得失敗は無視
-        }
-
-        // キャッシュの有効性をチェック（actualFileIdを使用）
-        let useCache = false;
-        if (modifiedTime) {
-          useCache = await isCacheValid(actualFileId, modifiedTime);
-          if (!useCache) {
-            await invalidateCache(actualFileId);
-            console.log('[AiChecker] Cache invalidated: file was modified');
-          }
-        }
-
-        if (useCache) {
-          const cached = await getCachedPdfAsync(actualFileId);
-          if (cached) {
-            console.log('[AiChecker] PDF found in valid cache:', actualFileId);
-            pdfBytes = cached;
-          }
-        }
-
-        if (!pdfBytes) {
-          console.log('[AiChecker] Fetching PDF from GAS:', actualFileId);
-          // GAS経由でPDFを取得（actualFileIdを使用）
-          const response = await fetch(`${gasUrl}?action=fetchPdf&fileId=${actualFileId}`, { cache: 'no-store' });
-          if (!response.ok) throw new Error('PDF取得失敗');
-          const data = await response.json();
-          if (data.error) throw new Error(data.error);
-          if (!data.base64) throw new Error('PDFデータがありません');
-          // Base64をArrayBufferに変換（sanitization付き）
-          pdfBytes = safeBase64ToArrayBuffer(data.base64);
-          // キャッシュに保存（modifiedTime付き）
-          await setCachedPdf(actualFileId, pdfBytes, modifiedTime || data.modifiedTime);
-          console.log('[AiChecker] PDF cached:', actualFileId);
-        }
+        const { bytes: pdfBytes } = await fetchDrivePdf(gasUrl, fileId, { contractorId, docKey }, 'AiChecker');
 
         const pdf = await getDocument({ data: pdfBytes }).promise;
         pdfDocRef.current = pdf;
@@ -176,12 +114,12 @@ export function AiChecker() {
   };
 
   const handleBack = () => {
-    window.parent.postMessage({ type: 'ai-check-cancel' }, '*');
+    postToParent({ type: 'ai-check-cancel' });
   };
 
   const handleSaveAndBack = () => {
     if (result) {
-      window.parent.postMessage({
+      postToParent({
         type: 'ai-check-result',
         result,
         contractor,
@@ -189,7 +127,7 @@ export function AiChecker() {
         docType,
         docKey,  // 実際のドキュメントキー（07_建退共など）
         fileId,
-      }, '*');
+      });
     }
   };
 
@@ -10,6 +10,7 @@ import {
   authenticateWithPasskey,
   removePasskey,
 } from '../services/webAuthn';
+import { postToParent } from '../utils/editorProtocol';
 import './ApiKeySetup.css';
 
 interface Props {
@@ -127,7 +128,7 @@ export function ApiKeySetup({ onComplete, onCancel }: Props) {
         <div className="setup-form">
           <p className="success-message">設定完了</p>
           <div className="button-row">
-            <button onClick={() => window.parent.postMessage({ type: 'apikey-setup-complete' }, '*')}>
+            <button onClick={() => postToParent({ type: 'apikey-setup-complete' })}>
               閉じる
             </button>
           </div>
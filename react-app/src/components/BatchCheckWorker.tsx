@@ -0,0 +1,71 @@
+/**
+ * AI一括チェックのワーカー（非表示iframeで1書類ずつ実行）
+ *
+ * PDFの1ページ目をAIチェック画面と同じ倍率で画像化してGeminiに送り、
+ * 結果または失敗理由を batch-check-result で親ウィンドウへ返す。
+ */
+import { useEffect } from 'react';
+import { getDocument, GlobalWorkerOptions } from 'pdfjs-dist';
+import { requestDocumentCheck } from '../services/gemini';
+import { getApiKey } from '../services/apiKey';
+import { fetchDrivePdf } from '../services/drivePdf';
+import { postToParent } from '../utils/editorProtocol';
+
+GlobalWorkerOptions.workerSrc = new URL(
+  'pdfjs-dist/build/pdf.worker.min.mjs',
+  import.meta.url
+).toString();
+
+/** AIチェック画面（AiChecker）と同じ描画倍率 */
+const RENDER_SCALE = 1.5;
+
+async function renderFirstPage(pdfBytes: ArrayBuffer): Promise<string> {
+  const pdf = await getDocument({ data: pdfBytes }).promise;
+  const page = await pdf.getPage(1);
+  const viewport = page.getViewport({ scale: RENDER_SCALE });
+  const canvas = document.createElement('canvas');
+  const ctx = canvas.getContext('2d');
+  if (!ctx) throw new Error('キャンバスを作成できません');
+
+  canvas.width = viewport.width;
+  canvas.height = viewport.height;
+  await page.render({ canvasContext: ctx, viewport, canvas }).promise;
+  return canvas.toDataURL('image/png').split(',')[1];
+}
+
+export function BatchCheckWorker() {
+  useEffect(() => {
+    const params = new URLSearchParams(window.location.search);
+    const jobId = params.get('jobId') || '';
+    const fileId = params.get('fileId');
+    const gasUrl = params.get('gasUrl');
+    const docType = params.get('docType') || '書類';
+    const contractor = params.get('contractor') || '業者';
+    const docKey = params.get('docKey') || docType;
+    const contractorId = params.get('contractorId') || '';
+
+    const fail = (error: string) => {
+      postToParent({ type: 'batch-check-result', jobId, contractor, docKey, fileId, error });
+    };
+
+    const run = async () => {
+      if (!fileId) return fail('ファイルIDが指定されていません');
+      if (!gasUrl) return fail('シート連携が未設定です');
+      if (!getApiKey()) return fail('APIキーが設定されていません');
+
+      try {
+        const pdf = await fetchDrivePdf(gasUrl, fileId, { contractorId, docKey }, 'BatchCheck');
+        const base64 = await renderFirstPage(pdf.bytes);
+        const result = await requestDocumentCheck(base64, 'image/png', docType, contractor);
+        // 最新版に切り替わった場合は実際にチェックしたファイルIDを返す
+        postToParent({ type: 'batch-check-result', jobId, contractor, docKey, fileId: pdf.fileId, result });
+      } catch (e) {
+        fail(e instanceof Error ? e.message : String(e));
+      }
+    };
+
+    run();
+  }, []);
+
+  return <div className="batch-check-worker">チェック中...</div>;
+}
@@ -6,6 +6,7 @@ import type { PDFDocumentProxy } from 'pdfjs-dist';
 import { getCachedPdf, setCachedPdf, isCacheValid, invalidateCache } from '../services/pdfCache';
 import { createFontSubset } from '../utils/fontSubset';
 import { safeBase64ToArrayBuffer } from '../utils/base64';
+import { postToParent } from '../utils/editorProtocol';
 import './PdfEditor.css';
 
 // PDF.js worker設定 (v5.x - use bundled worker)
@@ -957,7 +958,7 @@ export function PdfEditor({ pdfUrl, onSave }: PdfEditorProps) {
           onClick={() => {
             // 親ウィンドウに戻るメッセージを送信（iframe統合用）
             if (window.parent !== window) {
-              window.parent.postMessage({ type: 'back' }, '*');
+              postToParent({ type: 'back' });
             }
             // 常にhistory.back()を試行
             window.history.back();
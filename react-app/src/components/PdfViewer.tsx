@@ -7,7 +7,9 @@ import type { PDFDocumentProxy } from 'pdfjs-dist';
 import { getCachedPdfAsync, setCachedPdf, isCacheValid, invalidateCache } from '../services/pdfCache';
 import { checkDocumentImage, type CheckResult } from '../services/gemini';
 import { getApiKey } from '../services/apiKey';
+import { buildUpdateDocUrl } from '../services/docUrl';
 import { safeBase64ToArrayBuffer } from '../utils/base64';
+import { postToParent } from '../utils/editorProtocol';
 import './PdfViewer.css';
 
 GlobalWorkerOptions.workerSrc = new URL(
@@ -20,6 +22,22 @@ function getUrlParam(name: string): string | null {
   return params.get(name);
 }
 
+/** 強調する領域（ページ内の比率 0.0〜1.0） */
+interface Highlight {
+  x: number;
+  y: number;
+  width: number;
+  height: number;
+}
+
+/** highlight パラメータ（x,y,width,height）を解釈 */
+function parseHighlight(value: string | null): Highlight | null {
+  if (!value) return null;
+  const [x, y, width, height] = value.split(',').map(Number);
+  if ([x, y, width, height].some(n => !Number.isFinite(n))) return null;
+  return { x, y, width, height };
+}
+
 /** フィールドキーを日本語ラベルに変換 */
 function formatFieldName(key: string): string {
   const labels: Record<string, string> = {
@@ -52,6 +70,9 @@ export function PdfViewer() {
   const contractorId = getUrlParam('contractorId') || '';
   const docKey = getUrlParam('docKey') || '';
   const gasUrl = getUrlParam('gasUrl');
+  // 指摘箇所から開いた場合のページと強調する領域
+  const initialPage = Math.max(1, Number(getUrlParam('page')) || 1);
+  const highlight = parseHighlight(getUrlParam('highlight'));
 
   // PDF読み込み
   useEffect(() => {
@@ -102,7 +123,12 @@ export function PdfViewer() {
               // スプレッドシートのURLを更新（GETリクエスト）
               if (contractorId && docKey) {
                 try {
-                  const updateUrl = `${gasUrl}?action=updateDocUrl&contractorId=${encodeURIComponent(contractorId)}&docKey=${encodeURIComponent(docKey)}&newFileId=${encodeURIComponent(info.fileId)}`;
+                  const updateUrl = buildUpdateDocUrl(gasUrl, {
+                    contractorId,
+                    docKey,
+                    newFileId: info.fileId,
+                    source: 'latest_file',
+                  });
                   await fetch(updateUrl, { cache: 'no-store' });
                   console.log('[PdfViewer] Spreadsheet URL updated');
                 } catch (e) {
@@ -184,10 +210,10 @@ export function PdfViewer() {
     loadPdf();
   }, [fileId]);
 
-  // PDF読み込み完了後に最初のページを描画
+  // PDF読み込み完了後に最初のページ（指摘箇所のページ）を描画
   useEffect(() => {
     if (pdfLoaded && canvasRef.current) {
-      renderPage(1);
+      renderPage(Math.min(initialPage, pdfDocRef.current?.numPages ?? 1));
     }
   }, [pdfLoaded]);
 
@@ -209,11 +235,11 @@ export function PdfViewer() {
   };
 
   const handleBack = () => {
-    window.parent.postMessage({ type: 'viewer-back' }, '*');
+    postToParent({ type: 'viewer-back' });
   };
 
   const handleEdit = () => {
-    window.parent.postMessage({ type: 'viewer-edit' }, '*');
+    postToParent({ type: 'viewer-edit' });
   };
 
   // インラインAIチェック実行
@@ -247,7 +273,7 @@ export function PdfViewer() {
   // 結果を保存して閉じる
   const handleSaveResult = () => {
     if (checkResult) {
-      window.parent.postMessage({
+      postToParent({
         type: 'ai-check-result',
         result: checkResult,
         contractor,
@@ -255,7 +281,7 @@ export function PdfViewer() {
         docType,
         docKey,
         fileId,
-      }, '*');
+      });
     }
     setShowResult(false);
   };
@@ -334,7 +360,20 @@ export function PdfViewer() {
             <div className="loading-text">PDF読み込み中</div>
           </div>
         ) : (
-          <canvas ref={canvasRef} />
+          <div className="canvas-wrap">
+            <canvas ref={canvasRef} />
+            {highlight && currentPage === initialPage && (
+              <div
+                className="finding-highlight"
+                style={{
+                  left: `${highlight.x * 100}%`,
+                  top: `${highlight.y * 100}%`,
+                  width: `${highlight.width * 100}%`,
+                  height: `${highlight.height * 100}%`,
+                }}
+              />
+            )}
+          </div>
         )}
 
         {/* インラインチェック結果パネル */}
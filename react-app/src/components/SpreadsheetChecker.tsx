@@ -12,6 +12,7 @@
 const BUILD_VERSION = '2026-01-03T10:10:00';
 import { useState, useEffect } from 'react';
 import { getApiKey } from '../services/apiKey';
+import { buildUpdateDocUrl } from '../services/docUrl';
 import { safeBase64ToArrayBuffer } from '../utils/base64';
 import {
   extractFields,
@@ -30,6 +31,7 @@ import {
 } from '../services/xlsxFixer';
 import type { CheckResult } from '../services/gemini';
 import * as XLSX from 'xlsx';
+import { postToParent } from '../utils/editorProtocol';
 import './AiChecker.css';
 
 interface SheetInfo {
@@ -496,7 +498,7 @@ export function SpreadsheetChecker() {
       setResult(null);
       setSheetData(null);
     } else {
-      window.parent.postMessage({ type: 'spreadsheet-check-cancel' }, '*');
+      postToParent({ type: 'spreadsheet-check-cancel' });
     }
   };
 
@@ -505,7 +507,7 @@ export function SpreadsheetChecker() {
       // ExtractionResultをCheckResult形式に変換
       const checkResult: CheckResult = convertExtractionResultToCheckResult(result);
 
-      window.parent.postMessage({
+      postToParent({
         type: 'ai-check-result',
         result: checkResult,
         contractor,
@@ -513,7 +515,7 @@ export function SpreadsheetChecker() {
         docType,
         docKey,
         fileId: spreadsheetId || fileId, // フィールド名をfileIdに統一
-      }, '*');
+      });
     }
   };
 
@@ -570,7 +572,12 @@ export function SpreadsheetChecker() {
       }
 
       // ProjectDataのURLを更新
-      const updateUrl = `${gasUrl}?action=updateDocUrl&contractorId=${encodeURIComponent(contractorId)}&docKey=${encodeURIComponent(docKey)}&newFileId=${encodeURIComponent(latestData.fileId)}`;
+      const updateUrl = buildUpdateDocUrl(gasUrl, {
+        contractorId,
+        docKey,
+        newFileId: latestData.fileId,
+        source: 'adopt_fixed_version',
+      });
       const updateResponse = await fetch(updateUrl, { cache: 'no-store' });
       const updateData = await updateResponse.json();
 
@@ -581,7 +588,7 @@ export function SpreadsheetChecker() {
       alert(`修正版を採用しました: ${latestData.fileName}`);
 
       // 親ウィンドウに通知して閉じる
-      window.parent.postMessage({
+      postToParent({
         type: 'fixed-version-adopted',
         newFileId: latestData.fileId,
         fileName: latestData.fileName,
@@ -589,7 +596,7 @@ export function SpreadsheetChecker() {
         contractorId,
         docType,
         docKey,
-      }, '*');
+      });
     } catch (e) {
       console.error('[SpreadsheetChecker] Adopt error:', e);
       alert(`修正版の採用に失敗しました: ${e instanceof Error ? e.message : '不明なエラー'}`);
@@ -0,0 +1,42 @@
+/**
+ * GASの updateDocUrl（書類URLの更新）呼び出し
+ *
+ * 書類の変更履歴（history）に記録する経路と操作者名を付けて送る。
+ * 経路の値はRust側の DocChangeSource（snake_case）と同じ。
+ */
+
+/** 操作者名の保存キー（Rust側 utils/audit.rs の ACTOR_NAME_KEY と同じ） */
+const ACTOR_NAME_KEY = 'sekou_taisei_actor_name';
+
+/** URL更新の経路 */
+export type DocUrlSource = 'adopt_fixed_version' | 'latest_file';
+
+export interface DocUrlUpdate {
+  contractorId: string;
+  docKey: string;
+  newFileId: string;
+  source: DocUrlSource;
+}
+
+function getActorName(): string {
+  try {
+    return localStorage.getItem(ACTOR_NAME_KEY) || '';
+  } catch {
+    return '';
+  }
+}
+
+/**
+ * updateDocUrl のリクエストURL
+ */
+export function buildUpdateDocUrl(gasUrl: string, update: DocUrlUpdate): string {
+  const params = new URLSearchParams({
+    action: 'updateDocUrl',
+    contractorId: update.contractorId,
+    docKey: update.docKey,
+    newFileId: update.newFileId,
+    source: update.source,
+    actor: getActorName(),
+  });
+  return `${gasUrl}?${params.toString()}`;
+}
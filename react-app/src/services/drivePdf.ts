@@ -0,0 +1,97 @@
+/**
+ * Google Drive上のPDFをGAS経由で取得（IndexedDBキャッシュ付き）
+ *
+ * AIチェック画面と一括チェックで共通。
+ */
+import { getCachedPdfAsync, setCachedPdf, isCacheValid, invalidateCache } from './pdfCache';
+import { safeBase64ToArrayBuffer } from '../utils/base64';
+import { buildUpdateDocUrl } from './docUrl';
+
+/** ファイルIDが更新された場合にスプレッドシートのURLを書き換える対象 */
+export interface DocTarget {
+  contractorId: string;
+  docKey: string;
+}
+
+export interface DrivePdf {
+  bytes: ArrayBuffer;
+  /** 実際に読み込んだファイルID（フォルダ内の最新ファイルに更新されている場合あり） */
+  fileId: string;
+}
+
+/**
+ * PDFを取得（フォルダ内の同名or最新ファイルを探し、未変更ならキャッシュを使う）
+ */
+export async function fetchDrivePdf(
+  gasUrl: string,
+  fileId: string,
+  target?: DocTarget,
+  logTag = 'DrivePdf'
+): Promise<DrivePdf> {
+  let modifiedTime: string | undefined;
+
+  // GASから最新ファイル情報を取得（フォルダ内の同名or最新ファイルを探す）
+  let actualFileId = fileId;
+  try {
+    const infoRes = await fetch(`${gasUrl}?action=getLatestFile&fileId=${fileId}`, { cache: 'no-store' });
+    const info = await infoRes.json();
+    console.log(`[${logTag}] GAS getLatestFile response:`, info);
+    if (!info.error) {
+      modifiedTime = info.modifiedTime;
+      // ファイルIDが更新された場合は新しいIDを使用
+      if (info.wasUpdated && info.fileId) {
+        console.log(`[${logTag}] File updated:`, fileId, '->', info.fileId);
+        actualFileId = info.fileId;
+        // スプレッドシートのURLを更新（GETリクエスト）
+        if (target?.contractorId && target.docKey) {
+          try {
+            const updateUrl = buildUpdateDocUrl(gasUrl, {
+              contractorId: target.contractorId,
+              docKey: target.docKey,
+              newFileId: info.fileId,
+              source: 'latest_file',
+            });
+            await fetch(updateUrl, { cache: 'no-store' });
+            console.log(`[${logTag}] Spreadsheet URL updated`);
+          } catch (e) {
+            console.error(`[${logTag}] Failed to update spreadsheet URL:`, e);
+          }
+        }
+      }
+    }
+  } catch {
+    // ファイル情報取得失敗は無視
+  }
+
+  // キャッシュの有効性をチェック（actualFileIdを使用）
+  let useCache = false;
+  if (modifiedTime) {
+    useCache = await isCacheValid(actualFileId, modifiedTime);
+    if (!useCache) {
+      await invalidateCache(actualFileId);
+      console.log(`[${logTag}] Cache invalidated: file was modified`);
+    }
+  }
+
+  if (useCache) {
+    const cached = await getCachedPdfAsync(actualFileId);
+    if (cached) {
+      console.log(`[${logTag}] PDF found in valid cache:`, actualFileId);
+      return { bytes: cached, fileId: actualFileId };
+    }
+  }
+
+  console.log(`[${logTag}] Fetching PDF from GAS:`, actualFileId);
+  // GAS経由でPDFを取得（actualFileIdを使用）
+  const response = await fetch(`${gasUrl}?action=fetchPdf&fileId=${actualFileId}`, { cache: 'no-store' });
+  if (!response.ok) throw new Error('PDF取得失敗');
+  const data = await response.json();
+  if (data.error) throw new Error(data.error);
+  if (!data.base64) throw new Error('PDFデータがありません');
+  // Base64をArrayBufferに変換（sanitization付き）
+  const bytes = safeBase64ToArrayBuffer(data.base64);
+  // キャッシュに保存（modifiedTime付き）
+  await setCachedPdf(actualFileId, bytes, modifiedTime || data.modifiedTime);
+  console.log(`[${logTag}] PDF cached:`, actualFileId);
+  return { bytes, fileId: actualFileId };
+}
@@ -2,6 +2,11 @@
  * Gemini API サービス - ブラウザから直接呼び出し
  *
  * ## 変更履歴
+ * - 2026-10-18: requestDocumentCheck追加（例外を投げる版、一括チェックで失敗を区別するため）
+ * - 2026-10-18: 指摘・未記入項目に位置（page / bbox）を追加（全プロンプト共通の指示）
+ * - 2026-10-18: 暴対法誓約書の抽出フィールド追加（OCRテンプレート抽出と同じキー）
+ * - 2026-10-18: 建設業許可用プロンプト追加（許可行政庁・区分・番号・業種・許可日を抽出）
+ * - 2026-10-18: 労働保険番号・事業所番号の抽出フィールド追加（番号形式チェック用）
  * - 2026-01-03: extracted_fields追加、現場代理人/主任技術者の資格・在籍用プロンプト追加
  *               → 必須フィールド（氏名、資格番号）を構造化抽出
  * - 2026-01-02: 入場年月日等をチェック対象外として明示（全プロンプト）
@@ -13,11 +18,17 @@ import { getApiKey } from './apiKey';
 const GEMINI_MODEL = 'gemini-2.0-flash-exp';
 const API_BASE = 'https://generativelanguage.googleapis.com/v1beta/models';
 
+/** 指摘箇所の位置（ページは1始まり、bboxはページ内の比率 0.0〜1.0） */
+export interface FindingLocation {
+  page?: number;
+  bbox?: { x: number; y: number; width: number; height: number };
+}
+
 export interface CheckResult {
   status: 'ok' | 'warning' | 'error';
   summary: string;
-  items: Array<{ type: 'ok' | 'warning' | 'error'; message: string }>;
-  missing_fields: Array<{ field: string; location: string }>;
+  items: Array<{ type: 'ok' | 'warning' | 'error'; message: string } & FindingLocation>;
+  missing_fields: Array<{ field: string; location: string } & FindingLocation>;
   /** 書類から抽出した必須フィールド */
   extracted_fields?: Record<string, string>;
 }
@@ -28,12 +39,19 @@ export interface CheckResult {
  * - 042_現場代理人在籍: representative_name
  * - 051_主任技術者資格: chief_engineer_name, qualification_number
  * - 052_主任技術者在籍: chief_engineer_name
+ * - 01_建設業許可: permit_authority, permit_category, permit_number, permit_trades, permit_date
+ * - 02_事業所番号: employment_office_number, health_office_symbol, health_insurer_number（記載があるもの）
+ * - 03_労働保険番号: labor_insurance_number
+ * - 09_暴対法誓約書: pledge_date, recipient, address, company_name, representative_name
  */
 export const REQUIRED_EXTRACTED_FIELDS: Record<string, string[]> = {
   '現場代理人資格': ['representative_name', 'qualification_number'],
   '現場代理人在籍': ['representative_name'],
   '主任技術者資格': ['chief_engineer_name', 'qualification_number'],
   '主任技術者在籍': ['chief_engineer_name'],
+  '建設業許可': ['permit_authority', 'permit_category', 'permit_number', 'permit_trades', 'permit_date'],
+  '労働保険番号': ['labor_insurance_number'],
+  '暴対法誓約書': ['pledge_date', 'recipient', 'address', 'company_name', 'representative_name'],
 };
 
 const PROMPTS: Record<string, string> = {
@@ -59,7 +77,14 @@ const PROMPTS: Record<string, string> = {
     ],
     "missing_fields": [
         {"field": "未記入項目名", "location": "位置の説明"}
-    ]
+    ],
+    "extracted_fields": {
+        "pledge_date": "yyyy-MM-dd（未記入の場合は空文字）",
+        "recipient": "抽出した宛先",
+        "address": "抽出した誓約者の住所",
+        "company_name": "抽出した誓約者の法人名",
+        "representative_name": "抽出した代表者の役職と氏名"
+    }
 }`,
 
   "作業員名簿": `あなたは建設業の書類チェック専門家です。
@@ -176,6 +201,44 @@ const PROMPTS: Record<string, string> = {
 
 };
 
+// 建設業許可証明用プロンプト
+const KENSETSU_KYOKA_PROMPT = `あなたは建設業の書類チェック専門家です。
+この書類が「建設業許可」の証明（許可通知書・許可証明書）として有効かどうかを確認してください。
+
+業者名: {contractor_name}
+
+【チェック項目】
+1. 許可を受けた者の商号が業者名（{contractor_name}）と一致するか
+2. 許可番号が確認できるか
+3. 許可業種が確認できるか
+4. 許可年月日が確認できるか
+
+【必須抽出フィールド】
+- permit_authority: 許可行政庁（例: 国土交通大臣、東京都知事）
+- permit_category: 一般 または 特定
+- permit_number: 許可番号（例: (般-5) 第12345号）
+- permit_trades: 許可業種（読点区切り。例: 土木工事業、舗装工事業）
+- permit_date: 許可年月日（yyyy-MM-dd形式。和暦は西暦に変換）
+
+結果を以下のJSON形式で返してください:
+{
+    "status": "ok" | "warning" | "error",
+    "summary": "全体の評価（1文）",
+    "items": [
+        {"type": "ok" | "warning" | "error", "message": "具体的な指摘"}
+    ],
+    "missing_fields": [
+        {"field": "未記入項目名", "location": "位置の説明"}
+    ],
+    "extracted_fields": {
+        "permit_authority": "抽出した許可行政庁",
+        "permit_category": "一般 または 特定",
+        "permit_number": "抽出した許可番号",
+        "permit_trades": "抽出した許可業種",
+        "permit_date": "yyyy-MM-dd"
+    }
+}`;
+
 // 労働保険番号確認用プロンプト
 const ROUDOU_HOKEN_PROMPT = `あなたは建設業の書類チェック専門家です。
 この書類が「労働保険番号」の証明として有効かどうかを確認してください。
@@ -197,6 +260,9 @@ const ROUDOU_HOKEN_PROMPT = `あなたは建設業の書類チェック専門家
 3. 口座名義が業者名（{contractor_name}）と一致または関連しているか
 4. 書類の形式が労働保険関連の公的書類として妥当か
 
+【必須抽出フィールド】
+- labor_insurance_number: 労働保険番号（14桁。府県-所掌-管轄-基幹番号-枝番号の区切りのまま）
+
 結果を以下のJSON形式で返してください:
 {
     "status": "ok" | "warning" | "error",
@@ -206,7 +272,43 @@ const ROUDOU_HOKEN_PROMPT = `あなたは建設業の書類チェック専門家
     ],
     "missing_fields": [
         {"field": "未記入項目名", "location": "位置の説明"}
-    ]
+    ],
+    "extracted_fields": {
+        "labor_insurance_number": "抽出した労働保険番号"
+    }
+}`;
+
+// 事業所番号確認用プロンプト
+const JIGYOSHO_BANGO_PROMPT = `あなたは建設業の書類チェック専門家です。
+この書類が「事業所番号」（雇用保険・健康保険の適用事業所）の証明として有効かどうかを確認してください。
+
+業者名: {contractor_name}
+
+チェック項目:
+1. 雇用保険の事業所番号、または健康保険の事業所整理記号・保険者番号が確認できるか
+2. 事業所名が業者名（{contractor_name}）と一致または関連しているか
+3. 書類の形式が公的書類として妥当か
+
+【抽出フィールド】（記載があるもののみ。数字は1桁ずつ正確に読み取ること）
+- employment_office_number: 雇用保険事業所番号（例: 1301-123456-7）
+- health_office_symbol: 健康保険の事業所整理記号（例: 01-イロハ）
+- health_insurer_number: 健康保険の保険者番号（8桁または6桁）
+
+結果を以下のJSON形式で返してください:
+{
+    "status": "ok" | "warning" | "error",
+    "summary": "全体の評価（1文）",
+    "items": [
+        {"type": "ok" | "warning" | "error", "message": "具体的な指摘"}
+    ],
+    "missing_fields": [
+        {"field": "未記入項目名", "location": "位置の説明"}
+    ],
+    "extracted_fields": {
+        "employment_office_number": "抽出した雇用保険事業所番号",
+        "health_office_symbol": "抽出した事業所整理記号",
+        "health_insurer_number": "抽出した保険者番号"
+    }
 }`;
 
 // 法定外労災加入証明用プロンプト
@@ -448,10 +550,22 @@ const GENERIC_PROMPT = `あなたは建設業の書類チェック専門家で
     ]
 }`;
 
+/** 指摘箇所の位置（全プロンプト共通。ダッシュボードで該当箇所を強調表示する） */
+const LOCATION_INSTRUCTION = `
+
+【指摘箇所の位置】
+items と missing_fields の各要素には、書類上の位置が分かる場合のみ次を追加してください:
+- "page": ページ番号（1始まり）
+- "bbox": {"x": 左端, "y": 上端, "width": 幅, "height": 高さ}（ページの幅・高さに対する比率 0.0〜1.0）
+位置が分からない場合は省略してください。`;
+
 function getPrompt(docType: string, contractorName: string): string {
   let template: string;
   if (PROMPTS[docType]) {
     template = PROMPTS[docType];
+  } else if (docType.includes('建設業許可')) {
+    // 建設業許可証明
+    template = KENSETSU_KYOKA_PROMPT;
   } else if (docType.includes('現場代理人') && docType.includes('資格')) {
     // 現場代理人資格証明
     template = GENBA_DAIRI_SHIKAKU_PROMPT;
@@ -467,6 +581,9 @@ function getPrompt(docType: string, contractorName: string): string {
   } else if (docType.includes('法定外労災') || docType.includes('法廷外労災')) {
     // 「法定外労災」を含む書類は法定外労災加入証明用プロンプトを使用
     template = HOUTEI_GAI_ROUSAI_PROMPT;
+  } else if (docType.includes('事業所番号')) {
+    // 雇用保険・健康保険の事業所番号
+    template = JIGYOSHO_BANGO_PROMPT;
   } else if (docType.includes('労働保険')) {
     // 「労働保険」を含む書類は労働保険番号確認用プロンプトを使用
     template = ROUDOU_HOKEN_PROMPT;
@@ -476,9 +593,10 @@ function getPrompt(docType: string, contractorName: string): string {
   } else {
     template = GENERIC_PROMPT.replace('{doc_type}', docType);
   }
-  return template.replace(/{contractor_name}/g, contractorName);
+  return template.replace(/{contractor_name}/g, contractorName) + LOCATION_INSTRUCTION;
 }
 
+
 function parseResponse(text: string): CheckResult {
   let jsonText = text;
 
@@ -502,7 +620,10 @@ function parseResponse(text: string): CheckResult {
   }
 }
 
-export async function checkDocumentImage(
+/**
+ * 書類画像をチェック（APIキー未設定・API呼び出しの失敗は例外を投げる）
+ */
+export async function requestDocumentCheck(
   imageBase64: string,
   mimeType: string,
   docType: string,
@@ -510,48 +631,62 @@ export async function checkDocumentImage(
 ): Promise<CheckResult> {
   const apiKey = getApiKey();
   if (!apiKey) {
-    return {
-      status: 'error',
-      summary: 'APIキーが設定されていません',
-      items: [{ type: 'error', message: 'メニュー → APIキー設定 から設定してください' }],
-      missing_fields: [],
-    };
+    throw new Error('APIキーが設定されていません');
   }
 
   const prompt = getPrompt(docType, contractorName);
   const url = `${API_BASE}/${GEMINI_MODEL}:generateContent?key=${apiKey}`;
 
-  try {
-    const response = await fetch(url, {
-      method: 'POST',
-      headers: { 'Content-Type': 'application/json' },
-      body: JSON.stringify({
-        contents: [{
-          parts: [
-            { text: prompt },
-            { inline_data: { mime_type: mimeType, data: imageBase64 } },
-          ],
-        }],
-        generationConfig: {
-          temperature: 0.1,
-          maxOutputTokens: 2048,
-        },
-      }),
-    });
+  const response = await fetch(url, {
+    method: 'POST',
+    headers: { 'Content-Type': 'application/json' },
+    body: JSON.stringify({
+      contents: [{
+        parts: [
+          { text: prompt },
+          { inline_data: { mime_type: mimeType, data: imageBase64 } },
+        ],
+      }],
+      generationConfig: {
+        temperature: 0.1,
+        maxOutputTokens: 2048,
+      },
+    }),
+  });
+
+  if (!response.ok) {
+    // 429（レート制限）は呼び出し側で再試行できるようにステータスを残す（本文がJSONでない場合もある）
+    const body = await response.json().catch(() => null);
+    throw new Error(`API error ${response.status}: ${body?.error?.message || response.statusText}`);
+  }
 
-    if (!response.ok) {
-      const error = await response.json();
-      throw new Error(error.error?.message || `API error: ${response.status}`);
-    }
+  const data = await response.json();
+  const text = data.candidates?.[0]?.content?.parts?.[0]?.text;
 
-    const data = await response.json();
-    const text = data.candidates?.[0]?.content?.parts?.[0]?.text;
+  if (!text) {
+    throw new Error('Empty response from Gemini');
+  }
 
-    if (!text) {
-      throw new Error('Empty response from Gemini');
-    }
+  return parseResponse(text);
+}
 
-    return parseResponse(text);
+export async function checkDocumentImage(
+  imageBase64: string,
+  mimeType: string,
+  docType: string,
+  contractorName: string
+): Promise<CheckResult> {
+  if (!getApiKey()) {
+    return {
+      status: 'error',
+      summary: 'APIキーが設定されていません',
+      items: [{ type: 'error', message: 'メニュー → APIキー設定 から設定してください' }],
+      missing_fields: [],
+    };
+  }
+
+  try {
+    return await requestDocumentCheck(imageBase64, mimeType, docType, contractorName);
   } catch (e) {
     return {
       status: 'error',
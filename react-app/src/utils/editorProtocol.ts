@@ -0,0 +1,54 @@
+/**
+ * Leptosアプリ（親ウィンドウ）とのpostMessageプロトコル
+ *
+ * Rust側の定義は src/utils/editor_protocol.rs の EditorMessage。
+ * メッセージを変更する場合は両方を更新し、PROTOCOL_VERSION を上げる。
+ */
+
+import type { CheckResult } from '../services/gemini';
+
+/** プロトコルのバージョン（Rust側の PROTOCOL_VERSION と一致させる） */
+export const PROTOCOL_VERSION = 2;
+
+export type EditorMessage =
+  | { type: 'back' }
+  | { type: 'viewer-back' }
+  | { type: 'viewer-edit' }
+  | { type: 'ai-check-cancel' }
+  | { type: 'spreadsheet-check-cancel' }
+  | {
+      type: 'ai-check-result';
+      result: CheckResult;
+      contractor: string;
+      contractorId?: string | null;
+      docType?: string | null;
+      docKey: string;
+      fileId?: string | null;
+    }
+  | {
+      type: 'batch-check-result';
+      jobId: string;
+      contractor: string;
+      docKey: string;
+      fileId?: string | null;
+      result?: CheckResult | null;
+      error?: string | null;
+    }
+  | {
+      type: 'fixed-version-adopted';
+      newFileId: string;
+      fileName?: string | null;
+      contractor: string;
+      contractorId?: string | null;
+      docType?: string | null;
+      docKey: string;
+    }
+  | { type: 'apikey-setup-complete' }
+  | { type: 'apikey-setup-cancel' };
+
+/**
+ * 親ウィンドウへメッセージを送信（同一オリジンのみ）
+ */
+export function postToParent(message: EditorMessage): void {
+  window.parent.postMessage({ v: PROTOCOL_VERSION, ...message }, window.location.origin);
+}
@@ -0,0 +1,248 @@
+//! ローカルのルールによる書類チェック（APIキー不要）
+//!
+//! 書類種別ごとに次を確認する。
+//! - 必須キーワード（書類の種類が違う・読み取れていない場合の検出）
+//! - 日付（空欄の「令和 年 月 日」、未来の日付）
+//! - 抽出項目（組み込みルール・帳票テンプレート）の形式と、業者名・宛先とプロジェクトの照合
+//! - 帳票テンプレートの未記入欄（OCRトークンがある場合）
+
+use std::collections::HashMap;
+
+use super::{token_location, CheckInput, CheckSource, DocumentChecker, ResultBuilder};
+use crate::models::{CheckResultData, DocStatus, FindingLocation, FormTemplate, OcrDocument, StandardDoc};
+use crate::rules::{field_issues_for_doc, field_specs_for_doc};
+use crate::rules::permit::is_prime;
+use crate::utils::date::{find_date_spans, normalize_date};
+use crate::utils::ocr_blank::detect_blank_fields;
+use crate::utils::ocr_extract::extract_fields;
+use crate::utils::ocr_layout::group_tokens;
+use crate::utils::search::{normalize_chars, normalize_for_search};
+use crate::CheckStatus;
+
+/// 書類種別ごとのルール
+struct DocRule {
+    /// いずれかを含むべき語のまとまり（まとまりごとに1つ以上）
+    keywords: &'static [&'static [&'static str]],
+    /// 記入済みの日付が必要か
+    date_required: bool,
+}
+
+fn doc_rule(kind: StandardDoc) -> DocRule {
+    let (keywords, date_required): (&'static [&'static [&'static str]], bool) = match kind {
+        StandardDoc::Permit => (&[&["建設業"], &["許可"]], true),
+        StandardDoc::OfficeNumber => (&[&["事業所番号", "事業所整理記号", "被保険者"]], false),
+        StandardDoc::LaborInsurance => (&[&["労働保険"]], false),
+        StandardDoc::SiteRepresentativeQualification | StandardDoc::ChiefEngineerQualification => {
+            (&[&["合格", "免許", "資格者証", "修了", "技術検定"]], false)
+        }
+        StandardDoc::SiteRepresentativeEmployment | StandardDoc::ChiefEngineerEmployment => {
+            (&[&["健康保険", "被保険者", "雇用保険", "在籍"]], false)
+        }
+        StandardDoc::ExtraWorkersComp => (&[&["保険"], &["労災", "災害", "補償"]], false),
+        StandardDoc::Kentaikyo => (&[&["退職金共済", "建退共", "掛金"]], false),
+        StandardDoc::WorkerRoster => (&[&["作業員名簿"], &["氏名"], &["生年月日"]], true),
+        StandardDoc::AntiGangPledge => (&[&["誓約"], &["暴力団"]], true),
+    };
+    DocRule { keywords, date_required }
+}
+
+/// 名前の照合（空白・全角半角・法人格の表記を除いて一方が他方を含む）
+fn names_match(a: &str, b: &str) -> bool {
+    let strip = |s: &str| {
+        let mut s = normalize_for_search(s);
+        for mark in ["株式会社", "有限会社", "合同会社", "(株)", "(有)"] {
+            s = s.replace(mark, "");
+        }
+        s
+    };
+    let (a, b) = (strip(a), strip(b));
+    !a.is_empty() && !b.is_empty() && (a.contains(&b) || b.contains(&a))
+}
+
+// ============================================
+// プロバイダー
+// ============================================
+
+/// ローカルのルールによる書類チェック
+pub struct LocalRuleChecker {
+    /// 項目抽出・未記入欄の検出に使う帳票テンプレート
+    templates: Vec<FormTemplate>,
+}
+
+impl LocalRuleChecker {
+    pub fn new(templates: Vec<FormTemplate>) -> Self {
+        LocalRuleChecker { templates }
+    }
+
+    /// 必須キーワード
+    fn check_keywords(result: &mut ResultBuilder, rule: &DocRule, text: &str) {
+        let normalized = normalize_for_search(text);
+        let mut found = Vec::new();
+        for group in rule.keywords {
+            match group.iter().find(|k| normalized.contains(&normalize_for_search(k))) {
+                Some(k) => found.push(*k),
+                None => result.warning(format!(
+                    "「{}」の記載が見つかりません（書類の種類が違うか、文字を読み取れていない可能性があります）",
+                    group.join("」「")
+                )),
+            }
+        }
+        if !found.is_empty() && found.len() == rule.keywords.len() {
+            result.ok(format!("書類の種類を確認しました（{}）", found.join("・")));
+        }
+    }
+
+    /// 日付（空欄・未来の日付）。日付欄が空欄なら true
+    fn check_dates(
+        result: &mut ResultBuilder,
+        rule: &DocRule,
+        lines: &[(String, Option<FindingLocation>)],
+        today: &str,
+    ) -> bool {
+        let mut dates = Vec::new();
+        let mut blanks = Vec::new();
+        for (line, at) in lines {
+            let chars = normalize_chars(line);
+            for (start, end) in find_date_spans(&chars) {
+                let raw: String = chars[start..end].iter().collect();
+                match normalize_date(&raw) {
+                    Some(date) => dates.push((date, at.clone())),
+                    None => blanks.push(at.clone()),
+                }
+            }
+        }
+        for (date, at) in dates.iter().filter(|(d, _)| d.as_str() > today) {
+            result.push("warning", format!("未来の日付が記載されています（{}）", date), at.clone());
+        }
+        let dates: Vec<String> = dates.into_iter().map(|(d, _)| d).collect();
+        if rule.date_required {
+            if let Some(at) = blanks.into_iter().next() {
+                result.push("error", "日付欄が空欄です（年月日が記入されていません）", at);
+                return true;
+            } else if dates.is_empty() {
+                result.warning("日付が見つかりません");
+            } else {
+                result.ok(format!("日付を確認しました（{}）", dates.join("、")));
+            }
+        }
+        false
+    }
+
+    /// 抽出項目とプロジェクトの照合（locations は抽出項目のキーごとの書類上の位置）
+    fn check_fields(
+        result: &mut ResultBuilder,
+        input: &CheckInput,
+        extracted: &HashMap<String, String>,
+        locations: &HashMap<String, FindingLocation>,
+    ) {
+        // 形式チェック（手入力と同じ検証）
+        let doc = DocStatus {
+            status: true,
+            file: None,
+            url: None,
+            note: None,
+            valid_from: None,
+            valid_until: None,
+            check_result: None,
+            last_checked: None,
+            person_id: None,
+            fields: extracted.clone(),
+            history: Vec::new(),
+            check_history: Vec::new(),
+        };
+        let specs = field_specs_for_doc(input.doc_key);
+        for issue in field_issues_for_doc(input.doc_key, &doc) {
+            let label = specs.iter().find(|s| s.key == issue.key).map_or(issue.key, |s| s.label);
+            let message = format!("{}: {}", label, issue.message);
+            let item_type = if issue.status == CheckStatus::Error { "error" } else { "warning" };
+            result.push(item_type, message, locations.get(issue.key).cloned());
+        }
+
+        if let (Some(contractor), Some(company)) = (input.contractor, extracted.get("company_name")) {
+            if !names_match(&contractor.name, company) {
+                result.push(
+                    "warning",
+                    format!("法人名（{}）が業者名（{}）と一致しません", company, contractor.name),
+                    locations.get("company_name").cloned(),
+                );
+            }
+        }
+        if let (Some(project), Some(recipient)) = (input.project, extracted.get("recipient")) {
+            // 宛先は発注者または元請
+            let mut expected: Vec<&str> = project.contractors.iter().filter(|c| is_prime(c)).map(|c| c.name.as_str()).collect();
+            expected.push(project.client.as_str());
+            expected.retain(|n| !n.trim().is_empty());
+            if !expected.is_empty() && !expected.iter().any(|n| names_match(n, recipient)) {
+                result.push(
+                    "warning",
+                    format!("宛先（{}）が発注者・元請（{}）と一致しません", recipient, expected.join("、")),
+                    locations.get("recipient").cloned(),
+                );
+            }
+        }
+    }
+}
+
+impl DocumentChecker for LocalRuleChecker {
+    fn name(&self) -> &'static str {
+        "local-rules"
+    }
+
+    fn check(&self, input: &CheckInput) -> Result<CheckResultData, String> {
+        let text = input.text();
+        let lines = input.lines();
+        if text.trim().is_empty() {
+            return Err("書類の文字がありません（OCR結果を確認してください）".to_string());
+        }
+        let mut result = ResultBuilder::default();
+        let blank_date = match input.kind.standard().map(doc_rule) {
+            Some(rule) => {
+                Self::check_keywords(&mut result, &rule, &text);
+                Self::check_dates(&mut result, &rule, &lines, input.today)
+            }
+            None => {
+                result.warning(format!("{}のルールはありません（日付のみ確認しました）", input.kind.label()));
+                let dates_only = DocRule { keywords: &[], date_required: false };
+                Self::check_dates(&mut result, &dates_only, &lines, input.today)
+            }
+        };
+
+        // 項目抽出と未記入欄（OCRトークンのみ。テキストは日付欄のみ未記入項目にする）
+        let mut extracted = HashMap::new();
+        let mut locations = HashMap::new();
+        if let CheckSource::Text(_) = input.source {
+            if blank_date {
+                result.missing("日付", "日付欄（令和 年 月 日）", None);
+            }
+        }
+        if let CheckSource::Tokens(tokens) = input.source {
+            let doc = OcrDocument {
+                contractor: input.contractor.map(|c| c.name.clone()).unwrap_or_default(),
+                doc_type: input.doc_key.to_string(),
+                image_url: String::new(),
+                tokens: tokens.to_vec(),
+                page_images: Vec::new(),
+            };
+            let layout = group_tokens(tokens);
+            let extraction = extract_fields(&doc, &layout, &self.templates);
+            for label in &extraction.missing {
+                result.missing(label.clone(), "OCRで読み取れず", None);
+            }
+            for blank in detect_blank_fields(&doc, &layout, &self.templates) {
+                if !extraction.missing.contains(&blank.field_name) {
+                    let at = FindingLocation::new(blank.page, blank.position.as_ref());
+                    result.missing(blank.field_name, format!("{}ページの記入欄", blank.page.unwrap_or(1)), at);
+                }
+            }
+            for field in &extraction.fields {
+                if let Some(at) = token_location(tokens, field.tokens.iter().copied()) {
+                    locations.insert(field.key.clone(), at);
+                }
+            }
+            extracted = extraction.extracted_fields();
+        }
+        Self::check_fields(&mut result, input, &extracted, &locations);
+
+        Ok(result.build(self.name(), extracted))
+    }
+}
@@ -0,0 +1,35 @@
+//! モックの書類チェック（入力だけで決まる結果）
+//!
+//! 画面表示・結果の保存・履歴の差分などを、AIやOCRの結果に左右されずに確かめるためのプロバイダー。
+//! 書類の文字数と書類種別の入力項目だけから結果を作る
+
+use std::collections::HashMap;
+
+use super::{CheckInput, DocumentChecker, ResultBuilder};
+use crate::models::CheckResultData;
+use crate::rules::field_specs_for_doc;
+
+/// モックの書類チェック
+pub struct MockChecker;
+
+impl DocumentChecker for MockChecker {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    fn check(&self, input: &CheckInput) -> Result<CheckResultData, String> {
+        let chars = input.text().chars().filter(|c| !c.is_whitespace()).count();
+        let mut result = ResultBuilder::default();
+        if chars == 0 {
+            result.error("文字がありません（モック）");
+        } else {
+            result.ok(format!("{}を確認しました（モック、{}文字）", input.kind.label(), chars));
+        }
+        // 入力項目のある書類は「MOCK-キー」を抽出値にする
+        let extracted: HashMap<String, String> = field_specs_for_doc(input.doc_key)
+            .iter()
+            .map(|spec| (spec.key.to_string(), format!("MOCK-{}", spec.key)))
+            .collect();
+        Ok(result.build(self.name(), extracted))
+    }
+}
@@ -0,0 +1,355 @@
+//! 書類チェックのプロバイダー
+//!
+//! 書類の種類・書類の文字（テキストまたはOCRトークン）・プロジェクトの情報を受け取り、
+//! AIチェックと同じ形の `CheckResultData` を返す。
+//! Gemini によるAIチェックは従来どおりエディタ（iframe）で行い、APIキーがない場合でも
+//! ローカルのルール（キーワード・日付・項目の形式）で書類を確認できるようにする
+//!
+//! | プロバイダー | 内容 |
+//! |--------------|------|
+//! | LocalRuleChecker | 書類種別ごとの必須キーワード・日付・抽出項目の形式・未記入欄 |
+//! | MockChecker | 入力だけで決まる結果（画面の動作確認用。書類には保存しない） |
+
+pub mod local;
+pub mod mock;
+
+pub use local::LocalRuleChecker;
+pub use mock::MockChecker;
+
+use crate::models::{
+    CheckItem, CheckMissingField, CheckResultData, Contractor, DocKind, FindingLocation, FormTemplate, OcrToken,
+    ProjectData,
+};
+use crate::utils::ocr_extract::union_position;
+use crate::utils::ocr_layout::group_tokens;
+
+// ============================================
+// 入力
+// ============================================
+
+/// チェック対象の文字
+#[derive(Clone, Copy)]
+pub enum CheckSource<'a> {
+    /// 抽出済みのテキスト
+    Text(&'a str),
+    /// OCRトークン（行・ブロックにまとめて使う）
+    Tokens(&'a [OcrToken]),
+}
+
+/// チェックの入力
+pub struct CheckInput<'a> {
+    /// 書類キー（例: 09_暴対法誓約書）
+    pub doc_key: &'a str,
+    pub kind: DocKind,
+    pub source: CheckSource<'a>,
+    pub project: Option<&'a ProjectData>,
+    /// 書類を提出した業者（プロジェクトで見つかった場合）
+    pub contractor: Option<&'a Contractor>,
+    /// 今日の日付（yyyy-MM-dd）
+    pub today: &'a str,
+}
+
+impl<'a> CheckInput<'a> {
+    pub fn new(doc_key: &'a str, source: CheckSource<'a>, today: &'a str) -> Self {
+        CheckInput { doc_key, kind: DocKind::parse(doc_key), source, project: None, contractor: None, today }
+    }
+
+    /// プロジェクトと業者（名前で照合）を設定
+    pub fn with_project(mut self, project: Option<&'a ProjectData>, contractor_name: &str) -> Self {
+        self.project = project;
+        self.contractor = project.and_then(|p| p.contractors.iter().find(|c| c.name.trim() == contractor_name.trim()));
+        self
+    }
+
+    /// 書類のテキスト（トークンは行ごとに改行）
+    pub fn text(&self) -> String {
+        match self.source {
+            CheckSource::Text(text) => text.to_string(),
+            CheckSource::Tokens(tokens) => group_tokens(tokens).text(),
+        }
+    }
+
+    /// 書類の行と、その位置（トークンの場合のみ）
+    pub fn lines(&self) -> Vec<(String, Option<FindingLocation>)> {
+        match self.source {
+            CheckSource::Text(text) => text.lines().map(|l| (l.to_string(), None)).collect(),
+            CheckSource::Tokens(tokens) => group_tokens(tokens)
+                .lines()
+                .map(|line| (line.text(), token_location(tokens, line.tokens())))
+                .collect(),
+        }
+    }
+}
+
+/// トークンを囲む領域（最初のトークンのページ分）
+pub fn token_location(tokens: &[OcrToken], indices: impl IntoIterator<Item = usize>) -> Option<FindingLocation> {
+    let mut indices = indices.into_iter().filter_map(|i| tokens.get(i)).peekable();
+    let page = indices.peek()?.page;
+    let bbox = union_position(indices.filter(|t| t.page == page))?;
+    Some(FindingLocation { page, bbox: Some(bbox) })
+}
+
+// ============================================
+// プロバイダー
+// ============================================
+
+/// 書類チェックのプロバイダー
+pub trait DocumentChecker {
+    /// プロバイダー名（CheckResultData.checker に記録）
+    fn name(&self) -> &'static str;
+
+    fn check(&self, input: &CheckInput) -> Result<CheckResultData, String>;
+}
+
+/// 画面で選べるプロバイダー
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CheckerKind {
+    Local,
+    Mock,
+}
+
+impl CheckerKind {
+    pub const ALL: [CheckerKind; 2] = [CheckerKind::Local, CheckerKind::Mock];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            CheckerKind::Local => "ローカルルール",
+            CheckerKind::Mock => "モック（動作確認用）",
+        }
+    }
+
+    /// 結果を書類に保存できるか（モックの結果は書類の判定ではないため保存しない）
+    pub fn can_save(&self) -> bool {
+        !matches!(self, CheckerKind::Mock)
+    }
+
+    /// プロバイダーを作る（ローカルルールは帳票テンプレートで項目を抽出する）
+    pub fn checker(&self, templates: &[FormTemplate]) -> Box<dyn DocumentChecker> {
+        match self {
+            CheckerKind::Local => Box::new(LocalRuleChecker::new(templates.to_vec())),
+            CheckerKind::Mock => Box::new(MockChecker),
+        }
+    }
+}
+
+// ============================================
+// 結果の組み立て
+// ============================================
+
+/// 指摘・未記入項目を集めて CheckResultData を作る
+#[derive(Default)]
+pub struct ResultBuilder {
+    items: Vec<CheckItem>,
+    missing_fields: Vec<CheckMissingField>,
+}
+
+impl ResultBuilder {
+    /// 指摘を追加（位置が分かる場合は書類上の位置付き）
+    pub fn push(&mut self, item_type: &str, message: impl Into<String>, at: Option<FindingLocation>) {
+        let (page, bbox) = at.map_or((None, None), |at| (Some(at.page), at.bbox));
+        self.items.push(CheckItem { item_type: item_type.to_string(), message: message.into(), page, bbox });
+    }
+
+    pub fn ok(&mut self, message: impl Into<String>) {
+        self.push("ok", message, None);
+    }
+
+    pub fn warning(&mut self, message: impl Into<String>) {
+        self.push("warning", message, None);
+    }
+
+    pub fn error(&mut self, message: impl Into<String>) {
+        self.push("error", message, None);
+    }
+
+    pub fn missing(&mut self, field: impl Into<String>, location: impl Into<String>, at: Option<FindingLocation>) {
+        let (page, bbox) = at.map_or((None, None), |at| (Some(at.page), at.bbox));
+        self.missing_fields.push(CheckMissingField { field: field.into(), location: location.into(), page, bbox });
+    }
+
+    /// 最も重い指摘を全体の判定にする（未記入項目は warning 扱い）
+    pub fn build(self, checker: &str, extracted_fields: std::collections::HashMap<String, String>) -> CheckResultData {
+        let count = |t: &str| self.items.iter().filter(|i| i.item_type == t).count();
+        let (errors, warnings) = (count("error"), count("warning") + self.missing_fields.len());
+        let status = if errors > 0 {
+            "error"
+        } else if warnings > 0 {
+            "warning"
+        } else {
+            "ok"
+        };
+        let summary = if errors + warnings == 0 {
+            "指摘はありません".to_string()
+        } else {
+            format!("エラー{}件・注意{}件", errors, warnings)
+        };
+        CheckResultData {
+            status: status.to_string(),
+            summary,
+            items: self.items,
+            missing_fields: self.missing_fields,
+            extracted_fields,
+            checker: Some(checker.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NormalizedCoords, PageSize, PixelCoords};
+
+    const PLEDGE: &str = "09_暴対法誓約書";
+    const TODAY: &str = "2024-05-01";
+
+    /// 1行を1トークンにしたOCR結果（行ごとに下へずらす）
+    fn tokens(page: u32, lines: &[&str]) -> Vec<OcrToken> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                let y = 0.1 + i as f64 * 0.05;
+                OcrToken {
+                    text: text.to_string(),
+                    page,
+                    normalized: NormalizedCoords { x: 0.1, y, width: 0.5, height: 0.02 },
+                    pixels: PixelCoords { x: 100, y: (y * 1000.0) as i32, width: 500, height: 20 },
+                    page_size: PageSize { width: 1000.0, height: 1000.0 },
+                }
+            })
+            .collect()
+    }
+
+    fn checkers() -> Vec<Box<dyn DocumentChecker>> {
+        CheckerKind::ALL.iter().map(|kind| kind.checker(&[])).collect()
+    }
+
+    fn item_types(result: &CheckResultData) -> Vec<&str> {
+        result.items.iter().map(|i| i.item_type.as_str()).collect()
+    }
+
+    // ---- プロバイダー共通 ----
+
+    #[test]
+    fn checkers_record_their_name() {
+        let lines = ["誓約書", "暴力団員ではないことを誓約します", "令和6年4月1日"];
+        let text = lines.join("\n");
+        let tokens = tokens(1, &lines);
+        for checker in checkers() {
+            for source in [CheckSource::Text(&text), CheckSource::Tokens(&tokens)] {
+                let result = checker.check(&CheckInput::new(PLEDGE, source, TODAY)).unwrap();
+                assert_eq!(result.checker.as_deref(), Some(checker.name()));
+                assert!(result.items.iter().all(|i| i.item_type == "ok"), "{}: {:?}", checker.name(), item_types(&result));
+            }
+        }
+    }
+
+    #[test]
+    fn text_and_tokens_read_the_same_lines() {
+        let lines = ["誓約書", "令和6年4月1日"];
+        let text = lines.join("\n");
+        let tokens = tokens(2, &lines);
+        let from_text = CheckInput::new(PLEDGE, CheckSource::Text(&text), TODAY);
+        let from_tokens = CheckInput::new(PLEDGE, CheckSource::Tokens(&tokens), TODAY);
+        let texts = |input: &CheckInput| input.lines().into_iter().map(|(l, _)| l).filter(|l| !l.is_empty()).collect::<Vec<_>>();
+        assert_eq!(texts(&from_text), texts(&from_tokens));
+        assert!(from_text.lines().iter().all(|(_, at)| at.is_none()));
+        assert!(from_tokens.lines().iter().all(|(_, at)| at.as_ref().is_some_and(|at| at.page == 2 && at.bbox.is_some())));
+    }
+
+    // ---- LocalRuleChecker ----
+
+    #[test]
+    fn local_text_blank_date_is_error_and_missing_field() {
+        let text = "誓約書\n暴力団員ではないことを誓約します\n令和　年　月　日";
+        let checker: &dyn DocumentChecker = &LocalRuleChecker::new(Vec::new());
+        let result = checker.check(&CheckInput::new(PLEDGE, CheckSource::Text(text), TODAY)).unwrap();
+        assert_eq!(result.status, "error");
+        assert!(result.items.iter().any(|i| i.item_type == "error" && i.message.contains("日付欄が空欄")));
+        assert_eq!(result.missing_fields.iter().map(|f| f.field.as_str()).collect::<Vec<_>>(), ["日付"]);
+    }
+
+    #[test]
+    fn local_tokens_locate_future_dates() {
+        let tokens = tokens(3, &["誓約書", "暴力団員ではないことを誓約します", "令和7年1月1日"]);
+        let checker: &dyn DocumentChecker = &LocalRuleChecker::new(Vec::new());
+        let result = checker.check(&CheckInput::new(PLEDGE, CheckSource::Tokens(&tokens), TODAY)).unwrap();
+        assert_eq!(result.status, "warning");
+        let future = result.items.iter().find(|i| i.message.contains("未来の日付")).unwrap();
+        assert_eq!(future.page, Some(3));
+        assert!(future.bbox.is_some());
+    }
+
+    #[test]
+    fn local_warns_on_missing_keywords() {
+        let text = "作業員名簿\n令和6年4月1日";
+        let checker: &dyn DocumentChecker = &LocalRuleChecker::new(Vec::new());
+        let result = checker.check(&CheckInput::new(PLEDGE, CheckSource::Text(text), TODAY)).unwrap();
+        assert_eq!(result.status, "warning");
+        assert!(result.items.iter().any(|i| i.message.contains("「誓約」")));
+        assert!(result.items.iter().any(|i| i.message.contains("「暴力団」")));
+    }
+
+    #[test]
+    fn local_rejects_empty_source() {
+        let checker: &dyn DocumentChecker = &LocalRuleChecker::new(Vec::new());
+        assert!(checker.check(&CheckInput::new(PLEDGE, CheckSource::Text("  \n"), TODAY)).is_err());
+        assert!(checker.check(&CheckInput::new(PLEDGE, CheckSource::Tokens(&[]), TODAY)).is_err());
+    }
+
+    // ---- MockChecker ----
+
+    #[test]
+    fn only_real_checkers_can_save() {
+        assert!(CheckerKind::Local.can_save());
+        assert!(!CheckerKind::Mock.can_save());
+    }
+
+    #[test]
+    fn mock_counts_characters_from_either_source() {
+        let lines = ["誓約書", "令和6年4月1日"];
+        let text = lines.join("\n");
+        let tokens = tokens(1, &lines);
+        let checker: &dyn DocumentChecker = &MockChecker;
+        for source in [CheckSource::Text(&text), CheckSource::Tokens(&tokens)] {
+            let result = checker.check(&CheckInput::new(PLEDGE, source, TODAY)).unwrap();
+            assert_eq!(result.status, "ok");
+            assert!(result.items[0].message.contains("11文字"), "{}", result.items[0].message);
+        }
+        let empty = checker.check(&CheckInput::new(PLEDGE, CheckSource::Tokens(&[]), TODAY)).unwrap();
+        assert_eq!(empty.status, "error");
+    }
+
+    // ---- ResultBuilder ----
+
+    #[test]
+    fn result_status_is_most_severe_finding() {
+        let build = |f: &dyn Fn(&mut ResultBuilder)| {
+            let mut result = ResultBuilder::default();
+            f(&mut result);
+            result.build("test", Default::default())
+        };
+
+        let ok = build(&|r| r.ok("確認"));
+        assert_eq!((ok.status.as_str(), ok.summary.as_str()), ("ok", "指摘はありません"));
+
+        let warning = build(&|r| {
+            r.ok("確認");
+            r.warning("注意");
+        });
+        assert_eq!(warning.status, "warning");
+
+        // 未記入項目は warning 扱い
+        let missing = build(&|r| r.missing("日付", "日付欄", None));
+        assert_eq!((missing.status.as_str(), missing.summary.as_str()), ("warning", "エラー0件・注意1件"));
+
+        let error = build(&|r| {
+            r.warning("注意");
+            r.error("誤り");
+            r.ok("確認");
+            r.missing("氏名", "記入欄", None);
+        });
+        assert_eq!((error.status.as_str(), error.summary.as_str()), ("error", "エラー1件・注意2件"));
+        assert_eq!(error.checker.as_deref(), Some("test"));
+    }
+}
@@ -4,7 +4,8 @@
 
 use leptos::*;
 use crate::{ContextMenuState, ProjectContext, CheckMode};
-use crate::models::{ViewMode, DocFileType, detect_file_type};
+use crate::models::{ViewMode, DocChangeSource, DocFileType, detect_file_type};
+use crate::utils::audit::{format_change, get_actor_name, now_iso, record_doc_change};
 use crate::utils::gas::get_gas_url;
 
 /// コンテキストメニュー（操作選択）
@@ -35,6 +36,8 @@ pub fn ContextMenu() -> impl IntoView {
             doc_label: state.doc_label.clone(),
             check_result: None, // 個別のAIチェック結果は別途取得が必要
             last_checked: None,
+            check_diff: None,
+            history: state.history.clone(),
             hover_timer_id: None,
         });
         set_menu_state.set(ContextMenuState::default());
@@ -44,7 +47,7 @@ pub fn ContextMenu() -> impl IntoView {
         {move || {
             let state = menu_state.get();
             if !state.visible {
-                return view! { <></> }.into_view();
+                return ().into_view();
             }
 
             // 画面内に収まるように位置調整
@@ -99,7 +102,7 @@ pub fn ContextMenu() -> impl IntoView {
                             let doc_key = state.doc_key.clone();
                             let contractor_id = state.contractor_id.clone();
                             let set_view_mode = ctx.set_view_mode;
-                            let set_menu = set_menu_state.clone();
+                            let set_menu = set_menu_state;
 
                             let set_tooltip = ctx.set_check_result_tooltip;
                             let on_open = move |_| {
@@ -111,6 +114,7 @@ pub fn ContextMenu() -> impl IntoView {
                                     url: url.clone(),
                                     doc_key: doc_key.clone(),
                                     contractor_id: contractor_id.clone(),
+                                    locate: None,
                                 });
                                 set_menu.set(ContextMenuState::default());
                             };
@@ -123,6 +127,61 @@ pub fn ContextMenu() -> impl IntoView {
                             }
                         })}
 
+                        // AIチェックの指摘箇所（PDF・画像の場合。クリックでそのページを開いて強調）
+                        {state.url.as_ref()
+                            .filter(|url| matches!(detect_file_type(url), DocFileType::Pdf | DocFileType::Image))
+                            .map(|url| {
+                                let findings = ctx.project.with(|p| {
+                                    p.as_ref()
+                                        .and_then(|p| p.contractors.iter().find(|c| c.id == state.contractor_id))
+                                        .and_then(|c| c.docs.get(&state.doc_key))
+                                        .and_then(|d| d.check_result.as_ref())
+                                        .map(|r| r.located_findings())
+                                        .unwrap_or_default()
+                                });
+                                (!findings.is_empty()).then(|| {
+                                    let count = findings.len();
+                                    view! {
+                                        <details class="menu-history menu-findings">
+                                            <summary class="menu-item">
+                                                <span class="menu-icon">"📍"</span>
+                                                <span class="menu-label">{format!("指摘箇所（{}件）", count)}</span>
+                                            </summary>
+                                            <ul class="menu-history-list">
+                                                {findings.into_iter().map(|(message, at)| {
+                                                    let url = url.clone();
+                                                    let contractor = state.contractor_name.clone();
+                                                    let doc_type = state.doc_label.clone();
+                                                    let doc_key = state.doc_key.clone();
+                                                    let contractor_id = state.contractor_id.clone();
+                                                    let set_view_mode = ctx.set_view_mode;
+                                                    let set_tooltip = ctx.set_check_result_tooltip;
+                                                    let page = at.page;
+                                                    let on_locate = move |_| {
+                                                        set_tooltip.set(crate::CheckResultTooltipState::default());
+                                                        set_view_mode.set(ViewMode::PdfViewer {
+                                                            contractor: contractor.clone(),
+                                                            doc_type: doc_type.clone(),
+                                                            url: url.clone(),
+                                                            doc_key: doc_key.clone(),
+                                                            contractor_id: contractor_id.clone(),
+                                                            locate: Some(at.clone()),
+                                                        });
+                                                        set_menu_state.set(ContextMenuState::default());
+                                                    };
+                                                    view! {
+                                                        <li class="locatable" on:click=on_locate>
+                                                            {message}
+                                                            <span class="finding-location">{format!(" 📍 {}ページ", page)}</span>
+                                                        </li>
+                                                    }
+                                                }).collect_view()}
+                                            </ul>
+                                        </details>
+                                    }
+                                })
+                            })}
+
                         // AI自動修正（スプレッドシート/Excelの場合）
                         {state.url.as_ref().and_then(|url| {
                             let file_type = detect_file_type(url);
@@ -134,7 +193,7 @@ pub fn ContextMenu() -> impl IntoView {
                                     let doc_key = state.doc_key.clone();
                                     let contractor_id = state.contractor_id.clone();
                                     let set_view_mode = ctx.set_view_mode;
-                                    let set_menu = set_menu_state.clone();
+                                    let set_menu = set_menu_state;
                                     let set_tooltip = ctx.set_check_result_tooltip;
 
                                     let on_auto_fix = move |_| {
@@ -166,7 +225,7 @@ pub fn ContextMenu() -> impl IntoView {
                             let url = url.clone();
                             let doc_key = state.doc_key.clone();
                             let contractor_id = state.contractor_id.clone();
-                            let set_menu = set_menu_state.clone();
+                            let set_menu = set_menu_state;
                             let set_project = ctx.set_project;
                             let project = ctx.project;
 
@@ -174,8 +233,8 @@ pub fn ContextMenu() -> impl IntoView {
                                 let url = url.clone();
                                 let doc_key = doc_key.clone();
                                 let contractor_id = contractor_id.clone();
-                                let set_project = set_project.clone();
-                                let project = project.clone();
+                                let set_project = set_project;
+                                let project = project;
 
                                 // メニューを閉じる
                                 set_menu.set(ContextMenuState::default());
@@ -197,6 +256,20 @@ pub fn ContextMenu() -> impl IntoView {
                             }
                         })}
 
+                        // 変更履歴（新しい順）
+                        {(!state.history.is_empty()).then(|| view! {
+                            <details class="menu-history">
+                                <summary class="menu-item">
+                                    <span class="menu-icon">"🕘"</span>
+                                    <span class="menu-label">{format!("変更履歴（{}件）", state.history.len())}</span>
+                                </summary>
+                                <ul class="menu-history-list">
+                                    {state.history.iter().rev().map(|change| view! {
+                                        <li>{format_change(change)}</li>
+                                    }).collect_view()}
+                                </ul>
+                            </details>
+                        })}
                     </div>
                 </div>
             }.into_view()
@@ -277,11 +350,12 @@ async fn adopt_fixed_version(
 
     // ProjectDataのURLを更新
     let update_url = format!(
-        "{}?action=updateDocUrl&contractorId={}&docKey={}&newFileId={}",
+        "{}?action=updateDocUrl&contractorId={}&docKey={}&newFileId={}&source=adopt_fixed_version&actor={}",
         gas_url,
         js_sys::encode_uri_component(contractor_id),
         js_sys::encode_uri_component(doc_key),
-        js_sys::encode_uri_component(new_file_id)
+        js_sys::encode_uri_component(new_file_id),
+        js_sys::encode_uri_component(&get_actor_name())
     );
 
     web_sys::console::log_1(&format!("[adopt_fixed_version] update_url: {}", update_url).into());
@@ -327,7 +401,9 @@ async fn adopt_fixed_version(
         for contractor in proj.contractors.iter_mut() {
             if contractor.id == contractor_id {
                 if let Some(doc) = contractor.docs.get_mut(doc_key) {
+                    let before = doc.clone();
                     doc.url = Some(new_url.clone());
+                    record_doc_change(Some(&before), doc, DocChangeSource::AdoptFixedVersion, &get_actor_name(), &now_iso());
                 }
                 break;
             }
@@ -0,0 +1,121 @@
+//! 下請契約一覧エディタ
+//!
+//! 契約ごとの下請負人・請負代金額・税区分・契約日・工種を編集する
+
+use leptos::*;
+use crate::components::row_list::RowList;
+use crate::models::{Contract, TaxHandling};
+use crate::utils::{format_yen, parse_yen};
+
+/// 下請契約一覧の編集コンポーネント
+///
+/// 下請負人は業者名の候補（datalist）から選べるが、候補にない名前も入力できる
+#[component]
+pub fn ContractListEditor<F>(
+    contracts: Vec<Contract>,
+    /// 下請負人の候補（業者名）
+    #[prop(into)]
+    contractor_names: Signal<Vec<String>>,
+    on_update: F,
+) -> impl IntoView
+where
+    F: Fn(Vec<Contract>) + 'static,
+{
+    let (contracts, set_contracts) = create_signal(contracts);
+    let rows = RowList::new(contracts, set_contracts, move |cs: &[Contract]| on_update(cs.to_vec()));
+    let add_contract = move |_| rows.push(Contract::default());
+
+    view! {
+        <div class="contract-list-editor">
+            <div class="docs-header">
+                <span>"下請契約"</span>
+                <button class="add-btn small" on:click=add_contract>"+ 契約追加"</button>
+            </div>
+            <datalist id="contract-contractor-names">
+                {move || contractor_names.get().into_iter().map(|name| view! {
+                    <option value=name />
+                }).collect_view()}
+            </datalist>
+            {move || (0..rows.count()).map(|i| {
+                // 請負代金額は入力中の文字列を保持し、確定時（変更・フォーカス移動）に解釈する
+                let initial = contracts.with_untracked(|cs| cs.get(i).and_then(|c| c.amount).map(format_yen).unwrap_or_default());
+                let (amount_text, set_amount_text) = create_signal(initial);
+                let commit_amount = move || {
+                    let text = amount_text.get_untracked();
+                    if text.trim().is_empty() {
+                        rows.edit(i, |c| c.amount = None);
+                    } else if let Some(amount) = parse_yen(&text) {
+                        rows.edit(i, |c| c.amount = Some(amount));
+                        set_amount_text.set(format_yen(amount));
+                    }
+                };
+
+                view! {
+                    <div class="contract-editor">
+                        <div class="contract-editor-row">
+                            <input type="text" class="contract-name" placeholder="契約名（例: 舗装工事 下請契約書）"
+                                prop:value=move || rows.read(i, |c| c.name.clone())
+                                on:input=move |ev| { let v = event_target_value(&ev); rows.edit(i, |c| c.name = v); }
+                            />
+                            <input type="text" class="contract-contractor" placeholder="下請負人" list="contract-contractor-names"
+                                prop:value=move || rows.read(i, |c| c.contractor.clone().unwrap_or_default())
+                                on:input=move |ev| {
+                                    let v = event_target_value(&ev);
+                                    rows.edit(i, |c| c.contractor = (!v.trim().is_empty()).then(|| v.clone()));
+                                }
+                            />
+                            <input type="text" class="contract-work-type" placeholder="工種"
+                                prop:value=move || rows.read(i, |c| c.work_type.clone().unwrap_or_default())
+                                on:input=move |ev| {
+                                    let v = event_target_value(&ev);
+                                    rows.edit(i, |c| c.work_type = (!v.trim().is_empty()).then(|| v.clone()));
+                                }
+                            />
+                            <button class="delete-btn small" on:click=move |_| rows.remove(i)>"✕"</button>
+                        </div>
+                        <div class="contract-editor-row">
+                            <input type="text" class="contract-amount" placeholder="請負代金額（例: 1,000万）"
+                                class:invalid=move || amount_text.with(|t| !t.trim().is_empty() && parse_yen(t).is_none())
+                                prop:value=amount_text
+                                on:input=move |ev| set_amount_text.set(event_target_value(&ev))
+                                on:change=move |_| commit_amount()
+                            />
+                            <select
+                                on:change=move |ev| {
+                                    let tax = if event_target_value(&ev) == "excluded" { TaxHandling::Excluded } else { TaxHandling::Included };
+                                    rows.edit(i, |c| c.tax = tax);
+                                }
+                            >
+                                {[TaxHandling::Included, TaxHandling::Excluded].into_iter().map(|tax| view! {
+                                    <option
+                                        value=if tax == TaxHandling::Excluded { "excluded" } else { "included" }
+                                        selected=move || rows.read(i, |c| Some(c.tax)) == Some(tax)
+                                    >
+                                        {tax.label()}
+                                    </option>
+                                }).collect_view()}
+                            </select>
+                            <label class="worker-field">
+                                "契約日"
+                                <input type="date"
+                                    prop:value=move || rows.read(i, |c| c.contract_date.clone().unwrap_or_default())
+                                    on:input=move |ev| {
+                                        let v = event_target_value(&ev);
+                                        rows.edit(i, |c| c.contract_date = (!v.is_empty()).then(|| v.clone()));
+                                    }
+                                />
+                            </label>
+                            <input type="url" class="contract-url" placeholder="契約書URL"
+                                prop:value=move || rows.read(i, |c| c.url.clone().unwrap_or_default())
+                                on:input=move |ev| {
+                                    let v = event_target_value(&ev);
+                                    rows.edit(i, |c| c.url = (!v.trim().is_empty()).then(|| v.clone()));
+                                }
+                            />
+                        </div>
+                    </div>
+                }
+            }).collect_view()}
+        </div>
+    }
+}
@@ -4,13 +4,20 @@ use leptos::*;
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 
-use crate::models::{Contractor, DocFileType, ViewMode, detect_file_type};
+use crate::models::{Contractor, DocFileType, DocKind, ViewMode, detect_file_type};
+use crate::rules::ccus::CcusStatus;
+use super::search_bar::{contractor_element_id, doc_element_id};
 use crate::{CheckResultTooltipState, ContextMenuState, ProjectContext};
 
 /// 業者カードコンポーネント
 /// 業者ごとの書類状況を表示し、クリックでドキュメントビューアを開く
 #[component]
-pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
+pub fn ContractorCard(
+    contractor: Contractor,
+    /// 表示する書類キー（絞り込み時。未指定は全書類）
+    #[prop(default = None)]
+    visible_docs: Option<Vec<String>>,
+) -> impl IntoView {
     let ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
     let total = contractor.docs.len();
     let complete = contractor.docs.values().filter(|d| d.status).count();
@@ -29,13 +36,19 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
 
     let contractor_name = contractor.name.clone();
     let contractor_id = contractor.id.clone();
+    let ccus = CcusStatus::of(&contractor);
 
     // ドキュメントをソートして表示
-    let mut docs: Vec<_> = contractor.docs.into_iter().collect();
-    docs.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut docs: Vec<_> = contractor.docs.into_iter()
+        .filter(|(k, _)| visible_docs.as_ref().is_none_or(|keys| keys.contains(k)))
+        .collect();
+    docs.sort_by_cached_key(|(k, _)| DocKind::parse(k));
 
     view! {
-        <div class=format!("contractor-card {}", if is_complete { "complete" } else { "incomplete" })>
+        <div
+            id=contractor_element_id(&contractor_id)
+            class=format!("contractor-card {}", if is_complete { "complete" } else { "incomplete" })
+        >
             <div class="contractor-header">
                 <h4>{contractor.name}</h4>
                 <span class="role">{contractor.role}</span>
@@ -43,6 +56,16 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                 <div class="header-stats">
                     <span class="count">{complete}"/" {total}</span>
 
+                    // CCUS登録状況
+                    {(!ccus.business_registered).then(|| view! {
+                        <span class="ccus-badge missing" title="CCUS事業者IDが未登録です">"CCUS未登録"</span>
+                    })}
+                    {(ccus.business_registered && ccus.workers_missing > 0).then(|| view! {
+                        <span class="ccus-badge partial" title="CCUS技能者IDが未登録の作業員がいます">
+                            "技能者ID未登録 " {ccus.workers_missing}
+                        </span>
+                    })}
+
                     // チェック状況バッジ
                     {(checked_count > 0).then(|| view! {
                         <span class="checked-stats">
@@ -60,8 +83,7 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
 
             <div class="doc-list">
                 {docs.into_iter().map(|(key, status)| {
-                    let label = key.replace("_", " ").chars().skip_while(|c| c.is_numeric()).collect::<String>();
-                    let label = label.trim_start_matches('_').to_string();
+                    let label = DocKind::parse(&key).label();
                     let has_url = status.url.is_some();
                     let url = status.url.clone();
 
@@ -103,6 +125,8 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                     let key_hover = key.clone();
                     let check_result_hover = status.check_result.clone();
                     let last_checked_hover = status.last_checked.clone();
+                    let history_hover = status.history.clone();
+                    let check_diff_hover = status.latest_check_diff();
                     let set_tooltip = ctx.set_check_result_tooltip;
                     let tooltip_state = ctx.check_result_tooltip;
 
@@ -112,6 +136,8 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                     let key_enter = key_hover.clone();
                     let check_result_enter = check_result_hover.clone();
                     let last_checked_enter = last_checked_hover.clone();
+                    let history_enter = history_hover.clone();
+                    let check_diff_enter = check_diff_hover.clone();
                     let on_mouse_enter = move |ev: web_sys::MouseEvent| {
                         let window = web_sys::window().unwrap();
                         // 既存タイマーをキャンセル
@@ -124,6 +150,8 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                         let key = key_enter.clone();
                         let check_result = check_result_enter.clone();
                         let last_checked = last_checked_enter.clone();
+                        let history = history_enter.clone();
+                        let check_diff = check_diff_enter.clone();
                         let x = ev.client_x();
                         let y = ev.client_y();
 
@@ -137,6 +165,8 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                                 doc_label: label,
                                 check_result,
                                 last_checked,
+                                check_diff,
+                                history,
                                 hover_timer_id: None,
                             });
                         }) as Box<dyn FnOnce()>);
@@ -169,6 +199,7 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                     let label_ctx = label.clone();
                     let url_ctx = url.clone();
                     let has_check = status.check_result.is_some();
+                    let history_ctx = status.history.clone();
 
                     let on_context_menu = move |ev: web_sys::MouseEvent| {
                         ev.prevent_default();
@@ -182,6 +213,7 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                             doc_label: label_ctx.clone(),
                             url: url_ctx.clone(),
                             has_check_result: has_check,
+                            history: history_ctx.clone(),
                         });
                     };
 
@@ -193,6 +225,7 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                     let label_touch = label.clone();
                     let url_touch = url.clone();
                     let has_check_touch = status.check_result.is_some();
+                    let history_touch = status.history.clone();
                     let long_press_timer = create_rw_signal(None::<i32>);
 
                     let on_touch_start = move |ev: web_sys::TouchEvent| {
@@ -222,6 +255,7 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                         let doc_label = label_touch.clone();
                         let url = url_touch.clone();
                         let has_check = has_check_touch;
+                        let history = history_touch.clone();
 
                         // 500ms後にコンテキストメニュー表示
                         let closure = Closure::once(Box::new(move || {
@@ -235,6 +269,7 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                                 doc_label,
                                 url,
                                 has_check_result: has_check,
+                                history,
                             });
                         }) as Box<dyn FnOnce()>);
 
@@ -282,6 +317,7 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                                         url: u.clone(),
                                         doc_key: key_click.clone(),
                                         contractor_id: contractor_id_click.clone(),
+                                        locate: None,
                                     });
                                 }
                                 DocFileType::GoogleSpreadsheet | DocFileType::Excel => {
@@ -291,6 +327,7 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
                                         url: u.clone(),
                                         doc_key: key_click.clone(),
                                         contractor_id: contractor_id_click.clone(),
+                                        auto_fix: false,
                                     });
                                 }
                                 _ => {
@@ -305,6 +342,7 @@ pub fn ContractorCard(contractor: Contractor) -> impl IntoView {
 
                     view! {
                         <div
+                            id=doc_element_id(&contractor_id, &key)
                             class=format!("doc-item {} {} {}",
                                 if status.status { "ok" } else { "missing" },
                                 if has_url { "has-link clickable" } else { "" },
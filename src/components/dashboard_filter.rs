@@ -0,0 +1,246 @@
+//! ダッシュボードの絞り込み・並び替え
+//!
+//! 書類の状態（未提出・期限切れ・期限間近・AI指摘・未チェック）、役割、
+//! フリーテキストで業者カードと書類を絞り込む。条件は名前を付けてプロジェクトに保存できる
+
+use leptos::*;
+use crate::models::{Contractor, DashboardFilter, DashboardSort, DocKind, DocStatus, SavedView};
+use crate::utils::cache::save_to_cache;
+use crate::utils::date::add_days;
+use crate::utils::search::normalize_for_search;
+use crate::ProjectContext;
+
+/// 絞り込み後の業者
+#[derive(Debug, Clone)]
+pub struct FilteredContractor {
+    pub contractor: Contractor,
+    /// 表示する書類キー（Noneは全書類）
+    pub visible_docs: Option<Vec<String>>,
+}
+
+/// AIチェック結果の状態
+fn check_status(doc: &DocStatus) -> Option<&str> {
+    doc.check_result.as_ref().map(|r| r.status.as_str())
+}
+
+/// 書類が状態条件のいずれかに該当するか（条件なしは常に該当）
+fn matches_doc_conditions(doc: &DocStatus, filter: &DashboardFilter, today: &str) -> bool {
+    if !filter.has_doc_conditions() {
+        return true;
+    }
+    let valid_until = doc.valid_until.as_deref().filter(|d| !d.is_empty());
+    let expiring = filter.expiring_days.is_some_and(|days| {
+        let limit = add_days(today, days as i64).unwrap_or_else(|| today.to_string());
+        valid_until.is_some_and(|d| d >= today && d <= limit.as_str())
+    });
+
+    (filter.missing && !doc.status)
+        || (filter.expired && valid_until.is_some_and(|d| d < today))
+        || expiring
+        || (filter.ai_error && check_status(doc) == Some("error"))
+        || (filter.ai_warning && check_status(doc) == Some("warning"))
+        || (filter.unchecked && doc.check_result.is_none())
+}
+
+/// 並び替え用の集計値
+fn missing_count(c: &Contractor) -> usize {
+    c.docs.values().filter(|d| !d.status).count()
+}
+
+fn issue_count(c: &Contractor) -> usize {
+    c.docs.values().filter(|d| matches!(check_status(d), Some("error") | Some("warning"))).count()
+}
+
+fn nearest_expiry(c: &Contractor) -> Option<&str> {
+    c.docs.values().filter_map(|d| d.valid_until.as_deref()).filter(|d| !d.is_empty()).min()
+}
+
+/// 業者と書類を絞り込み・並び替え
+pub fn apply_filter(contractors: &[Contractor], filter: &DashboardFilter, today: &str) -> Vec<FilteredContractor> {
+    let terms: Vec<String> = filter.text.split_whitespace().map(normalize_for_search).collect();
+    let text_matches = |text: &str| {
+        let normalized = normalize_for_search(text);
+        terms.iter().all(|t| normalized.contains(t.as_str()))
+    };
+
+    let mut filtered: Vec<FilteredContractor> = contractors.iter()
+        .filter(|c| filter.role.as_deref().is_none_or(|r| c.role == r))
+        .filter_map(|c| {
+            // 業者名・役割が一致すれば書類名では絞らない
+            let contractor_matches = terms.is_empty() || text_matches(&format!("{} {}", c.name, c.role));
+            if !filter.is_active() || (contractor_matches && !filter.has_doc_conditions()) {
+                return Some(FilteredContractor { contractor: c.clone(), visible_docs: None });
+            }
+            let keys: Vec<String> = c.sorted_docs().into_iter()
+                .filter(|(_, d)| matches_doc_conditions(d, filter, today))
+                .filter(|(k, d)| {
+                    contractor_matches
+                        || text_matches(&format!("{} {}", DocKind::parse(k).label(), d.note.as_deref().unwrap_or_default()))
+                })
+                .map(|(k, _)| k.clone())
+                .collect();
+            (!keys.is_empty()).then(|| FilteredContractor { contractor: c.clone(), visible_docs: Some(keys) })
+        })
+        .collect();
+
+    match filter.sort {
+        DashboardSort::Registered => {}
+        DashboardSort::Name => filtered.sort_by(|a, b| a.contractor.name.cmp(&b.contractor.name)),
+        DashboardSort::MostMissing => filtered.sort_by_key(|f| std::cmp::Reverse(missing_count(&f.contractor))),
+        DashboardSort::MostIssues => filtered.sort_by_key(|f| std::cmp::Reverse(issue_count(&f.contractor))),
+        // 期限のない業者は末尾
+        DashboardSort::NearestExpiry => filtered.sort_by(|a, b| {
+            match (nearest_expiry(&a.contractor), nearest_expiry(&b.contractor)) {
+                (Some(x), Some(y)) => x.cmp(y),
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => std::cmp::Ordering::Equal,
+            }
+        }),
+    }
+    filtered
+}
+
+/// 絞り込み条件の入力バー
+#[component]
+pub fn DashboardFilterBar(
+    /// 役割の選択肢
+    roles: Vec<String>,
+) -> impl IntoView {
+    let ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
+    let filter = ctx.dashboard_filter;
+    let set_filter = ctx.set_dashboard_filter;
+    let (view_name, set_view_name) = create_signal(String::new());
+
+    let saved_views = move || ctx.project.with(|p| p.as_ref().map(|p| p.saved_views.clone()).unwrap_or_default());
+
+    // 保存ビューを更新してキャッシュに保存（シートへは「シートに保存」で反映）
+    let update_views = move |f: &dyn Fn(&mut Vec<SavedView>)| {
+        let Some(mut project) = ctx.project.get_untracked() else {
+            return;
+        };
+        f(&mut project.saved_views);
+        save_to_cache(&project);
+        ctx.set_project.set(Some(project));
+    };
+
+    let save_view = move |_| {
+        let name = view_name.get_untracked().trim().to_string();
+        if name.is_empty() {
+            return;
+        }
+        let current = filter.get_untracked();
+        update_views(&|views| {
+            match views.iter_mut().find(|v| v.name == name) {
+                Some(v) => v.filter = current.clone(),
+                None => views.push(SavedView { name: name.clone(), filter: current.clone() }),
+            }
+        });
+    };
+
+    let delete_view = move |_| {
+        let name = view_name.get_untracked();
+        update_views(&|views| views.retain(|v| v.name != name));
+        set_view_name.set(String::new());
+    };
+
+    let toggle = move |label: &'static str, get: fn(&DashboardFilter) -> bool, set: fn(&mut DashboardFilter, bool)| view! {
+        <label class="checkbox-label filter-chip" class:active=move || filter.with(get)>
+            <input type="checkbox"
+                prop:checked=move || filter.with(get)
+                on:change=move |ev| { let v = event_target_checked(&ev); set_filter.update(|f| set(f, v)); }
+            />
+            {label}
+        </label>
+    };
+
+    view! {
+        <div class="dashboard-filter">
+            <div class="filter-row">
+                <input type="search" class="filter-text" placeholder="業者名・書類名・備考で絞り込み"
+                    prop:value=move || filter.with(|f| f.text.clone())
+                    on:input=move |ev| { let v = event_target_value(&ev); set_filter.update(|f| f.text = v); }
+                />
+                <select on:change=move |ev| {
+                    let v = event_target_value(&ev);
+                    set_filter.update(|f| f.role = (!v.is_empty()).then_some(v));
+                }>
+                    <option value="" selected=move || filter.with(|f| f.role.is_none())>"全ての役割"</option>
+                    {roles.into_iter().map(|role| {
+                        let r = role.clone();
+                        view! {
+                            <option value=role.clone() selected=move || filter.with(|f| f.role.as_deref() == Some(r.as_str()))>{role}</option>
+                        }
+                    }).collect_view()}
+                </select>
+                <select on:change=move |ev| {
+                    let sort = DashboardSort::from_key(&event_target_value(&ev));
+                    set_filter.update(|f| f.sort = sort);
+                }>
+                    {DashboardSort::ALL.into_iter().map(|sort| view! {
+                        <option value=sort.key() selected=move || filter.with(|f| f.sort == sort)>{sort.label()}</option>
+                    }).collect_view()}
+                </select>
+                <button class="add-btn small" on:click=move |_| set_filter.set(DashboardFilter::default())
+                    disabled=move || filter.with(|f| !f.is_active() && f.sort == DashboardSort::Registered)>
+                    "条件をクリア"
+                </button>
+            </div>
+
+            <div class="filter-row">
+                {toggle("未提出", |f| f.missing, |f, v| f.missing = v)}
+                {toggle("期限切れ", |f| f.expired, |f, v| f.expired = v)}
+                <label class="checkbox-label filter-chip" class:active=move || filter.with(|f| f.expiring_days.is_some())>
+                    <input type="checkbox"
+                        prop:checked=move || filter.with(|f| f.expiring_days.is_some())
+                        on:change=move |ev| {
+                            let v = event_target_checked(&ev);
+                            set_filter.update(|f| f.expiring_days = v.then_some(30));
+                        }
+                    />
+                    <input type="number" class="filter-days" min="1"
+                        prop:value=move || filter.with(|f| f.expiring_days.unwrap_or(30).to_string())
+                        on:input=move |ev| {
+                            let days = event_target_value(&ev).parse().ok().filter(|d| *d > 0);
+                            set_filter.update(|f| f.expiring_days = days);
+                        }
+                    />
+                    "日以内に期限"
+                </label>
+                {toggle("AI要対応", |f| f.ai_error, |f, v| f.ai_error = v)}
+                {toggle("AI要確認", |f| f.ai_warning, |f, v| f.ai_warning = v)}
+                {toggle("未チェック", |f| f.unchecked, |f, v| f.unchecked = v)}
+            </div>
+
+            <div class="filter-row saved-views">
+                {move || saved_views().into_iter().map(|v| {
+                    let name = v.name.clone();
+                    let applied = v.filter.clone();
+                    view! {
+                        <button
+                            class="saved-view-btn"
+                            class:active=move || filter.with(|f| *f == applied)
+                            on:click=move |_| {
+                                set_filter.set(v.filter.clone());
+                                set_view_name.set(v.name.clone());
+                            }
+                        >
+                            {name}
+                        </button>
+                    }
+                }).collect_view()}
+                <input type="text" class="saved-view-name" placeholder="ビュー名（例: 今週の督促対象）"
+                    prop:value=move || view_name.get()
+                    on:input=move |ev| set_view_name.set(event_target_value(&ev))
+                />
+                <button class="add-btn small" on:click=save_view disabled=move || view_name.with(|n| n.trim().is_empty())>
+                    "ビューを保存"
+                </button>
+                <button class="delete-btn small" on:click=delete_view
+                    disabled=move || { let name = view_name.get(); !saved_views().iter().any(|v| v.name == name) }>
+                    "削除"
+                </button>
+            </div>
+        </div>
+    }
+}
@@ -5,25 +5,57 @@
 use leptos::*;
 use std::collections::HashMap;
 use wasm_bindgen_futures::spawn_local;
-use crate::models::{Contractor, DocStatus, ProjectData, DocLink};
-use crate::ProjectContext;
+use crate::models::{Contractor, DocChangeSource, DocKind, DocStatus, ProjectData, DocLink, Person, RequestLetterTemplate, StandardDoc};
+use crate::views::request_letter::{is_default_template, TEMPLATE_PLACEHOLDERS};
+use crate::components::contract_editor::ContractListEditor;
+use crate::components::worker_editor::WorkerRosterEditor;
+use crate::rules::{field_issues_for_doc, field_specs_for_doc};
+use crate::rules::ccus::{import_ccus_csv, normalize_ccus_id};
+use crate::rules::engineer::{determine_engineer_requirement, Dedication};
+use crate::rules::ledger::determine_ledger_requirement;
+use crate::rules::persons::is_person_doc;
+use crate::{CheckStatus, ProjectContext};
 use crate::utils::gas::{get_gas_url, save_to_gas};
+use crate::utils::audit::record_project_changes;
 use crate::utils::cache::save_to_cache;
+use crate::utils::{format_yen, parse_yen, read_file_as_text};
 
-/// 標準的な書類リスト
-pub const STANDARD_DOCS: &[(&str, &str)] = &[
-    ("01_建設業許可", "建設業許可"),
-    ("02_事業所番号", "事業所番号"),
-    ("03_労働保険番号", "労働保険番号"),
-    ("041_現場代理人資格", "現場代理人資格"),
-    ("042_現場代理人在籍", "現場代理人在籍"),
-    ("051_主任技術者資格", "主任技術者資格"),
-    ("052_主任技術者在籍", "主任技術者在籍"),
-    ("06_法定外労災", "法定外労災"),
-    ("07_建退共", "建退共"),
-    ("08_作業員名簿", "作業員名簿"),
-    ("09_暴対法誓約書", "暴対法誓約書"),
-];
+/// 技術者資格登録簿からの人物選択
+///
+/// 選択解除時は `None` を通知する
+#[component]
+fn PersonSelect<S, F>(
+    selected: S,
+    on_select: F,
+) -> impl IntoView
+where
+    S: Fn() -> Option<String> + 'static,
+    F: Fn(Option<Person>) + 'static,
+{
+    let ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
+    let registry = ctx.person_registry;
+    let selected = store_value(selected);
+
+    view! {
+        <select class="person-select"
+            on:change=move |ev| {
+                let id = event_target_value(&ev);
+                on_select(registry.with(|ps| ps.iter().find(|p| p.id == id).cloned()));
+            }
+        >
+            <option value="" selected=move || selected.with_value(|s| s()).is_none()>"登録簿から選択"</option>
+            {move || registry.get().into_iter().map(|p| {
+                let id = p.id.clone();
+                let label = if p.company.is_empty() { p.name.clone() } else { format!("{}（{}）", p.name, p.company) };
+                view! {
+                    <option value=p.id.clone() selected=move || selected.with_value(|s| s()).as_deref() == Some(id.as_str())>
+                        {label}
+                    </option>
+                }
+            }).collect_view()}
+        </select>
+    }
+}
 
 /// プロジェクト全体書類の編集用コンポーネント
 #[component]
@@ -89,9 +121,30 @@ pub fn ProjectEditor(project: ProjectData) -> impl IntoView {
     let (period_end, set_period_end) = create_signal(project.period_end.clone().unwrap_or_default());
     let (site_representative, set_site_representative) = create_signal(project.site_representative.clone().unwrap_or_default());
     let (chief_engineer, set_chief_engineer) = create_signal(project.chief_engineer.clone().unwrap_or_default());
+    let (site_representative_id, set_site_representative_id) = create_signal(project.site_representative_id.clone());
+    let (chief_engineer_id, set_chief_engineer_id) = create_signal(project.chief_engineer_id.clone());
+    let (contract_amount, set_contract_amount) = create_signal(project.contract_amount.map(|v| v.to_string()).unwrap_or_default());
+    let (subcontract_total, set_subcontract_total) = create_signal(project.subcontract_total.map(|v| v.to_string()).unwrap_or_default());
     let (project_docs, set_project_docs) = create_signal(project.project_docs.clone());
     let (contractors, set_contractors) = create_signal(project.contractors.clone());
-    let (contracts, _) = create_signal(project.contracts.clone());
+    let (public_works, set_public_works) = create_signal(project.public_works);
+    let (contracts, set_contracts) = create_signal(project.contracts.clone());
+    let (request_template, set_request_template) = create_signal(project.request_template.clone().unwrap_or_default());
+
+    // 施工体制台帳の作成義務（編集中の契約・区分から都度判定）
+    let original = store_value(project);
+    let editing_project = move || ProjectData {
+        contract_amount: parse_yen(&contract_amount.get()),
+        subcontract_total: parse_yen(&subcontract_total.get()),
+        public_works: public_works.get(),
+        contractors: contractors.get(),
+        contracts: contracts.get(),
+        ..original.get_value()
+    };
+    let ledger_requirement = create_memo(move |_| determine_ledger_requirement(&editing_project()));
+    // 配置技術者（監理/主任）と専任の要否
+    let engineer_requirement = create_memo(move |_| determine_engineer_requirement(&editing_project()));
+    let contractor_names = Signal::derive(move || contractors.with(|cs| cs.iter().map(|c| c.name.clone()).collect::<Vec<_>>()));
 
     // 保存状態
     let (saving, set_saving) = create_signal(false);
@@ -104,7 +157,7 @@ pub fn ProjectEditor(project: ProjectData) -> impl IntoView {
         let sr = site_representative.get();
         let ce = chief_engineer.get();
 
-        let updated = ProjectData {
+        let mut updated = ProjectData {
             project_name: project_name.get(),
             client: client.get(),
             period: period.get(),
@@ -112,10 +165,20 @@ pub fn ProjectEditor(project: ProjectData) -> impl IntoView {
             period_end: if pe.is_empty() { None } else { Some(pe) },
             site_representative: if sr.is_empty() { None } else { Some(sr) },
             chief_engineer: if ce.is_empty() { None } else { Some(ce) },
+            site_representative_id: site_representative_id.get(),
+            chief_engineer_id: chief_engineer_id.get(),
+            contract_amount: parse_yen(&contract_amount.get()),
+            subcontract_total: parse_yen(&subcontract_total.get()),
+            public_works: public_works.get(),
             project_docs: project_docs.get(),
             contractors: contractors.get(),
             contracts: contracts.get(),
+            request_template: Some(request_template.get()).filter(|t| !is_default_template(t)),
+            saved_views: original.with_value(|p| p.saved_views.clone()),
         };
+        // 提出状況・URLの変更を履歴に記録（次回保存時は今回の保存内容と比較）
+        original.with_value(|before| record_project_changes(before, &mut updated, DocChangeSource::Manual));
+        original.set_value(updated.clone());
 
         // ローカル状態を更新
         ctx.set_project.set(Some(updated.clone()));
@@ -155,7 +218,10 @@ pub fn ProjectEditor(project: ProjectData) -> impl IntoView {
                 id: new_id,
                 name: "新規業者".to_string(),
                 role: "".to_string(),
+                email: None,
+                ccus_business_id: None,
                 docs: HashMap::new(),
+                workers: Vec::new(),
             });
         });
     };
@@ -169,6 +235,36 @@ pub fn ProjectEditor(project: ProjectData) -> impl IntoView {
         });
     };
 
+    // CCUS CSV取込（事業者ID・技能者IDを名簿に反映）
+    let (ccus_message, set_ccus_message) = create_signal(None::<String>);
+    let on_ccus_import = move |ev: web_sys::Event| {
+        let input: web_sys::HtmlInputElement = event_target(&ev);
+        let Some(file) = input.files().and_then(|files| files.get(0)) else {
+            return;
+        };
+        input.set_value("");
+        read_file_as_text(&file, move |text| {
+            let mut updated = contractors.get_untracked();
+            match import_ccus_csv(&mut updated, &text) {
+                Ok(summary) => {
+                    set_contractors.set(updated);
+                    let mut msg = format!(
+                        "CCUS取込: 事業者ID {}件、技能者ID {}件を更新",
+                        summary.contractors_updated, summary.workers_updated
+                    );
+                    if !summary.unmatched.is_empty() {
+                        msg.push_str(&format!(" / 照合できず: {}", summary.unmatched.join("、")));
+                    }
+                    if !summary.invalid.is_empty() {
+                        msg.push_str(&format!(" / 形式不正: {}", summary.invalid.join("、")));
+                    }
+                    set_ccus_message.set(Some(msg));
+                }
+                Err(e) => set_ccus_message.set(Some(format!("CCUS取込エラー: {}", e))),
+            }
+        });
+    };
+
     // 業者更新
     let update_contractor = move |idx: usize, updated: Contractor| {
         set_contractors.update(|cs| {
@@ -229,6 +325,31 @@ pub fn ProjectEditor(project: ProjectData) -> impl IntoView {
                         />
                     </div>
                 </div>
+                <div class="form-group">
+                    <label>"請負代金額（税込・円）"</label>
+                    <input type="text" inputmode="numeric"
+                        prop:value=move || contract_amount.get()
+                        on:input=move |ev| set_contract_amount.set(event_target_value(&ev))
+                        placeholder="例: 48000000（技術者の専任要否の判定に使用）"
+                    />
+                </div>
+                <div class="form-group">
+                    <label>"下請契約総額（税込・円）"</label>
+                    <input type="text" inputmode="numeric"
+                        prop:value=move || subcontract_total.get()
+                        on:input=move |ev| set_subcontract_total.set(event_target_value(&ev))
+                        placeholder="契約一覧に金額がない場合に入力（例: 52000000）"
+                    />
+                </div>
+                <div class="form-group">
+                    <label class="checkbox-label">
+                        <input type="checkbox"
+                            prop:checked=move || public_works.get()
+                            on:change=move |ev| set_public_works.set(event_target_checked(&ev))
+                        />
+                        "公共工事"
+                    </label>
+                </div>
                 // 旧形式の工期（表示のみ、新データでは使わない）
                 {move || {
                     let p = period.get();
@@ -251,21 +372,80 @@ pub fn ProjectEditor(project: ProjectData) -> impl IntoView {
                         <label>"現場代理人"</label>
                         <input type="text"
                             prop:value=move || site_representative.get()
-                            on:input=move |ev| set_site_representative.set(event_target_value(&ev))
+                            on:input=move |ev| {
+                                // 手入力した場合は登録簿との連携を解除
+                                set_site_representative.set(event_target_value(&ev));
+                                set_site_representative_id.set(None);
+                            }
                             placeholder="例: 山田太郎"
                         />
+                        <PersonSelect
+                            selected=move || site_representative_id.get()
+                            on_select=move |p| {
+                                if let Some(ref p) = p {
+                                    set_site_representative.set(p.name.clone());
+                                }
+                                set_site_representative_id.set(p.map(|p| p.id));
+                            }
+                        />
                     </div>
                     <div class="form-group">
-                        <label>"主任技術者"</label>
+                        <label>
+                            {move || engineer_requirement.get().kind.label()}
+                            {move || match engineer_requirement.get().dedication {
+                                Dedication::Required | Dedication::RequiredUnlessResidence => Some(view! {
+                                    <span class="engineer-dedicated-badge">"専任"</span>
+                                }),
+                                _ => None,
+                            }}
+                        </label>
                         <input type="text"
                             prop:value=move || chief_engineer.get()
-                            on:input=move |ev| set_chief_engineer.set(event_target_value(&ev))
+                            on:input=move |ev| {
+                                set_chief_engineer.set(event_target_value(&ev));
+                                set_chief_engineer_id.set(None);
+                            }
                             placeholder="例: 鈴木一郎"
                         />
+                        <span class="hint" title=move || engineer_requirement.get().dedication_reason>
+                            {move || engineer_requirement.get().kind_reason}
+                        </span>
+                        <PersonSelect
+                            selected=move || chief_engineer_id.get()
+                            on_select=move |p| {
+                                if let Some(ref p) = p {
+                                    set_chief_engineer.set(p.name.clone());
+                                }
+                                set_chief_engineer_id.set(p.map(|p| p.id));
+                            }
+                        />
                     </div>
                 </div>
             </div>
 
+            <div class="editor-section">
+                <h3>"下請契約"</h3>
+                <ContractListEditor
+                    contracts=contracts.get_untracked()
+                    contractor_names=contractor_names
+                    on_update=move |cs| set_contracts.set(cs)
+                />
+                {move || {
+                    let req = ledger_requirement.get();
+                    view! {
+                        <div class=format!("ledger-requirement {}", if req.required { "required" } else { "not-required" })>
+                            {req.total.as_ref().map(|t| view! {
+                                <span class="ledger-total">
+                                    "下請総額（税込）: " {format_yen(t.amount)}
+                                    {(!t.computed).then_some("（手入力）")}
+                                </span>
+                            })}
+                            <span class="ledger-reason">{req.reason.clone()}</span>
+                        </div>
+                    }
+                }}
+            </div>
+
             <div class="editor-section">
                 <h3>"全体書類"</h3>
                 <div class="project-docs-editor">
@@ -287,11 +467,48 @@ pub fn ProjectEditor(project: ProjectData) -> impl IntoView {
                 </div>
             </div>
 
+            <div class="editor-section">
+                <div class="section-header">
+                    <h3>"提出依頼テンプレート"</h3>
+                    <button class="add-btn small" on:click=move |_| set_request_template.set(RequestLetterTemplate::default())>
+                        "既定に戻す"
+                    </button>
+                </div>
+                <div class="form-group">
+                    <label>"件名"</label>
+                    <input type="text"
+                        prop:value=move || request_template.get().subject
+                        on:input=move |ev| set_request_template.update(|t| t.subject = event_target_value(&ev))
+                    />
+                </div>
+                <div class="form-group">
+                    <label>"本文"</label>
+                    <textarea class="template-body-input" rows="12"
+                        prop:value=move || request_template.get().body
+                        on:input=move |ev| set_request_template.update(|t| t.body = event_target_value(&ev))
+                    ></textarea>
+                </div>
+                <div class="template-placeholders">
+                    {TEMPLATE_PLACEHOLDERS.iter().map(|(name, desc)| view! {
+                        <span class="placeholder-chip" title=*desc>{*name}</span>
+                    }).collect_view()}
+                </div>
+            </div>
+
             <div class="editor-section">
                 <div class="section-header">
                     <h3>"業者一覧"</h3>
+                    <label class="add-btn file-btn">
+                        "CCUS CSV取込"
+                        <input type="file" accept=".csv" on:change=on_ccus_import style="display:none" />
+                    </label>
                     <button class="add-btn" on:click=add_contractor>"+ 業者追加"</button>
                 </div>
+                {move || ccus_message.get().map(|msg| view! {
+                    <div class=format!("save-message {}", if msg.contains("エラー") { "error" } else { "success" })>
+                        {msg}
+                    </div>
+                })}
 
                 <div class="contractors-editor">
                     {move || contractors.get().into_iter().enumerate().map(|(idx, c)| {
@@ -324,10 +541,27 @@ where
 {
     let (name, set_name) = create_signal(contractor.name.clone());
     let (role, set_role) = create_signal(contractor.role.clone());
+    let (email, set_email) = create_signal(contractor.email.clone().unwrap_or_default());
+    let (ccus_business_id, set_ccus_business_id) = create_signal(contractor.ccus_business_id.clone().unwrap_or_default());
     let (docs, set_docs) = create_signal(contractor.docs.clone());
+    let (workers, set_workers) = create_signal(contractor.workers.clone());
     let (expanded, set_expanded) = create_signal(false);
 
-    let contractor_id = contractor.id.clone();
+    // 編集中の値から業者データを組み立て（編集対象外のフィールドは元データを維持）
+    let original = store_value(contractor);
+    let current = move || {
+        let e = email.get();
+        let ccus = ccus_business_id.get();
+        Contractor {
+            name: name.get(),
+            role: role.get(),
+            email: if e.trim().is_empty() { None } else { Some(e) },
+            ccus_business_id: if ccus.trim().is_empty() { None } else { Some(ccus.trim().to_string()) },
+            docs: docs.get(),
+            workers: workers.get(),
+            ..original.get_value()
+        }
+    };
 
     view! {
         <div class="contractor-editor">
@@ -336,16 +570,10 @@ where
                 <input type="text" class="name-input"
                     prop:value=move || name.get()
                     on:input={
-                        let contractor_id = contractor_id.clone();
                         let on_update = on_update.clone();
                         move |ev| {
                             set_name.set(event_target_value(&ev));
-                            on_update(Contractor {
-                                id: contractor_id.clone(),
-                                name: name.get(),
-                                role: role.get(),
-                                docs: docs.get(),
-                            });
+                            on_update(current());
                         }
                     }
                     on:click=move |ev| ev.stop_propagation()
@@ -353,16 +581,36 @@ where
                 <input type="text" class="role-input" placeholder="役割"
                     prop:value=move || role.get()
                     on:input={
-                        let contractor_id = contractor_id.clone();
                         let on_update = on_update.clone();
                         move |ev| {
                             set_role.set(event_target_value(&ev));
-                            on_update(Contractor {
-                                id: contractor_id.clone(),
-                                name: name.get(),
-                                role: role.get(),
-                                docs: docs.get(),
-                            });
+                            on_update(current());
+                        }
+                    }
+                    on:click=move |ev| ev.stop_propagation()
+                />
+                <input type="email" class="email-input" placeholder="メールアドレス"
+                    prop:value=move || email.get()
+                    on:input={
+                        let on_update = on_update.clone();
+                        move |ev| {
+                            set_email.set(event_target_value(&ev));
+                            on_update(current());
+                        }
+                    }
+                    on:click=move |ev| ev.stop_propagation()
+                />
+                <input type="text" class="ccus-input" placeholder="CCUS事業者ID（14桁）" inputmode="numeric"
+                    class:invalid=move || {
+                        let id = ccus_business_id.get();
+                        !id.trim().is_empty() && normalize_ccus_id(&id).is_err()
+                    }
+                    prop:value=move || ccus_business_id.get()
+                    on:input={
+                        let on_update = on_update.clone();
+                        move |ev| {
+                            set_ccus_business_id.set(event_target_value(&ev));
+                            on_update(current());
                         }
                     }
                     on:click=move |ev| ev.stop_propagation()
@@ -376,14 +624,13 @@ where
             {move || {
                 let is_expanded = expanded.get();
                 let on_update = on_update.clone();
-                let contractor_id = contractor_id.clone();
 
                 is_expanded.then(|| {
                     let mut doc_list: Vec<_> = docs.get().into_iter().collect();
-                    doc_list.sort_by(|a, b| a.0.cmp(&b.0));
+                    doc_list.sort_by_cached_key(|(k, _)| DocKind::parse(k));
 
                     let on_update_add = on_update.clone();
-                    let contractor_id_add = contractor_id.clone();
+                    let on_update_workers = on_update.clone();
 
                     view! {
                         <div class="docs-editor">
@@ -391,9 +638,10 @@ where
                                 <span>"書類一覧"</span>
                                 <button class="add-btn small" on:click=move |_| {
                                     set_docs.update(|d| {
-                                        for (key, _) in STANDARD_DOCS {
-                                            if !d.contains_key(*key) {
-                                                d.insert(key.to_string(), DocStatus {
+                                        for doc in StandardDoc::ALL {
+                                            // 名称違いの旧キーがあれば追加しない
+                                            if !d.keys().any(|k| DocKind::parse(k).is(doc)) {
+                                                d.insert(doc.key(), DocStatus {
                                                     status: false,
                                                     file: None,
                                                     url: None,
@@ -402,17 +650,16 @@ where
                                                     valid_until: None,
                                                     check_result: None,
                                                     last_checked: None,
+                                                    person_id: None,
+                                                    fields: HashMap::new(),
+                                                    history: Vec::new(),
+                                                    check_history: Vec::new(),
                                                 });
                                                 break;
                                             }
                                         }
                                     });
-                                    on_update_add(Contractor {
-                                        id: contractor_id_add.clone(),
-                                        name: name.get(),
-                                        role: role.get(),
-                                        docs: docs.get(),
-                                    });
+                                    on_update_add(current());
                                 }>"+ 書類追加"</button>
                             </div>
                             {doc_list.into_iter().map(|(key, status)| {
@@ -420,31 +667,19 @@ where
                                 let key_for_delete = key.clone();
                                 let on_update_doc = on_update.clone();
                                 let on_update_del = on_update.clone();
-                                let contractor_id_doc = contractor_id.clone();
-                                let contractor_id_del = contractor_id.clone();
 
                                 let update_doc = move |updated_status: DocStatus| {
                                     set_docs.update(|d| {
                                         d.insert(key_clone.clone(), updated_status);
                                     });
-                                    on_update_doc(Contractor {
-                                        id: contractor_id_doc.clone(),
-                                        name: name.get(),
-                                        role: role.get(),
-                                        docs: docs.get(),
-                                    });
+                                    on_update_doc(current());
                                 };
 
                                 let delete_doc = move |_| {
                                     set_docs.update(|d| {
                                         d.remove(&key_for_delete);
                                     });
-                                    on_update_del(Contractor {
-                                        id: contractor_id_del.clone(),
-                                        name: name.get(),
-                                        role: role.get(),
-                                        docs: docs.get(),
-                                    });
+                                    on_update_del(current());
                                 };
 
                                 view! {
@@ -457,6 +692,13 @@ where
                                 }
                             }).collect_view()}
                         </div>
+                        <WorkerRosterEditor
+                            workers=workers.get_untracked()
+                            on_update=move |ws| {
+                                set_workers.set(ws);
+                                on_update_workers(current());
+                            }
+                        />
                     }
                 })
             }}
@@ -481,28 +723,41 @@ where
     let (url, set_url) = create_signal(status.url.clone().unwrap_or_default());
     let (valid_until, set_valid_until) = create_signal(status.valid_until.clone().unwrap_or_default());
     let (note, set_note) = create_signal(status.note.clone().unwrap_or_default());
+    let (person_id, set_person_id) = create_signal(status.person_id.clone());
+    let (fields, set_fields) = create_signal(status.fields.clone());
+    let field_specs = field_specs_for_doc(&doc_key);
+    // AIチェックの抽出値（手入力が空のときのプレースホルダー表示用）
+    let extracted = status.check_result.as_ref().map(|r| r.extracted_fields.clone()).unwrap_or_default();
+
+    let label = DocKind::parse(&doc_key).label();
+    let is_person = is_person_doc(&doc_key);
+
+    // 編集中の値から書類データを組み立て（編集対象外のフィールドは元データを維持）
+    let original = store_value(status);
+    let non_empty = |s: String| if s.is_empty() { None } else { Some(s) };
+    let current = move || DocStatus {
+        status: doc_status.get(),
+        file: non_empty(file.get()),
+        url: non_empty(url.get()),
+        note: non_empty(note.get()),
+        valid_until: non_empty(valid_until.get()),
+        person_id: person_id.get(),
+        fields: fields.get(),
+        ..original.get_value()
+    };
 
-    // 既存データを保持（編集時に消えないように）
-    let original_valid_from = status.valid_from.clone();
-    let original_check_result = status.check_result.clone();
-    let original_last_checked = status.last_checked.clone();
-
-    let label = doc_key.replace("_", " ").chars().skip_while(|c| c.is_numeric()).collect::<String>();
-    let label = label.trim_start_matches('_').to_string();
+    // 書類項目の形式エラー（入力のたびに再検証）
+    let issue_doc_key = doc_key.clone();
+    let field_issues = create_memo(move |_| field_issues_for_doc(&issue_doc_key, &current()));
 
     // 各イベント用にon_updateをクローン
     let on_update_1 = on_update.clone();
     let on_update_2 = on_update.clone();
     let on_update_3 = on_update.clone();
     let on_update_4 = on_update.clone();
-    let on_update_5 = on_update;
-
-    // 各ハンドラ用に既存値をクローン
-    let (vf1, cr1, lc1) = (original_valid_from.clone(), original_check_result.clone(), original_last_checked.clone());
-    let (vf2, cr2, lc2) = (original_valid_from.clone(), original_check_result.clone(), original_last_checked.clone());
-    let (vf3, cr3, lc3) = (original_valid_from.clone(), original_check_result.clone(), original_last_checked.clone());
-    let (vf4, cr4, lc4) = (original_valid_from.clone(), original_check_result.clone(), original_last_checked.clone());
-    let (vf5, cr5, lc5) = (original_valid_from, original_check_result, original_last_checked);
+    let on_update_5 = on_update.clone();
+    let on_update_6 = on_update.clone();
+    let on_update_7 = on_update;
 
     view! {
         <div class=format!("doc-editor {}", if doc_status.get() { "complete" } else { "incomplete" })>
@@ -512,16 +767,7 @@ where
                         prop:checked=move || doc_status.get()
                         on:change=move |ev| {
                             set_doc_status.set(event_target_checked(&ev));
-                            on_update_1(DocStatus {
-                                status: doc_status.get(),
-                                file: if file.get().is_empty() { None } else { Some(file.get()) },
-                                url: if url.get().is_empty() { None } else { Some(url.get()) },
-                                note: if note.get().is_empty() { None } else { Some(note.get()) },
-                                valid_from: vf1.clone(),
-                                valid_until: if valid_until.get().is_empty() { None } else { Some(valid_until.get()) },
-                                check_result: cr1.clone(),
-                                last_checked: lc1.clone(),
-                            });
+                            on_update_1(current());
                         }
                     />
                     <span class="doc-label">{label}</span>
@@ -533,67 +779,90 @@ where
                     prop:value=move || file.get()
                     on:input=move |ev| {
                         set_file.set(event_target_value(&ev));
-                        on_update_2(DocStatus {
-                            status: doc_status.get(),
-                            file: if file.get().is_empty() { None } else { Some(file.get()) },
-                            url: if url.get().is_empty() { None } else { Some(url.get()) },
-                            note: if note.get().is_empty() { None } else { Some(note.get()) },
-                            valid_from: vf2.clone(),
-                            valid_until: if valid_until.get().is_empty() { None } else { Some(valid_until.get()) },
-                            check_result: cr2.clone(),
-                            last_checked: lc2.clone(),
-                        });
+                        on_update_2(current());
                     }
                 />
                 <input type="text" placeholder="URL"
                     prop:value=move || url.get()
                     on:input=move |ev| {
                         set_url.set(event_target_value(&ev));
-                        on_update_3(DocStatus {
-                            status: doc_status.get(),
-                            file: if file.get().is_empty() { None } else { Some(file.get()) },
-                            url: if url.get().is_empty() { None } else { Some(url.get()) },
-                            note: if note.get().is_empty() { None } else { Some(note.get()) },
-                            valid_from: vf3.clone(),
-                            valid_until: if valid_until.get().is_empty() { None } else { Some(valid_until.get()) },
-                            check_result: cr3.clone(),
-                            last_checked: lc3.clone(),
-                        });
+                        on_update_3(current());
                     }
                 />
                 <input type="date" placeholder="有効期限"
                     prop:value=move || valid_until.get()
                     on:input=move |ev| {
                         set_valid_until.set(event_target_value(&ev));
-                        on_update_4(DocStatus {
-                            status: doc_status.get(),
-                            file: if file.get().is_empty() { None } else { Some(file.get()) },
-                            url: if url.get().is_empty() { None } else { Some(url.get()) },
-                            note: if note.get().is_empty() { None } else { Some(note.get()) },
-                            valid_from: vf4.clone(),
-                            valid_until: if valid_until.get().is_empty() { None } else { Some(valid_until.get()) },
-                            check_result: cr4.clone(),
-                            last_checked: lc4.clone(),
-                        });
+                        on_update_4(current());
                     }
                 />
                 <input type="text" placeholder="備考"
                     prop:value=move || note.get()
                     on:input=move |ev| {
                         set_note.set(event_target_value(&ev));
-                        on_update_5(DocStatus {
-                            status: doc_status.get(),
-                            file: if file.get().is_empty() { None } else { Some(file.get()) },
-                            url: if url.get().is_empty() { None } else { Some(url.get()) },
-                            note: if note.get().is_empty() { None } else { Some(note.get()) },
-                            valid_from: vf5.clone(),
-                            valid_until: if valid_until.get().is_empty() { None } else { Some(valid_until.get()) },
-                            check_result: cr5.clone(),
-                            last_checked: lc5.clone(),
-                        });
+                        on_update_5(current());
                     }
                 />
+                {is_person.then(|| view! {
+                    <PersonSelect
+                        selected=move || person_id.get()
+                        on_select=move |p| {
+                            // 資格者証のリンク・期限は登録簿から引用（入力済みの値は維持）
+                            if let Some(ref p) = p {
+                                if url.get_untracked().is_empty() {
+                                    if let Some(u) = p.qualifications.iter().find_map(|q| q.url.clone()) {
+                                        set_url.set(u);
+                                    }
+                                }
+                                if valid_until.get_untracked().is_empty() {
+                                    if let Some(e) = p.qualifications.iter().filter_map(|q| q.expiry.clone()).min() {
+                                        set_valid_until.set(e);
+                                    }
+                                }
+                            }
+                            set_person_id.set(p.map(|p| p.id));
+                            on_update_6(current());
+                        }
+                    />
+                })}
             </div>
+            {(!field_specs.is_empty()).then(|| view! {
+                <div class="doc-editor-fields doc-structured-fields">
+                    {field_specs.iter().map(|spec| {
+                        let key = spec.key;
+                        let on_update_7 = on_update_7.clone();
+                        let placeholder = extracted.get(key)
+                            .map(|v| format!("AI抽出: {}", v))
+                            .unwrap_or_else(|| spec.placeholder.to_string());
+                        view! {
+                            <label class="doc-field">
+                                <span class="doc-field-label">{spec.label}</span>
+                                <input type=if spec.date { "date" } else { "text" }
+                                    placeholder=placeholder
+                                    prop:value=move || fields.with(|f| f.get(key).cloned().unwrap_or_default())
+                                    on:input=move |ev| {
+                                        let value = event_target_value(&ev);
+                                        set_fields.update(|f| {
+                                            if value.trim().is_empty() {
+                                                f.remove(key);
+                                            } else {
+                                                f.insert(key.to_string(), value);
+                                            }
+                                        });
+                                        on_update_7(current());
+                                    }
+                                />
+                                {move || field_issues.with(|issues| {
+                                    issues.iter().filter(|i| i.key == key).map(|i| {
+                                        let class = if i.status == CheckStatus::Error { "doc-field-issue error" } else { "doc-field-issue warning" };
+                                        view! { <span class=class>{i.message.clone()}</span> }
+                                    }).collect_view()
+                                })}
+                            </label>
+                        }
+                    }).collect_view()}
+                </div>
+            })}
         </div>
     }
 }
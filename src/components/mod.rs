@@ -1,13 +1,19 @@
 //! UIコンポーネントモジュール
 
+pub mod contract_editor;
 pub mod contractor_card;
+pub mod dashboard_filter;
 pub mod tooltip;
 pub mod context_menu;
 pub mod project_view;
+pub mod row_list;
+pub mod search_bar;
 pub mod editors;
+pub mod worker_editor;
 
 pub use contractor_card::ContractorCard;
 pub use tooltip::CheckResultTooltip;
 pub use context_menu::ContextMenu;
 pub use project_view::ProjectView;
+pub use search_bar::SearchBar;
 pub use editors::ProjectEditor;
@@ -2,7 +2,13 @@
 
 use leptos::*;
 use crate::models::{ProjectData, DocLink};
+use crate::rules::ccus::CcusStatus;
+use crate::rules::engineer::{determine_engineer_requirement, Dedication};
+use crate::rules::ledger::determine_ledger_requirement;
+use crate::utils::format_yen;
 use super::ContractorCard;
+use super::dashboard_filter::{apply_filter, DashboardFilterBar};
+use crate::ProjectContext;
 
 /// プロジェクト全体の書類カード
 #[component]
@@ -33,12 +39,13 @@ pub fn ProjectDocCard(label: &'static str, doc: Option<DocLink>) -> impl IntoVie
 /// プロジェクト詳細ビュー
 #[component]
 pub fn ProjectView(project: ProjectData) -> impl IntoView {
+    let ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
     let total_docs: usize = project.contractors.iter().map(|c| c.docs.len()).sum();
     let complete_docs: usize = project.contractors.iter()
         .flat_map(|c| c.docs.values())
         .filter(|d| d.status)
         .count();
-    let progress = if total_docs > 0 { (complete_docs * 100) / total_docs } else { 0 };
+    let progress = (complete_docs * 100).checked_div(total_docs).unwrap_or(0);
 
     let project_docs = project.project_docs.clone();
 
@@ -51,6 +58,35 @@ pub fn ProjectView(project: ProjectData) -> impl IntoView {
         "未設定".to_string()
     };
 
+    // CCUS未登録の業者
+    let ccus_missing: Vec<String> = project.contractors.iter()
+        .filter(|c| !CcusStatus::of(c).business_registered)
+        .map(|c| c.name.clone())
+        .collect();
+
+    // 施工体制台帳の作成義務・配置技術者
+    let ledger = determine_ledger_requirement(&project);
+    let engineer = determine_engineer_requirement(&project);
+    let engineer_label = format!(
+        "{}{}: ",
+        engineer.kind.label(),
+        if matches!(engineer.dedication, Dedication::Required | Dedication::RequiredUnlessResidence) { "（専任）" } else { "" }
+    );
+
+    // 絞り込み用（役割の選択肢は登録順で重複除去）
+    let mut roles: Vec<String> = Vec::new();
+    for c in &project.contractors {
+        if !c.role.is_empty() && !roles.contains(&c.role) {
+            roles.push(c.role.clone());
+        }
+    }
+    let all_contractors = store_value(project.contractors.clone());
+    let today = crate::get_today();
+    let filtered = move || {
+        let filter = ctx.dashboard_filter.get();
+        all_contractors.with_value(|cs| apply_filter(cs, &filter, &today))
+    };
+
     // 担当者情報
     let representative_display = project.site_representative.clone().unwrap_or_default();
     let chief_engineer_display = project.chief_engineer.clone().unwrap_or_default();
@@ -69,12 +105,19 @@ pub fn ProjectView(project: ProjectData) -> impl IntoView {
                             <span class="staff-item">"現場代理人: " {representative_display.clone()}</span>
                         })}
                         {(!chief_engineer_display.is_empty()).then(|| view! {
-                            <span class="staff-item">"主任技術者: " {chief_engineer_display.clone()}</span>
+                            <span class="staff-item" title=engineer.kind_reason.clone()>{engineer_label.clone()} {chief_engineer_display.clone()}</span>
                         })}
                     </div>
                 })}
             </div>
 
+            {(!ccus_missing.is_empty()).then(|| view! {
+                <div class="ccus-summary">
+                    <span class="ccus-badge missing">"CCUS未登録"</span>
+                    {ccus_missing.join("、")}
+                </div>
+            })}
+
             <div class="progress-section">
                 <div class="progress-bar">
                     <div class="progress-fill" style=format!("width: {}%", progress)></div>
@@ -84,7 +127,17 @@ pub fn ProjectView(project: ProjectData) -> impl IntoView {
 
             // 全体書類セクション
             <div class="project-docs-section">
-                <h4>"全体書類"</h4>
+                <h4>
+                    "全体書類"
+                    {ledger.has_subcontracts.then(|| view! {
+                        <span
+                            class=format!("ledger-badge {}", if ledger.required { "required" } else { "not-required" })
+                            title=ledger.reason.clone()
+                        >
+                            {if ledger.required { "台帳作成義務あり" } else { "台帳作成義務なし" }}
+                        </span>
+                    })}
+                </h4>
                 <div class="project-docs-grid">
                     <ProjectDocCard
                         label="施工体系図"
@@ -104,17 +157,32 @@ pub fn ProjectView(project: ProjectData) -> impl IntoView {
             // 各社書類セクション
             <div class="contractors-section">
                 <h4>"各社書類"</h4>
-                <div class="contractors-grid">
-                    {project.contractors.into_iter().map(|c| view! {
-                        <ContractorCard contractor=c />
-                    }).collect_view()}
-                </div>
+                <DashboardFilterBar roles=roles />
+                {move || {
+                    let shown = filtered();
+                    let total = all_contractors.with_value(|cs| cs.len());
+                    view! {
+                        {(shown.len() != total).then(|| view! {
+                            <p class="filter-summary">{shown.len()} " / " {total} " 社を表示"</p>
+                        })}
+                        <div class="contractors-grid">
+                            {shown.into_iter().map(|f| view! {
+                                <ContractorCard contractor=f.contractor visible_docs=f.visible_docs />
+                            }).collect_view()}
+                        </div>
+                    }
+                }}
             </div>
 
             // 下請施工体制セクション
             {(!project.contracts.is_empty()).then(|| view! {
                 <div class="contracts-section">
-                    <h4>"下請施工体制"</h4>
+                    <h4>
+                        "下請施工体制"
+                        {ledger.total.as_ref().map(|t| view! {
+                            <span class="contracts-total">"下請総額（税込） " {format_yen(t.amount)}</span>
+                        })}
+                    </h4>
                     <div class="contracts-list">
                         {project.contracts.into_iter().map(|c| view! {
                             <div class="contract-item">
@@ -127,9 +195,15 @@ pub fn ProjectView(project: ProjectData) -> impl IntoView {
                                         <span class="contract-name">{c.name}</span>
                                     }.into_view()
                                 }}
-                                {c.contractor.map(|contractor| view! {
+                                {c.contractor.clone().map(|contractor| view! {
                                     <span class="contract-contractor">{contractor}</span>
                                 })}
+                                {c.work_type.clone().map(|work_type| view! {
+                                    <span class="contract-work-type">{work_type}</span>
+                                })}
+                                {c.amount.map(|amount| view! {
+                                    <span class="contract-amount">{format_yen(amount)} "（" {c.tax.label()} "）"</span>
+                                })}
                             </div>
                         }).collect_view()}
                     </div>
@@ -0,0 +1,70 @@
+//! 行リストの編集（作業員名簿・下請契約・技術者資格登録簿で共通）
+//!
+//! 一覧は行数（追加・削除）が変わったときだけ作り直し、各入力欄は行番号で値を読み書きする。
+//! 1文字ごとに一覧全体を再描画しないため、入力中の欄からフォーカスが外れない
+
+use leptos::*;
+
+/// 行の変更後に呼ぶ処理（親への通知・保存）
+type OnChange<T> = Box<dyn Fn(&[T])>;
+
+/// 行リストの読み書きと変更通知
+pub struct RowList<T: 'static> {
+    rows: ReadSignal<Vec<T>>,
+    set_rows: WriteSignal<Vec<T>>,
+    count: Memo<usize>,
+    on_change: StoredValue<OnChange<T>>,
+}
+
+impl<T: 'static> Clone for RowList<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: 'static> Copy for RowList<T> {}
+
+impl<T: 'static> RowList<T> {
+    pub fn new(rows: ReadSignal<Vec<T>>, set_rows: WriteSignal<Vec<T>>, on_change: impl Fn(&[T]) + 'static) -> Self {
+        let count = create_memo(move |_| rows.with(|rs| rs.len()));
+        RowList { rows, set_rows, count, on_change: store_value(Box::new(on_change) as OnChange<T>) }
+    }
+
+    /// 行数（一覧の再描画はこれだけを追跡する）
+    pub fn count(&self) -> usize {
+        self.count.get()
+    }
+
+    fn notify(&self) {
+        self.rows.with_untracked(|rs| self.on_change.with_value(|cb| cb(rs)));
+    }
+
+    /// 指定行の値を読む（行がなければ既定値）
+    pub fn read<R: Default>(&self, idx: usize, f: impl FnOnce(&T) -> R) -> R {
+        self.rows.with(|rs| rs.get(idx).map(f).unwrap_or_default())
+    }
+
+    /// 指定行を編集して通知
+    pub fn edit(&self, idx: usize, f: impl FnOnce(&mut T)) {
+        self.set_rows.update(|rs| {
+            if let Some(row) = rs.get_mut(idx) {
+                f(row);
+            }
+        });
+        self.notify();
+    }
+
+    pub fn push(&self, row: T) {
+        self.set_rows.update(|rs| rs.push(row));
+        self.notify();
+    }
+
+    pub fn remove(&self, idx: usize) {
+        self.set_rows.update(|rs| {
+            if idx < rs.len() {
+                rs.remove(idx);
+            }
+        });
+        self.notify();
+    }
+}
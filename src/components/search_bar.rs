@@ -0,0 +1,117 @@
+//! 全文検索バー
+//!
+//! ヘッダーの検索欄。結果を選ぶと業者カード・書類へスクロールするか、
+//! OCRビューで該当トークンを選択する
+
+use std::time::Duration;
+use leptos::*;
+use crate::models::ViewMode;
+use crate::utils::search::{SearchHit, SearchIndex, SearchTarget};
+use crate::views::ocr_viewer::OcrViewContext;
+use crate::ProjectContext;
+
+/// 検索結果の最大表示件数
+const MAX_RESULTS: usize = 30;
+/// ジャンプ先を強調表示する時間
+const HIGHLIGHT_MS: u64 = 2000;
+
+/// 業者カード要素のID
+pub fn contractor_element_id(contractor_id: &str) -> String {
+    format!("contractor-{}", contractor_id)
+}
+
+/// 書類行要素のID
+pub fn doc_element_id(contractor_id: &str, doc_key: &str) -> String {
+    format!("doc-{}-{}", contractor_id, doc_key)
+}
+
+/// 要素までスクロールし、一時的に強調表示
+fn scroll_to_element(id: String) {
+    // ダッシュボードの再描画後に要素を探す
+    set_timeout(move || {
+        let Some(el) = web_sys::window().and_then(|w| w.document()).and_then(|d| d.get_element_by_id(&id)) else {
+            return;
+        };
+        el.scroll_into_view();
+        let _ = el.class_list().add_1("search-hit");
+        set_timeout(move || {
+            let _ = el.class_list().remove_1("search-hit");
+        }, Duration::from_millis(HIGHLIGHT_MS));
+    }, Duration::from_millis(50));
+}
+
+/// 全文検索バー
+#[component]
+pub fn SearchBar() -> impl IntoView {
+    let ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
+    let ocr = use_context::<OcrViewContext>().expect("OcrViewContext not found");
+
+    let (query, set_query) = create_signal(String::new());
+    let (open, set_open) = create_signal(false);
+
+    let index = create_memo(move |_| {
+        SearchIndex::build(ctx.project.get().as_ref(), &ocr.documents.get())
+    });
+    let hits = create_memo(move |_| index.with(|i| i.search(&query.get(), MAX_RESULTS)));
+
+    let jump = move |hit: SearchHit| {
+        set_open.set(false);
+        match hit.target {
+            SearchTarget::Contractor { contractor_id } => {
+                ctx.set_edit_mode.set(false);
+                ctx.set_view_mode.set(ViewMode::Dashboard);
+                scroll_to_element(contractor_element_id(&contractor_id));
+            }
+            SearchTarget::Doc { contractor_id, doc_key } => {
+                ctx.set_edit_mode.set(false);
+                ctx.set_view_mode.set(ViewMode::Dashboard);
+                scroll_to_element(doc_element_id(&contractor_id, &doc_key));
+            }
+            SearchTarget::OcrToken { doc_index, token_index } => {
+                ocr.set_current_doc_index.set(doc_index);
+                ocr.set_selected_token.set(Some(token_index));
+                ctx.set_view_mode.set(ViewMode::OcrViewer);
+            }
+        }
+    };
+
+    view! {
+        <div class="search-bar">
+            <input type="search" class="search-input" placeholder="検索（業者・書類・備考・OCR）"
+                prop:value=move || query.get()
+                on:input=move |ev| {
+                    set_query.set(event_target_value(&ev));
+                    set_open.set(true);
+                }
+                on:focus=move |_| set_open.set(true)
+                on:keydown=move |ev| {
+                    if ev.key() == "Escape" {
+                        set_open.set(false);
+                    }
+                }
+            />
+            {move || (open.get() && !query.get().trim().is_empty()).then(|| {
+                let hits = hits.get();
+                view! {
+                    <div class="search-results">
+                        {hits.is_empty().then(|| view! {
+                            <div class="search-empty">"該当なし"</div>
+                        })}
+                        {hits.into_iter().map(|hit| {
+                            let title = hit.title.clone();
+                            let field = hit.field.clone();
+                            let snippet = hit.snippet.clone();
+                            view! {
+                                <button class="search-result" on:click=move |_| jump(hit.clone())>
+                                    <span class="search-result-title">{title}</span>
+                                    <span class="search-result-field">{field}</span>
+                                    <span class="search-result-snippet">{snippet}</span>
+                                </button>
+                            }
+                        }).collect_view()}
+                    </div>
+                }
+            })}
+        </div>
+    }
+}
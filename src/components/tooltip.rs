@@ -3,9 +3,14 @@
 //! エントリーをホバーした時にチェック結果を表示するツールチップ
 
 use crate::models::*;
+use crate::rules::checklist::{checklist_summary, evaluate_checklist, ChecklistState};
+use crate::utils::audit::format_change;
 use crate::ProjectContext;
 use leptos::*;
 
+/// ツールチップに表示する変更履歴の件数
+const TOOLTIP_HISTORY_LIMIT: usize = 5;
+
 /// チェック結果ツールチップ（1秒ホバーで表示）
 #[component]
 pub fn CheckResultTooltip() -> impl IntoView {
@@ -17,7 +22,7 @@ pub fn CheckResultTooltip() -> impl IntoView {
         {move || {
             let state = tooltip_state.get();
             if !state.visible {
-                return view! { <></> }.into_view();
+                return ().into_view();
             }
 
             let status_text = state.check_result.as_ref().map(|r| {
@@ -31,7 +36,14 @@ pub fn CheckResultTooltip() -> impl IntoView {
 
             let summary = state.check_result.as_ref().map(|r| r.summary.clone());
             let items = state.check_result.as_ref().map(|r| r.items.clone()).unwrap_or_default();
+            // チェックリストは件数と不備のある行だけ表示
+            let checklist = state.check_result.as_ref()
+                .map(|r| evaluate_checklist(&state.doc_key, r))
+                .filter(|lines| !lines.is_empty());
             let last_checked = state.last_checked.clone();
+            let check_diff = state.check_diff.clone();
+            // 変更履歴は新しい順に表示
+            let history: Vec<String> = state.history.iter().rev().take(TOOLTIP_HISTORY_LIMIT).map(format_change).collect();
 
             // 画面内に収まるように位置調整
             let window = web_sys::window().unwrap();
@@ -99,6 +111,35 @@ pub fn CheckResultTooltip() -> impl IntoView {
                                     </div>
                                 })}
 
+                                {checklist.map(|lines| {
+                                    let failed: Vec<_> = lines.iter()
+                                        .filter(|l| l.state == ChecklistState::Failed)
+                                        .map(|l| l.item.label)
+                                        .collect();
+                                    view! {
+                                        <div class="tooltip-checklist">
+                                            <span class="issues-title">"チェックリスト: " {checklist_summary(&lines)}</span>
+                                            {(!failed.is_empty()).then(|| view! {
+                                                <ul>
+                                                    {failed.into_iter().map(|label| view! {
+                                                        <li class="checklist-failed">{label}</li>
+                                                    }).collect_view()}
+                                                </ul>
+                                            })}
+                                        </div>
+                                    }
+                                })}
+
+                                {check_diff.map(|diff| view! {
+                                    <div class="check-diff">
+                                        {format!(
+                                            "前回比: 解消 {}件 / 新規 {}件",
+                                            diff.resolved.len() + diff.resolved_fields.len(),
+                                            diff.added.len() + diff.added_fields.len()
+                                        )}
+                                    </div>
+                                })}
+
                                 {last_checked.map(|dt| view! {
                                     <div class="checked-at">"チェック日時: " {dt}</div>
                                 })}
@@ -110,6 +151,15 @@ pub fn CheckResultTooltip() -> impl IntoView {
                             </div>
                         }.into_view(),
                     }}
+
+                    {(!history.is_empty()).then(|| view! {
+                        <div class="tooltip-history">
+                            <span class="issues-title">"変更履歴:"</span>
+                            <ul>
+                                {history.into_iter().map(|line| view! { <li>{line}</li> }).collect_view()}
+                            </ul>
+                        </div>
+                    })}
                 </div>
             }.into_view()
         }}
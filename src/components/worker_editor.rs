@@ -0,0 +1,175 @@
+//! 作業員名簿エディタ
+//!
+//! 業者ごとの作業員（氏名・生年月日・資格・健康診断・社会保険）を編集する
+
+use leptos::*;
+use crate::components::row_list::RowList;
+use crate::models::{QualificationKind, Worker, WorkerQualification};
+use crate::rules::ccus::normalize_ccus_id;
+
+/// 作業員名簿の編集コンポーネント
+///
+/// 作業員ごとに基本情報・社会保険・資格の行を並べる。資格の行数も作業員ごとに追跡する
+#[component]
+pub fn WorkerRosterEditor<F>(
+    workers: Vec<Worker>,
+    on_update: F,
+) -> impl IntoView
+where
+    F: Fn(Vec<Worker>) + 'static,
+{
+    let (workers, set_workers) = create_signal(workers);
+    let rows = RowList::new(workers, set_workers, move |ws: &[Worker]| on_update(ws.to_vec()));
+    let add_worker = move |_| rows.push(Worker::default());
+
+    view! {
+        <div class="worker-roster-editor">
+            <div class="docs-header">
+                <span>"作業員名簿"</span>
+                <button class="add-btn small" on:click=add_worker>"+ 作業員追加"</button>
+            </div>
+            {move || (0..rows.count()).map(|i| {
+                let q_count = create_memo(move |_| rows.read(i, |w| w.qualifications.len()));
+                let edit_q = move |qi: usize, f: &dyn Fn(&mut WorkerQualification)| {
+                    rows.edit(i, |w| {
+                        if let Some(q) = w.qualifications.get_mut(qi) {
+                            f(q);
+                        }
+                    });
+                };
+                let read_q = move |qi: usize, f: fn(&WorkerQualification) -> String| {
+                    rows.read(i, |w| w.qualifications.get(qi).map(f).unwrap_or_default())
+                };
+
+                view! {
+                    <div class="worker-editor">
+                        <div class="worker-editor-row">
+                            <input type="text" class="worker-name" placeholder="氏名"
+                                prop:value=move || rows.read(i, |w| w.name.clone())
+                                on:input=move |ev| { let v = event_target_value(&ev); rows.edit(i, |w| w.name = v); }
+                            />
+                            <input type="text" class="worker-role" placeholder="職種"
+                                prop:value=move || rows.read(i, |w| w.role.clone())
+                                on:input=move |ev| { let v = event_target_value(&ev); rows.edit(i, |w| w.role = v); }
+                            />
+                            <label class="worker-field">
+                                "生年月日"
+                                <input type="date"
+                                    prop:value=move || rows.read(i, |w| w.birth_date.clone().unwrap_or_default())
+                                    on:input=move |ev| {
+                                        let v = event_target_value(&ev);
+                                        rows.edit(i, |w| w.birth_date = (!v.is_empty()).then(|| v.clone()));
+                                    }
+                                />
+                            </label>
+                            <input type="text" class="worker-blood" placeholder="血液型"
+                                prop:value=move || rows.read(i, |w| w.blood_type.clone().unwrap_or_default())
+                                on:input=move |ev| {
+                                    let v = event_target_value(&ev);
+                                    rows.edit(i, |w| w.blood_type = (!v.trim().is_empty()).then(|| v.clone()));
+                                }
+                            />
+                            <input type="text" class="worker-ccus" placeholder="CCUS技能者ID" inputmode="numeric"
+                                class:invalid=move || {
+                                    let id = rows.read(i, |w| w.ccus_worker_id.clone().unwrap_or_default());
+                                    !id.trim().is_empty() && normalize_ccus_id(&id).is_err()
+                                }
+                                prop:value=move || rows.read(i, |w| w.ccus_worker_id.clone().unwrap_or_default())
+                                on:input=move |ev| {
+                                    let v = event_target_value(&ev);
+                                    rows.edit(i, |w| w.ccus_worker_id = (!v.trim().is_empty()).then(|| v.trim().to_string()));
+                                }
+                            />
+                            <label class="worker-field">
+                                "健康診断"
+                                <input type="date"
+                                    prop:value=move || rows.read(i, |w| w.health_check_date.clone().unwrap_or_default())
+                                    on:input=move |ev| {
+                                        let v = event_target_value(&ev);
+                                        rows.edit(i, |w| w.health_check_date = (!v.is_empty()).then(|| v.clone()));
+                                    }
+                                />
+                            </label>
+                            <button class="delete-btn small" on:click=move |_| rows.remove(i)>"✕"</button>
+                        </div>
+
+                        <div class="worker-editor-row worker-insurance">
+                            <label class="checkbox-label">
+                                <input type="checkbox"
+                                    prop:checked=move || rows.read(i, |w| w.insurance.health)
+                                    on:change=move |ev| { let v = event_target_checked(&ev); rows.edit(i, |w| w.insurance.health = v); }
+                                />
+                                "健康保険"
+                            </label>
+                            <label class="checkbox-label">
+                                <input type="checkbox"
+                                    prop:checked=move || rows.read(i, |w| w.insurance.pension)
+                                    on:change=move |ev| { let v = event_target_checked(&ev); rows.edit(i, |w| w.insurance.pension = v); }
+                                />
+                                "厚生年金"
+                            </label>
+                            <label class="checkbox-label">
+                                <input type="checkbox"
+                                    prop:checked=move || rows.read(i, |w| w.insurance.employment)
+                                    on:change=move |ev| { let v = event_target_checked(&ev); rows.edit(i, |w| w.insurance.employment = v); }
+                                />
+                                "雇用保険"
+                            </label>
+                            <button class="add-btn small" on:click=move |_| {
+                                rows.edit(i, |w| w.qualifications.push(WorkerQualification::default()));
+                            }>"+ 資格追加"</button>
+                        </div>
+
+                        {move || (0..q_count.get()).map(|qi| view! {
+                            <div class="worker-editor-row worker-qualification">
+                                <select
+                                    on:change=move |ev| {
+                                        let kind = QualificationKind::from_key(&event_target_value(&ev));
+                                        edit_q(qi, &|q| q.kind = kind);
+                                    }
+                                >
+                                    {QualificationKind::ALL.into_iter().map(|kind| view! {
+                                        <option
+                                            value=kind.key()
+                                            selected=move || read_q(qi, |q| q.kind.key().to_string()) == kind.key()
+                                        >
+                                            {kind.label()}
+                                        </option>
+                                    }).collect_view()}
+                                </select>
+                                <input type="text" placeholder="資格名"
+                                    prop:value=move || read_q(qi, |q| q.name.clone())
+                                    on:input=move |ev| { let v = event_target_value(&ev); edit_q(qi, &|q| q.name = v.clone()); }
+                                />
+                                <input type="text" placeholder="番号"
+                                    prop:value=move || read_q(qi, |q| q.number.clone().unwrap_or_default())
+                                    on:input=move |ev| {
+                                        let v = event_target_value(&ev);
+                                        edit_q(qi, &|q| q.number = (!v.trim().is_empty()).then(|| v.clone()));
+                                    }
+                                />
+                                <label class="worker-field">
+                                    "有効期限"
+                                    <input type="date"
+                                        prop:value=move || read_q(qi, |q| q.expiry.clone().unwrap_or_default())
+                                        on:input=move |ev| {
+                                            let v = event_target_value(&ev);
+                                            edit_q(qi, &|q| q.expiry = (!v.is_empty()).then(|| v.clone()));
+                                        }
+                                    />
+                                </label>
+                                <button class="delete-btn small" on:click=move |_| {
+                                    rows.edit(i, |w| {
+                                        if qi < w.qualifications.len() {
+                                            w.qualifications.remove(qi);
+                                        }
+                                    });
+                                }>"✕"</button>
+                            </div>
+                        }).collect_view()}
+                    </div>
+                }
+            }).collect_view()}
+        </div>
+    }
+}
@@ -1,8 +1,10 @@
 // モジュール宣言
+mod checker;
 mod models;
 mod utils;
 mod components;
 mod views;
+mod rules;
 
 // 外部クレート
 use leptos::*;
@@ -15,13 +17,21 @@ use std::collections::HashMap;
 
 // 自モジュールからのインポート
 use models::*;
-use components::{CheckResultTooltip, ContextMenu};
+// 旧パス（crate::FieldPosition など）の互換用
+pub use models::{FieldPosition, FieldType};
+use components::{CheckResultTooltip, ContextMenu, SearchBar};
+use utils::audit::{get_actor_name, record_doc_change, save_actor_name};
 use utils::cache::{save_to_cache, load_from_cache, clear_cache};
+use utils::editor_protocol::{on_editor_message, EditorMessage, EditorMode, EditorUrl};
+use utils::registry::load_person_registry;
+use utils::form_template::load_form_templates;
+use utils::router::install_router;
+use utils::ocr_import::import_ocr;
 use utils::gas::{get_gas_url, save_gas_url, clear_gas_url, init_gas_from_url_params, generate_gas_share_url, fetch_from_gas, auto_save_api_key_to_sheet, format_gas_modified_time, save_gas_url_to_sheet};
-use utils::{encode_base64, decode_base64};
-use utils::log_trace::{log_info, log_info_with_data, log_warn, log_error, log_error_with_data, download_logs, clear_logs, copy_logs_to_clipboard_async};
-use views::{CheckResultsPanel, PdfViewer, SpreadsheetViewer};
-use views::ocr_viewer::{OcrDocument, OcrToken, OcrViewContext, OcrViewer};
+use utils::{encode_base64, decode_base64, download_file};
+use utils::log_trace::{log_info, log_info_with_data, log_error, log_error_with_data, download_logs, clear_logs, copy_logs_to_clipboard_async};
+use views::{CheckResultsPanel, PdfViewer, SpreadsheetViewer, RequestLetterView, PersonRegistryView, BatchCheckView};
+use views::ocr_viewer::{OcrViewContext, OcrViewer};
 use components::{ProjectView, ProjectEditor};
 
 
@@ -29,8 +39,7 @@ use components::{ProjectView, ProjectEditor};
 fn get_hash_data() -> Option<ProjectData> {
     let window = web_sys::window()?;
     let hash = window.location().hash().ok()?;
-    if hash.starts_with("#data=") {
-        let encoded = &hash[6..];
+    if let Some(encoded) = hash.strip_prefix("#data=") {
         let json = decode_base64(encoded)?;
         serde_json::from_str(&json).ok()
     } else {
@@ -118,6 +127,14 @@ pub enum CheckMode {
     None,
     Existence,  // 書類存在チェック
     Date,       // 日付チェック
+    Workers,    // 作業員名簿チェック
+    Persons,    // 技術者資格チェック
+    Permit,     // 建設業許可チェック
+    Numbers,    // 番号形式チェック
+    Ccus,       // CCUS登録チェック
+    Ledger,     // 施工体制台帳要否チェック
+    Engineer,   // 配置技術者チェック
+    AiHistory,  // AIチェック前回比較
 }
 
 #[derive(Debug, Clone)]
@@ -146,69 +163,23 @@ pub struct Contract {
 }
 
 // ============================================
-// フィールドタイプとMissingField定義
+// MissingField定義（FieldType・FieldPositionはmodels）
 // ============================================
 
-/// 入力フィールドのタイプ
-#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-pub enum FieldType {
-    /// 日付入力
-    Date,
-    /// テキスト入力
-    Text,
-    /// 署名
-    Signature,
-    /// 選択肢
-    Select,
-    /// チェックボックス
-    Checkbox,
-}
-
-impl FieldType {
-    /// HTML input typeを取得
-    pub fn input_type(&self) -> &'static str {
-        match self {
-            FieldType::Date => "date",
-            FieldType::Text => "text",
-            FieldType::Signature => "text", // 署名は別途処理
-            FieldType::Select => "text",
-            FieldType::Checkbox => "checkbox",
-        }
-    }
-
-    /// プレースホルダーテキストを取得
-    pub fn placeholder(&self) -> &'static str {
-        match self {
-            FieldType::Date => "YYYY-MM-DD",
-            FieldType::Text => "入力してください",
-            FieldType::Signature => "署名",
-            FieldType::Select => "選択してください",
-            FieldType::Checkbox => "",
-        }
-    }
-}
-
-/// フィールドの位置情報（OCRで検出した座標）
-#[derive(Clone, Debug, Serialize, Deserialize)]
-pub struct FieldPosition {
-    pub x: f64,
-    pub y: f64,
-    pub width: f64,
-    pub height: f64,
-}
-
 /// 不足フィールド情報
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MissingField {
     pub field_name: String,
     pub field_type: FieldType,
     pub value: String,
     pub position: Option<FieldPosition>,
+    /// ページ（1始まり。OCRの記入欄検出で設定）
+    #[serde(default)]
+    pub page: Option<u32>,
 }
 
 /// OCR結果（簡易版）
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct OcrResult {
     pub text: String,
     pub pages: Vec<OcrPage>,
@@ -221,14 +192,6 @@ pub struct OcrPage {
     pub text: String,
 }
 
-impl Default for OcrResult {
-    fn default() -> Self {
-        OcrResult {
-            text: String::new(),
-            pages: Vec::new(),
-        }
-    }
-}
 
 // ============================================
 // API通信関数
@@ -283,6 +246,12 @@ pub struct ProjectContext {
     /// コンテキストメニュー状態（右クリック/ロングプレス）
     pub context_menu: ReadSignal<ContextMenuState>,
     pub set_context_menu: WriteSignal<ContextMenuState>,
+    /// 技術者資格登録簿（プロジェクト横断、LocalStorageに保存）
+    pub person_registry: ReadSignal<Vec<Person>>,
+    pub set_person_registry: WriteSignal<Vec<Person>>,
+    /// ダッシュボードの絞り込み条件（表示切替をまたいで保持）
+    pub dashboard_filter: ReadSignal<DashboardFilter>,
+    pub set_dashboard_filter: WriteSignal<DashboardFilter>,
 }
 
 
@@ -319,7 +288,7 @@ fn Dashboard() -> impl IntoView {
 
 
 // JSONファイルをfetch
-async fn fetch_json(url: &str) -> Result<ProjectData, String> {
+async fn fetch_json<T: serde::de::DeserializeOwned>(url: &str) -> Result<T, String> {
     let opts = RequestInit::new();
     opts.set_method("GET");
 
@@ -387,40 +356,20 @@ fn PdfEditor(
     let set_view_mode = ctx.set_view_mode;
 
     // GAS URLとファイルIDを取得してiframe URLを構築
-    let iframe_url = {
-        let gas_url = get_gas_url();
-        let file_id = extract_file_id(&original_url);
-
-        match (gas_url, file_id) {
-            (Some(gas), Some(fid)) => {
-                let encoded_gas = js_sys::encode_uri_component(&gas).as_string().unwrap_or_default();
-                format!("editor/index.html?fileId={}&gasUrl={}", fid, encoded_gas)
-            }
-            _ => "editor/index.html".to_string()
-        }
+    let iframe_url = match (get_gas_url(), extract_file_id(&original_url)) {
+        (Some(gas), Some(fid)) => EditorUrl::new(EditorMode::Edit).file_id(&fid).gas_url(Some(&gas)).build(),
+        _ => EditorUrl::new(EditorMode::Edit).build(),
     };
 
     let on_back = move |_| {
         set_view_mode.set(ViewMode::Dashboard);
     };
 
-    // iframeからのpostMessageを受信してダッシュボードに戻る
-    create_effect(move |_| {
-        let window = web_sys::window().expect("window");
-        let closure = Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
-            if let Ok(data) = event.data().dyn_into::<js_sys::Object>() {
-                if let Ok(type_val) = js_sys::Reflect::get(&data, &JsValue::from_str("type")) {
-                    if let Some(type_str) = type_val.as_string() {
-                        if type_str == "back" {
-                            set_view_mode.set(ViewMode::Dashboard);
-                        }
-                    }
-                }
-            }
-        }) as Box<dyn FnMut(_)>);
-
-        window.add_event_listener_with_callback("message", closure.as_ref().unchecked_ref()).ok();
-        closure.forget(); // リスナーを保持
+    // iframeからの「戻る」でダッシュボードに戻る
+    on_editor_message(move |message| {
+        if matches!(message, EditorMessage::Back) {
+            set_view_mode.set(ViewMode::Dashboard);
+        }
     });
 
     view! {
@@ -443,9 +392,8 @@ fn PdfEditor(
 fn run_existence_check(project: &ProjectData) -> Vec<CheckResult> {
     let mut results = Vec::new();
     for contractor in &project.contractors {
-        for (doc_key, doc_status) in &contractor.docs {
-            let label = doc_key.replace("_", " ").chars().skip_while(|c| c.is_numeric()).collect::<String>();
-            let label = label.trim_start_matches('_').to_string();
+        for (doc_key, doc_status) in contractor.sorted_docs() {
+            let label = DocKind::parse(doc_key).label();
 
             if !doc_status.status {
                 results.push(CheckResult {
@@ -478,9 +426,8 @@ fn run_existence_check(project: &ProjectData) -> Vec<CheckResult> {
 fn run_date_check(project: &ProjectData, today: &str) -> Vec<CheckResult> {
     let mut results = Vec::new();
     for contractor in &project.contractors {
-        for (doc_key, doc_status) in &contractor.docs {
-            let label = doc_key.replace("_", " ").chars().skip_while(|c| c.is_numeric()).collect::<String>();
-            let label = label.trim_start_matches('_').to_string();
+        for (doc_key, doc_status) in contractor.sorted_docs() {
+            let label = DocKind::parse(doc_key).label();
 
             // 有効期限がある書類のみチェック
             if let Some(ref valid_until) = doc_status.valid_until {
@@ -520,7 +467,7 @@ fn run_date_check(project: &ProjectData, today: &str) -> Vec<CheckResult> {
 #[allow(dead_code)]
 fn clear_all_check_results(project: &mut ProjectData) {
     for contractor in &mut project.contractors {
-        for (_, doc) in &mut contractor.docs {
+        for doc in contractor.docs.values_mut() {
             // DocStatusにはcheck_result, last_checkedフィールドがないため、
             // 将来の拡張用にコメントを残す
             // doc.check_result = None;
@@ -550,20 +497,9 @@ fn clear_check_result(
     }
 }
 
-// 日付に日数を加算 (簡易実装)
+// 日付に日数を加算（解析できない場合は元の文字列）
 fn add_days_to_date(date: &str, days: i32) -> String {
-    // YYYY-MM-DD形式を想定
-    if let Some((year, rest)) = date.split_once('-') {
-        if let Some((month, day)) = rest.split_once('-') {
-            if let (Ok(y), Ok(m), Ok(d)) = (year.parse::<i32>(), month.parse::<i32>(), day.parse::<i32>()) {
-                let total_days = d + days;
-                let new_month = m + (total_days - 1) / 30;
-                let new_day = ((total_days - 1) % 30) + 1;
-                return format!("{:04}-{:02}-{:02}", y + (new_month - 1) / 12, ((new_month - 1) % 12) + 1, new_day);
-            }
-        }
-    }
-    date.to_string()
+    utils::date::add_days(date, days as i64).unwrap_or_else(|| date.to_string())
 }
 
 // 今日の日付を取得
@@ -590,33 +526,11 @@ fn get_timestamp() -> String {
 // JSONダウンロード用関数（タイムスタンプ付き）
 fn download_json(project: &ProjectData) {
     if let Ok(json) = serde_json::to_string_pretty(project) {
-        if let Some(window) = web_sys::window() {
-            if let Some(document) = window.document() {
-                // タイムスタンプ付きファイル名
-                let timestamp = get_timestamp();
-                let project_name = project.project_name.replace(" ", "_").replace("/", "-");
-                let filename = format!("{}_{}.json", project_name, timestamp);
-
-                // Blobを作成
-                let blob_parts = js_sys::Array::new();
-                blob_parts.push(&JsValue::from_str(&json));
-                let options = web_sys::BlobPropertyBag::new();
-                options.set_type("application/json");
-
-                if let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(&blob_parts, &options) {
-                    if let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) {
-                        if let Ok(a) = document.create_element("a") {
-                            let _ = a.set_attribute("href", &url);
-                            let _ = a.set_attribute("download", &filename);
-                            if let Some(element) = a.dyn_ref::<web_sys::HtmlElement>() {
-                                element.click();
-                            }
-                            let _ = web_sys::Url::revoke_object_url(&url);
-                        }
-                    }
-                }
-            }
-        }
+        // タイムスタンプ付きファイル名
+        let timestamp = get_timestamp();
+        let project_name = project.project_name.replace(" ", "_").replace("/", "-");
+        let filename = format!("{}_{}.json", project_name, timestamp);
+        download_file(&filename, "application/json", &json);
     }
 }
 
@@ -629,6 +543,8 @@ fn App() -> impl IntoView {
     let (show_gas_dialog, set_show_gas_dialog) = create_signal(false);
     let (gas_url_input, set_gas_url_input) = create_signal(String::new());
     let (gas_connected, set_gas_connected) = create_signal(get_gas_url().is_some());
+    // 変更履歴に記録する操作者名
+    let (actor_name, set_actor_name) = create_signal(get_actor_name());
     let (gas_syncing, set_gas_syncing) = create_signal(false);
     let (gas_message, set_gas_message) = create_signal(None::<String>);
     let (gas_code, set_gas_code) = create_signal(None::<String>);
@@ -653,6 +569,12 @@ fn App() -> impl IntoView {
     // コンテキストメニュー状態（右クリック/ロングプレス）
     let (context_menu, set_context_menu) = create_signal(ContextMenuState::default());
 
+    // 技術者資格登録簿（プロジェクト横断）
+    let (person_registry, set_person_registry) = create_signal(load_person_registry());
+
+    // ダッシュボードの絞り込み条件
+    let (dashboard_filter, set_dashboard_filter) = create_signal(DashboardFilter::default());
+
     // データソース追跡（デバッグ用）
     let (data_source, set_data_source) = create_signal("なし".to_string());
     let (show_debug, set_show_debug) = create_signal(false);
@@ -662,6 +584,13 @@ fn App() -> impl IntoView {
     let (current_doc_index, set_current_doc_index) = create_signal(0usize);
     let (selected_token, set_selected_token) = create_signal(None::<usize>);
     let (show_all_boxes, set_show_all_boxes) = create_signal(false);
+    let (ocr_search_query, set_ocr_search_query) = create_signal(String::new());
+    let (ocr_current_match, set_ocr_current_match) = create_signal(0usize);
+    let (ocr_current_page, set_ocr_current_page) = create_signal(1u32);
+    let (form_templates, set_form_templates) = create_signal(load_form_templates());
+    let (template_mode, set_template_mode) = create_signal(false);
+    let (selected_template_field, set_selected_template_field) = create_signal(None::<usize>);
+    let (ocr_highlight, set_ocr_highlight) = create_signal(None::<FindingLocation>);
 
     // OCRコンテキスト提供
     let ocr_ctx = OcrViewContext {
@@ -673,6 +602,20 @@ fn App() -> impl IntoView {
         set_selected_token,
         show_all_boxes,
         set_show_all_boxes,
+        search_query: ocr_search_query,
+        set_search_query: set_ocr_search_query,
+        current_match: ocr_current_match,
+        set_current_match: set_ocr_current_match,
+        current_page: ocr_current_page,
+        set_current_page: set_ocr_current_page,
+        templates: form_templates,
+        set_templates: set_form_templates,
+        template_mode,
+        set_template_mode,
+        selected_field: selected_template_field,
+        set_selected_field: set_selected_template_field,
+        highlight: ocr_highlight,
+        set_highlight: set_ocr_highlight,
     };
     provide_context(ocr_ctx);
 
@@ -700,177 +643,130 @@ fn App() -> impl IntoView {
         set_check_result_tooltip,
         context_menu,
         set_context_menu,
+        person_registry,
+        set_person_registry,
+        dashboard_filter,
+        set_dashboard_filter,
     };
     provide_context(ctx.clone());
 
-    // iframeからのpostMessageを受信（グローバル）
-    {
-        let set_view_mode = set_view_mode.clone();
-        let set_api_connected = set_api_connected.clone();
-        let set_project = set_project.clone();
-        let project = project.clone();
-        create_effect(move |_| {
-            let set_view_mode = set_view_mode.clone();
-            let set_api_connected = set_api_connected.clone();
-            let set_project = set_project.clone();
-            let project = project.clone();
-            let window = web_sys::window().expect("window");
-            let set_check_result_tooltip = set_check_result_tooltip.clone();
-            let closure = Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
-                // 全メッセージをログ
-                web_sys::console::log_1(&format!("[postMessage] Received event, data type: {:?}", event.data().js_typeof().as_string()).into());
-                if let Ok(data) = event.data().dyn_into::<js_sys::Object>() {
-                    if let Ok(type_val) = js_sys::Reflect::get(&data, &JsValue::from_str("type")) {
-                        if let Some(type_str) = type_val.as_string() {
-                            web_sys::console::log_1(&format!("[postMessage] type={}", type_str).into());
-                            match type_str.as_str() {
-                                "apikey-setup-complete" => {
-                                    // APIキー設定完了 - 状態を更新してダッシュボードに戻る
-                                    set_api_connected.set(check_api_key_exists());
-                                    set_view_mode.set(ViewMode::Dashboard);
-
-                                    // シート接続中ならAPIキーを自動保存
-                                    if let Some(gas_url) = get_gas_url() {
-                                        spawn_local(async move {
-                                            auto_save_api_key_to_sheet(&gas_url).await;
-                                        });
-                                    }
-                                }
-                                "ai-check-result" => {
-                                    log_info("ai-check-result", "AIチェック結果を受信");
-                                    // AIチェック結果を受け取り、ProjectDataを更新
-                                    let contractor = js_sys::Reflect::get(&data, &JsValue::from_str("contractor"))
-                                        .ok().and_then(|v| v.as_string());
-                                    let doc_key_raw = js_sys::Reflect::get(&data, &JsValue::from_str("docKey"))
-                                        .ok().and_then(|v| v.as_string());
-                                    let result_val = js_sys::Reflect::get(&data, &JsValue::from_str("result")).ok();
-                                    let file_id = js_sys::Reflect::get(&data, &JsValue::from_str("fileId"))
-                                        .ok().and_then(|v| v.as_string());
+    // iframeからのメッセージを受信（グローバル）
+    on_editor_message(move |message| {
+        log_info("postMessage", &format!("受信: {}", message.kind()));
+        match message {
+            EditorMessage::ApikeySetupComplete => {
+                // APIキー設定完了 - 状態を更新してダッシュボードに戻る
+                set_api_connected.set(check_api_key_exists());
+                set_view_mode.set(ViewMode::Dashboard);
+
+                // シート接続中ならAPIキーを自動保存
+                if let Some(gas_url) = get_gas_url() {
+                    spawn_local(async move {
+                        auto_save_api_key_to_sheet(&gas_url).await;
+                    });
+                }
+            }
+            EditorMessage::AiCheckResult { result: check_result, contractor: contractor_name, doc_key, file_id, .. } => {
+                log_info("ai-check-result", "AIチェック結果を受信");
+                let doc_key = doc_key.trim().to_string();
+                let trace_data = serde_json::json!({
+                    "contractor": contractor_name,
+                    "doc_key": doc_key,
+                    "file_id": file_id,
+                    "status": check_result.status,
+                    "extracted_fields": check_result.extracted_fields,
+                });
+                log_info_with_data("ai-check-result", "受信データ", trace_data);
+
+                // ProjectDataを更新
+                if let Some(mut proj) = project.get() {
+                    let now = js_sys::Date::new_0().to_iso_string().as_string().unwrap_or_default();
+
+                    // contractor.docsを更新
+                    let contractor_name_trimmed = contractor_name.trim();
+                    let mut updated = false;
+                    for contractor in &mut proj.contractors {
+                        if contractor.name.trim() == contractor_name_trimmed {
+                            log_info("ai-check-result", &format!("業者を発見: {}", contractor.name));
+
+                            let doc_keys: Vec<String> = contractor.docs.keys().cloned().collect();
+                            let trace_data = serde_json::json!({
+                                "available_doc_keys": doc_keys,
+                                "target_doc_key": doc_key,
+                            });
+                            log_info_with_data("ai-check-result", "利用可能なdocキー", trace_data);
 
+                            if let Some(doc_status) = contractor.docs.get_mut(&doc_key) {
+                                // 既存のcheck_resultをログ
+                                if let Some(ref old_result) = doc_status.check_result {
                                     let trace_data = serde_json::json!({
-                                        "contractor": contractor,
-                                        "doc_key": doc_key_raw,
-                                        "file_id": file_id,
+                                        "old_status": old_result.status,
+                                        "old_extracted_fields": old_result.extracted_fields,
                                     });
-                                    log_info_with_data("ai-check-result", "受信データ", trace_data);
-
-                                    if let (Some(contractor_name), Some(doc_key_raw), Some(result_js)) = (contractor, doc_key_raw, result_val) {
-                                        let doc_key = doc_key_raw.trim().to_string();
-                                        // 結果をCheckResultDataにデシリアライズ
-                                        match serde_wasm_bindgen::from_value::<CheckResultData>(result_js.clone()) {
-                                            Ok(check_result) => {
-                                                let trace_data = serde_json::json!({
-                                                    "status": check_result.status,
-                                                    "extracted_fields": check_result.extracted_fields,
-                                                });
-                                                log_info_with_data("ai-check-result", "デシリアライズ成功", trace_data);
-                                                
-                                                // ProjectDataを更新
-                                                if let Some(mut proj) = project.get() {
-                                                    let now = js_sys::Date::new_0().to_iso_string().as_string().unwrap_or_default();
-
-                                                    // contractor.docsを更新
-                                                    let contractor_name_trimmed = contractor_name.trim();
-                                                    let mut updated = false;
-                                                    for contractor in &mut proj.contractors {
-                                                        if contractor.name.trim() == contractor_name_trimmed {
-                                                            log_info("ai-check-result", &format!("業者を発見: {}", contractor.name));
-                                                            
-                                                            let doc_keys: Vec<String> = contractor.docs.keys().cloned().collect();
-                                                            let trace_data = serde_json::json!({
-                                                                "available_doc_keys": doc_keys,
-                                                                "target_doc_key": doc_key,
-                                                            });
-                                                            log_info_with_data("ai-check-result", "利用可能なdocキー", trace_data);
-                                                            
-                                                            if let Some(doc_status) = contractor.docs.get_mut(&doc_key) {
-                                                                // 既存のcheck_resultをログ
-                                                                if let Some(ref old_result) = doc_status.check_result {
-                                                                    let trace_data = serde_json::json!({
-                                                                        "old_status": old_result.status,
-                                                                        "old_extracted_fields": old_result.extracted_fields,
-                                                                    });
-                                                                    log_info_with_data("ai-check-result", "既存のcheck_result", trace_data);
-                                                                }
-                                                                doc_status.check_result = Some(check_result.clone());
-                                                                doc_status.last_checked = Some(now.clone());
-                                                                updated = true;
-                                                                
-                                                                let trace_data = serde_json::json!({
-                                                                    "doc_key": doc_key,
-                                                                    "new_extracted_fields": check_result.extracted_fields,
-                                                                    "last_checked": now,
-                                                                });
-                                                                log_info_with_data("ai-check-result", "ドキュメント更新完了", trace_data);
-                                                            } else {
-                                                                let trace_data = serde_json::json!({
-                                                                    "doc_key": doc_key,
-                                                                    "available_keys": doc_keys,
-                                                                });
-                                                                log_error_with_data("ai-check-result", &format!("Doc key '{}' が見つかりません", doc_key), trace_data);
-                                                            }
-                                                            break;
-                                                        }
-                                                    }
-                                                    if !updated {
-                                                        let trace_data = serde_json::json!({
-                                                            "contractor_name": contractor_name,
-                                                        });
-                                                        log_error_with_data("ai-check-result", &format!("業者 '{}' が見つかりません", contractor_name), trace_data);
-                                                    }
-
-                                                    // ローカル更新
-                                                    set_project.set(Some(proj.clone()));
-                                                    save_to_cache(&proj);
-                                                    log_info("ai-check-result", "キャッシュに保存完了");
-
-                                                    // GASに保存
-                                                    let proj_for_sync = proj.clone();
-                                                    spawn_local(async move {
-                                                        match sync_to_gas(&proj_for_sync).await {
-                                                            Ok(msg) => {
-                                                                log_info("gas-sync", &format!("GAS保存成功: {}", msg));
-                                                            }
-                                                            Err(e) => {
-                                                                log_error("gas-sync", &format!("GAS保存エラー: {}", e));
-                                                            }
-                                                        }
-                                                    });
-                                                }
-                                            }
-                                            Err(e) => {
-                                                let trace_data = serde_json::json!({
-                                                    "error": format!("{:?}", e),
-                                                });
-                                                log_error_with_data("ai-check-result", "デシリアライズエラー", trace_data);
-                                            }
-                                        }
-                                    } else {
-                                        log_error("ai-check-result", "必要なパラメータが不足しています");
-                                    }
-                                    // チェック結果パネルをクリア
-                                    set_check_mode.set(CheckMode::None);
-                                    set_check_results.set(Vec::new());
-                                    set_view_mode.set(ViewMode::Dashboard);
-                                }
-                                "ai-check-cancel" | "back" => {
-                                    // チェック結果パネルをクリア
-                                    set_check_mode.set(CheckMode::None);
-                                    set_check_results.set(Vec::new());
-                                    // ホバー状態もリセット
-                                    set_check_result_tooltip.set(CheckResultTooltipState::default());
-                                    set_view_mode.set(ViewMode::Dashboard);
+                                    log_info_with_data("ai-check-result", "既存のcheck_result", trace_data);
                                 }
-                                _ => {}
+                                let before = doc_status.clone();
+                                doc_status.push_check_result(check_result.clone(), &now, file_id.clone());
+                                record_doc_change(Some(&before), doc_status, DocChangeSource::AiCheck, &get_actor_name(), &now);
+                                updated = true;
+
+                                let trace_data = serde_json::json!({
+                                    "doc_key": doc_key,
+                                    "new_extracted_fields": check_result.extracted_fields,
+                                    "last_checked": now,
+                                });
+                                log_info_with_data("ai-check-result", "ドキュメント更新完了", trace_data);
+                            } else {
+                                let trace_data = serde_json::json!({
+                                    "doc_key": doc_key,
+                                    "available_keys": doc_keys,
+                                });
+                                log_error_with_data("ai-check-result", &format!("Doc key '{}' が見つかりません", doc_key), trace_data);
                             }
+                            break;
                         }
                     }
+                    if !updated {
+                        let trace_data = serde_json::json!({
+                            "contractor_name": contractor_name,
+                        });
+                        log_error_with_data("ai-check-result", &format!("業者 '{}' が見つかりません", contractor_name), trace_data);
+                    }
+
+                    // ローカル更新
+                    set_project.set(Some(proj.clone()));
+                    save_to_cache(&proj);
+                    log_info("ai-check-result", "キャッシュに保存完了");
+
+                    // GASに保存
+                    let proj_for_sync = proj.clone();
+                    spawn_local(async move {
+                        match sync_to_gas(&proj_for_sync).await {
+                            Ok(msg) => {
+                                log_info("gas-sync", &format!("GAS保存成功: {}", msg));
+                            }
+                            Err(e) => {
+                                log_error("gas-sync", &format!("GAS保存エラー: {}", e));
+                            }
+                        }
+                    });
                 }
-            }) as Box<dyn FnMut(_)>);
-            window.add_event_listener_with_callback("message", closure.as_ref().unchecked_ref()).ok();
-            closure.forget();
-        });
-    }
+                // チェック結果パネルをクリア
+                set_check_mode.set(CheckMode::None);
+                set_check_results.set(Vec::new());
+                set_view_mode.set(ViewMode::Dashboard);
+            }
+            EditorMessage::AiCheckCancel | EditorMessage::Back => {
+                // チェック結果パネルをクリア
+                set_check_mode.set(CheckMode::None);
+                set_check_results.set(Vec::new());
+                // ホバー状態もリセット
+                set_check_result_tooltip.set(CheckResultTooltipState::default());
+                set_view_mode.set(ViewMode::Dashboard);
+            }
+            _ => {}
+        }
+    });
 
     // 起動時にAPIキー設定をチェック
     set_api_connected.set(check_api_key_exists());
@@ -926,6 +822,9 @@ fn App() -> impl IntoView {
         }
     });
 
+    // 画面とURL（ハッシュルート）の同期
+    install_router(project, view_mode, set_view_mode, set_error_msg);
+
     // JSONファイル読み込み
     let on_file_change = move |ev: web_sys::Event| {
         let input: HtmlInputElement = event_target(&ev);
@@ -963,7 +862,7 @@ fn App() -> impl IntoView {
         set_menu_open.set(false);
         spawn_local(async move {
             set_loading.set(true);
-            match fetch_json("data/sample_project.json").await {
+            match fetch_json::<ProjectData>("data/sample_project.json").await {
                 Ok(data) => {
                     set_project.set(Some(data));
                     set_error_msg.set(None);
@@ -976,6 +875,25 @@ fn App() -> impl IntoView {
         });
     };
 
+    // OCRサンプル読み込み（OCR済みの書類とトークン。項目抽出の確認用）
+    let load_ocr_sample = move |_| {
+        set_menu_open.set(false);
+        spawn_local(async move {
+            set_loading.set(true);
+            match fetch_json::<Vec<OcrDocument>>("data/ocr_documents.json").await {
+                Ok(docs) => {
+                    set_ocr_documents.set(docs);
+                    set_error_msg.set(None);
+                    set_view_mode.set(ViewMode::OcrViewer);
+                }
+                Err(e) => {
+                    set_error_msg.set(Some(e));
+                }
+            }
+            set_loading.set(false);
+        });
+    };
+
     // 共有URL生成
     let generate_share_url = move |_| {
         if let Some(p) = project.get() {
@@ -1049,6 +967,86 @@ fn App() -> impl IntoView {
         }
     };
 
+    // 作業員名簿チェック
+    let on_worker_check = move |_| {
+        set_menu_open.set(false);
+        if let Some(p) = project.get() {
+            let results = rules::run_worker_check(&p, &get_today());
+            set_check_results.set(results);
+            set_check_mode.set(CheckMode::Workers);
+        }
+    };
+
+    // 建設業許可チェック
+    let on_permit_check = move |_| {
+        set_menu_open.set(false);
+        if let Some(p) = project.get() {
+            let results = rules::run_permit_check(&p, &get_today());
+            set_check_results.set(results);
+            set_check_mode.set(CheckMode::Permit);
+        }
+    };
+
+    // 番号形式チェック（事業所番号・労働保険番号）
+    let on_number_check = move |_| {
+        set_menu_open.set(false);
+        if let Some(p) = project.get() {
+            let results = rules::run_number_check(&p);
+            set_check_results.set(results);
+            set_check_mode.set(CheckMode::Numbers);
+        }
+    };
+
+    // CCUS登録チェック（事業者ID・技能者ID）
+    let on_ccus_check = move |_| {
+        set_menu_open.set(false);
+        if let Some(p) = project.get() {
+            let results = rules::run_ccus_check(&p);
+            set_check_results.set(results);
+            set_check_mode.set(CheckMode::Ccus);
+        }
+    };
+
+    // 施工体制台帳要否チェック（下請総額・公共/民間から作成義務を判定）
+    let on_ledger_check = move |_| {
+        set_menu_open.set(false);
+        if let Some(p) = project.get() {
+            let results = rules::run_ledger_check(&p);
+            set_check_results.set(results);
+            set_check_mode.set(CheckMode::Ledger);
+        }
+    };
+
+    // 技術者資格チェック（登録簿の有効期限を工期と照合）
+    let on_person_check = move |_| {
+        set_menu_open.set(false);
+        if let Some(p) = project.get() {
+            let results = rules::run_person_check(&p, &person_registry.get(), &get_today());
+            set_check_results.set(results);
+            set_check_mode.set(CheckMode::Persons);
+        }
+    };
+
+    // 配置技術者チェック（監理/主任・専任の要否を登録簿の資格と照合）
+    let on_engineer_check = move |_| {
+        set_menu_open.set(false);
+        if let Some(p) = project.get() {
+            let results = rules::run_engineer_check(&p, &person_registry.get(), &get_today());
+            set_check_results.set(results);
+            set_check_mode.set(CheckMode::Engineer);
+        }
+    };
+
+    // AIチェック前回比較（解消した指摘・新たな指摘）
+    let on_ai_history_check = move |_| {
+        set_menu_open.set(false);
+        if let Some(p) = project.get() {
+            let results = rules::run_ai_history_check(&p);
+            set_check_results.set(results);
+            set_check_mode.set(CheckMode::AiHistory);
+        }
+    };
+
     // 新規プロジェクト作成
     let on_new_project = move |_| {
         set_menu_open.set(false);
@@ -1060,16 +1058,26 @@ fn App() -> impl IntoView {
             period_end: None,
             site_representative: None,
             chief_engineer: None,
+            site_representative_id: None,
+            chief_engineer_id: None,
+            contract_amount: None,
+            subcontract_total: None,
+            saved_views: Vec::new(),
+            public_works: false,
             project_docs: ProjectDocs::default(),
             contractors: vec![
                 Contractor {
                     id: "prime".to_string(),
                     name: "元請業者".to_string(),
                     role: "元請".to_string(),
+                    email: None,
+                    ccus_business_id: None,
                     docs: HashMap::new(),
+                    workers: Vec::new(),
                 }
             ],
             contracts: Vec::new(),
+            request_template: None,
         };
         set_project.set(Some(new_project));
         set_edit_mode.set(true);
@@ -1081,6 +1089,15 @@ fn App() -> impl IntoView {
         set_edit_mode.update(|e| *e = !*e);
     };
 
+    // CCUS登録状況CSVエクスポート
+    let on_export_ccus_csv = move |_| {
+        set_menu_open.set(false);
+        if let Some(p) = project.get() {
+            let filename = format!("{}_CCUS登録状況.csv", p.project_name.replace(" ", "_").replace("/", "-"));
+            download_file(&filename, "text/csv", &rules::ccus::build_ccus_csv(&p));
+        }
+    };
+
     // JSONエクスポート
     let on_export_json = move |_| {
         set_menu_open.set(false);
@@ -1092,6 +1109,7 @@ fn App() -> impl IntoView {
     view! {
         <div class="app">
             <header class="app-header">
+                <SearchBar />
                 <div class="menu-container">
                     <button class="menu-btn" on:click=move |_| set_menu_open.update(|v| *v = !*v)>
                         "⋮"
@@ -1119,6 +1137,48 @@ fn App() -> impl IntoView {
                             <button class="menu-item" on:click=on_date_check disabled=move || project.get().is_none() || edit_mode.get()>
                                 "日付チェック"
                             </button>
+                            <button class="menu-item" on:click=on_permit_check disabled=move || project.get().is_none() || edit_mode.get()>
+                                "建設業許可チェック"
+                            </button>
+                            <button class="menu-item" on:click=on_number_check disabled=move || project.get().is_none() || edit_mode.get()>
+                                "番号形式チェック"
+                            </button>
+                            <button class="menu-item" on:click=on_ccus_check disabled=move || project.get().is_none() || edit_mode.get()>
+                                "CCUS登録チェック"
+                            </button>
+                            <button class="menu-item" on:click=on_ledger_check disabled=move || project.get().is_none() || edit_mode.get()>
+                                "施工体制台帳要否チェック"
+                            </button>
+                            <button class="menu-item" on:click=on_worker_check disabled=move || project.get().is_none() || edit_mode.get()>
+                                "作業員名簿チェック"
+                            </button>
+                            <button class="menu-item" on:click=on_person_check disabled=move || project.get().is_none() || edit_mode.get()>
+                                "技術者資格チェック"
+                            </button>
+                            <button class="menu-item" on:click=on_engineer_check disabled=move || project.get().is_none() || edit_mode.get()>
+                                "配置技術者チェック"
+                            </button>
+                            <button class="menu-item" on:click=on_ai_history_check disabled=move || project.get().is_none() || edit_mode.get()>
+                                "AIチェック前回比較"
+                            </button>
+                            <button class="menu-item" on:click=move |_| {
+                                set_menu_open.set(false);
+                                set_view_mode.set(ViewMode::RequestLetters);
+                            } disabled=move || project.get().is_none() || edit_mode.get()>
+                                "書類提出依頼"
+                            </button>
+                            <button class="menu-item" on:click=move |_| {
+                                set_menu_open.set(false);
+                                set_view_mode.set(ViewMode::BatchCheck);
+                            } disabled=move || project.get().is_none() || edit_mode.get()>
+                                "AI一括チェック"
+                            </button>
+                            <button class="menu-item" on:click=move |_| {
+                                set_menu_open.set(false);
+                                set_view_mode.set(ViewMode::PersonRegistry);
+                            } disabled=move || edit_mode.get()>
+                                "技術者資格登録簿"
+                            </button>
                             <button class="menu-item" on:click=move |_| {
                                 set_menu_open.set(false);
                                 set_check_mode.set(CheckMode::None);
@@ -1141,9 +1201,12 @@ fn App() -> impl IntoView {
                                     "OCR座標表示"
                                 }}
                             </button>
+                            <button class="menu-item" on:click=load_ocr_sample disabled=move || loading.get()>
+                                "OCRサンプル読込"
+                            </button>
                             <label class="menu-item file-input-label">
-                                "OCRトークンJSON読込"
-                                <input type="file" accept=".json" on:change=move |ev: web_sys::Event| {
+                                "OCR結果読込"
+                                <input type="file" accept=".json,.hocr,.html,.htm,.xml" on:change=move |ev: web_sys::Event| {
                                     let input: HtmlInputElement = event_target(&ev);
                                     if let Some(files) = input.files() {
                                         if let Some(file) = files.get(0) {
@@ -1154,19 +1217,15 @@ fn App() -> impl IntoView {
                                             let onload = Closure::wrap(Box::new(move |_: web_sys::Event| {
                                                 if let Ok(result) = reader_clone.result() {
                                                     if let Some(text) = result.as_string() {
-                                                        match serde_json::from_str::<Vec<OcrToken>>(&text) {
-                                                            Ok(tokens) => {
-                                                                let doc = OcrDocument {
-                                                                    contractor: filename.replace(".json", "").replace("debug_tokens_", ""),
-                                                                    doc_type: "OCR読込".to_string(),
-                                                                    image_url: String::new(),
-                                                                    tokens,
-                                                                };
-                                                                set_ocr_documents.update(|docs| docs.push(doc));
+                                                        // OCR書類・トークンJSON、Document AI、hOCR、ALTO（形式は自動判定）
+                                                        match import_ocr(&filename, &text) {
+                                                            Ok(docs) => {
+                                                                set_ocr_documents.update(|d| d.extend(docs));
                                                                 set_view_mode.set(ViewMode::OcrViewer);
                                                             }
                                                             Err(e) => {
-                                                                web_sys::console::log_1(&format!("OCR JSON解析エラー: {}", e).into());
+                                                                web_sys::console::log_1(&format!("OCR結果の解析エラー: {}", e).into());
+                                                                set_error_msg.set(Some(e));
                                                             }
                                                         }
                                                     }
@@ -1185,6 +1244,9 @@ fn App() -> impl IntoView {
                             <button class="menu-item" on:click=on_export_json disabled=move || project.get().is_none()>
                                 "JSONエクスポート"
                             </button>
+                            <button class="menu-item" on:click=on_export_ccus_csv disabled=move || project.get().is_none()>
+                                "CCUS登録状況CSV"
+                            </button>
                             <button class="menu-item" on:click=generate_share_url disabled=move || project.get().is_none()>
                                 {move || if copy_success.get() { "URLをコピーしました!" } else { "共有URLを生成" }}
                             </button>
@@ -1210,8 +1272,8 @@ fn App() -> impl IntoView {
                                     let window = web_sys::window().unwrap();
                                     let url = "http://localhost:8081/update";
                                     
-                                    let mut init = RequestInit::new();
-                                    init.method("POST");
+                                    let init = RequestInit::new();
+                                    init.set_method("POST");
                                     
                                     match Request::new_with_str_and_init(url, &init) {
                                         Ok(request) => {
@@ -1221,7 +1283,7 @@ fn App() -> impl IntoView {
                                                     if resp.status() == 200 {
                                                         let _ = window.alert_with_message("ヘルスダッシュボードを更新しました！\n\nページをリロードして確認してください。");
                                                     } else {
-                                                        let _ = window.alert_with_message(&format!("更新に失敗しました。\n\n開発サーバーが起動しているか確認してください:\n\nnode scripts/health-report-server.js\n\nまたは、trunk build --release を実行すると自動更新されます。"));
+                                                        let _ = window.alert_with_message("更新に失敗しました。\n\n開発サーバーが起動しているか確認してください:\n\nnode scripts/health-report-server.js\n\nまたは、trunk build --release を実行すると自動更新されます。");
                                                     }
                                                 }
                                                 Err(_) => {
@@ -1234,10 +1296,10 @@ fn App() -> impl IntoView {
                                                     } else {
                                                         "bash scripts/generate-health-report.sh"
                                                     };
-                                                    let promise = clipboard.write_text(&command);
+                                                    let promise = clipboard.write_text(command);
                                                     match JsFuture::from(promise).await {
                                                         Ok(_) => {
-                                                            let _ = window.alert_with_message(&format!("開発サーバーが起動していません。\n\n更新コマンドをクリップボードにコピーしました。\n\nターミナルで実行してください。\n\nまたは、開発サーバーを起動:\nnode scripts/health-report-server.js"));
+                                                            let _ = window.alert_with_message("開発サーバーが起動していません。\n\n更新コマンドをクリップボードにコピーしました。\n\nターミナルで実行してください。\n\nまたは、開発サーバーを起動:\nnode scripts/health-report-server.js");
                                                         }
                                                         Err(_) => {
                                                             let _ = window.alert_with_message("更新方法:\n\n1. 開発サーバーを起動: node scripts/health-report-server.js\n2. または、trunk build --release を実行");
@@ -1392,7 +1454,7 @@ fn App() -> impl IntoView {
                                         let docs: Vec<_> = c.docs.iter().map(|(k, v)| {
                                             let key = k.clone();
                                             let url = v.url.clone().unwrap_or_else(|| "なし".to_string());
-                                            let status = v.status.clone();
+                                            let status = v.status;
                                             view! {
                                                 <li>
                                                     <span class="debug-key">{key}</span>
@@ -1433,27 +1495,29 @@ fn App() -> impl IntoView {
                         </main>
                     }.into_view(),
 
-                    ViewMode::PdfViewer { contractor, doc_type, url, doc_key, contractor_id } => view! {
+                    ViewMode::PdfViewer { contractor, doc_type, url, doc_key, contractor_id, locate } => view! {
                         <PdfViewer
                             contractor=contractor
                             doc_type=doc_type
                             url=url
                             doc_key=doc_key
                             contractor_id=contractor_id
+                            locate=locate
                         />
                     }.into_view(),
 
-                    ViewMode::SpreadsheetViewer { contractor, doc_type, url, doc_key, contractor_id } => view! {
+                    ViewMode::SpreadsheetViewer { contractor, doc_type, url, doc_key, contractor_id, auto_fix } => view! {
                         <SpreadsheetViewer
                             contractor=contractor
                             doc_type=doc_type
                             url=url
                             doc_key=doc_key
                             contractor_id=contractor_id
+                            auto_fix=auto_fix
                         />
                     }.into_view(),
 
-                    ViewMode::PdfEditor { contractor, doc_type, original_url } => view! {
+                    ViewMode::PdfEditor { contractor, doc_type, original_url, .. } => view! {
                         <PdfEditor
                             contractor=contractor
                             doc_type=doc_type
@@ -1462,21 +1526,34 @@ fn App() -> impl IntoView {
                     }.into_view(),
 
                     ViewMode::AiChecker { contractor, doc_type, file_id, doc_key, contractor_id } => {
-                        let gas_url = get_gas_url().unwrap_or_default();
-                        let iframe_url = format!(
-                            "editor/index.html?mode=check&fileId={}&docType={}&contractor={}&gasUrl={}&docKey={}&contractorId={}",
-                            file_id,
-                            js_sys::encode_uri_component(&doc_type),
-                            js_sys::encode_uri_component(&contractor),
-                            js_sys::encode_uri_component(&gas_url),
-                            js_sys::encode_uri_component(&doc_key),
-                            js_sys::encode_uri_component(&contractor_id)
-                        );
+                        let iframe_url = EditorUrl::new(EditorMode::Check)
+                            .file_id(&file_id)
+                            .doc(&contractor, &contractor_id, &doc_type, &doc_key)
+                            .gas_url(get_gas_url().as_deref())
+                            .build();
                         view! {
                             <iframe src=iframe_url style="width: 100%; height: 100vh; border: none;"></iframe>
                         }.into_view()
                     },
 
+                    ViewMode::RequestLetters => view! {
+                        <main class="container">
+                            <RequestLetterView />
+                        </main>
+                    }.into_view(),
+
+                    ViewMode::PersonRegistry => view! {
+                        <main class="container">
+                            <PersonRegistryView />
+                        </main>
+                    }.into_view(),
+
+                    ViewMode::BatchCheck => view! {
+                        <main class="container">
+                            <BatchCheckView />
+                        </main>
+                    }.into_view(),
+
                     ViewMode::ApiKeySetup => view! {
                         <div class="api-key-setup-container">
                             <div class="back-button-container">
@@ -1484,7 +1561,7 @@ fn App() -> impl IntoView {
                                     "← 戻る"
                                 </button>
                             </div>
-                            <iframe src="editor/index.html?mode=apikey" style="width: 100%; height: calc(100vh - 50px); border: none;"></iframe>
+                            <iframe src=EditorUrl::new(EditorMode::ApiKey).build() style="width: 100%; height: calc(100vh - 50px); border: none;"></iframe>
                         </div>
                     }.into_view(),
                 }
@@ -1582,6 +1659,24 @@ fn App() -> impl IntoView {
                                     />
                                 </div>
                             </div>
+                            <div class="gas-step">
+                                <span class="step-num">"5"</span>
+                                <div class="step-content">
+                                    <p class="step-title">"操作者名"</p>
+                                    <p class="step-desc">"書類の変更履歴に記録されます（この端末に保存）"</p>
+                                    <input
+                                        type="text"
+                                        class="gas-url-input"
+                                        placeholder="例: 山田"
+                                        prop:value=move || actor_name.get()
+                                        on:input=move |ev| {
+                                            let value = event_target_value(&ev);
+                                            save_actor_name(&value);
+                                            set_actor_name.set(value);
+                                        }
+                                    />
+                                </div>
+                            </div>
                         </div>
                         <div class="gas-dialog-footer">
                             {move || gas_connected.get().then(|| view! {
@@ -2,6 +2,25 @@
 //!
 //! ## 変更履歴
 //! - 2026-01-03: CheckResultDataにextracted_fields追加（AIチェックで抽出した必須フィールド）
+//! - 2026-10-18: 書類提出依頼テンプレート（RequestLetterTemplate）、Contractor.email追加
+//! - 2026-10-18: 作業員名簿（Worker）をContractorに追加
+//! - 2026-10-18: 技術者資格登録簿（Person）、現場代理人・主任技術者・041/051書類から登録簿を参照
+//! - 2026-10-18: DocStatus.fields（書類項目の手入力）、ProjectData.subcontract_total追加
+//! - 2026-10-18: CCUS事業者ID（Contractor）・技能者ID（Worker）追加
+//! - 2026-10-18: Contractに契約金額・契約日・税区分・工種、ProjectData.public_works追加
+//! - 2026-10-18: ProjectData.contract_amount（元請の請負代金額）追加
+//! - 2026-10-18: ダッシュボードの保存ビュー（ProjectData.saved_views）追加
+//! - 2026-10-18: 書類種別（DocKind / StandardDoc）。書類キーの文字列解析を集約
+//! - 2026-10-18: DocStatus.history（提出状況・URL・AI判定の変更履歴）追加
+//! - 2026-10-18: DocStatus.check_history（AIチェック結果の履歴、直近10回）追加
+//! - 2026-10-18: ViewMode::PdfEditorに業者ID・書類キー追加（URLルーティング用）
+//! - 2026-10-18: 帳票テンプレート（FormTemplate / TemplateField）追加
+//! - 2026-10-18: TemplateFieldに抽出キー・目印（anchor）追加（テンプレート抽出用）
+//! - 2026-10-18: CheckResultData.checker（チェックしたプロバイダー）、DocChangeSource::RuleCheck追加
+//! - 2026-10-18: CheckItem・CheckMissingFieldにページ・領域（page / bbox）追加
+//! - 2026-10-18: FieldType・FieldPositionをmain.rsから移動（データモデルがmain.rsに依存しないように）
+//! - 2026-10-18: OCRトークン（OcrToken / OcrDocument）をOCRビューから移動（抽出・未記入検出をビューから分離）
+//! - 2026-10-18: ViewMode::PdfViewerに指摘箇所（locate）追加（AIチェックの指摘から該当ページを開く）
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -30,11 +49,32 @@ pub struct ProjectData {
     /// 主任技術者
     #[serde(default)]
     pub chief_engineer: Option<String>,
+    /// 現場代理人の登録簿ID（Person.id）
+    #[serde(default)]
+    pub site_representative_id: Option<String>,
+    /// 主任技術者の登録簿ID（Person.id）
+    #[serde(default)]
+    pub chief_engineer_id: Option<String>,
+    /// 元請の下請契約総額（税込・円）。契約一覧に金額がない場合の手入力値
+    #[serde(default)]
+    pub subcontract_total: Option<u64>,
+    /// 元請の請負代金額（税込・円）。技術者の専任要否の判定に使用
+    #[serde(default)]
+    pub contract_amount: Option<u64>,
+    /// 公共工事か（施工体制台帳の作成義務・技術者の専任要否の判定に使用）
+    #[serde(default)]
+    pub public_works: bool,
     #[serde(default)]
     pub project_docs: ProjectDocs,
     pub contractors: Vec<Contractor>,
     #[serde(default)]
     pub contracts: Vec<Contract>,
+    /// 書類提出依頼テンプレート（未設定時は既定文面）
+    #[serde(default)]
+    pub request_template: Option<RequestLetterTemplate>,
+    /// ダッシュボードの保存ビュー（絞り込み条件）
+    #[serde(default)]
+    pub saved_views: Vec<SavedView>,
 }
 
 /// 全体書類（施工体系図、施工体制台帳、下請契約書）
@@ -63,7 +103,291 @@ pub struct Contractor {
     pub id: String,
     pub name: String,
     pub role: String,
+    /// 連絡先メールアドレス（書類提出依頼の宛先）
+    #[serde(default)]
+    pub email: Option<String>,
+    /// 建設キャリアアップシステム（CCUS）事業者ID（14桁）
+    #[serde(default)]
+    pub ccus_business_id: Option<String>,
     pub docs: HashMap<String, DocStatus>,
+    /// 作業員名簿（08_作業員名簿の明細）
+    #[serde(default)]
+    pub workers: Vec<Worker>,
+}
+
+impl Contractor {
+    /// 書類を書類種別の順に並べて返す
+    pub fn sorted_docs(&self) -> Vec<(&String, &DocStatus)> {
+        let mut docs: Vec<_> = self.docs.iter().collect();
+        docs.sort_by_cached_key(|(k, _)| DocKind::parse(k));
+        docs
+    }
+}
+
+// ============================================
+// 書類種別
+// ============================================
+
+/// 標準書類（各社から提出を受ける安全書類）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StandardDoc {
+    /// 建設業許可
+    Permit,
+    /// 事業所番号（雇用保険・健康保険）
+    OfficeNumber,
+    /// 労働保険番号
+    LaborInsurance,
+    /// 現場代理人資格
+    SiteRepresentativeQualification,
+    /// 現場代理人在籍
+    SiteRepresentativeEmployment,
+    /// 主任技術者資格
+    ChiefEngineerQualification,
+    /// 主任技術者在籍
+    ChiefEngineerEmployment,
+    /// 法定外労災
+    ExtraWorkersComp,
+    /// 建退共
+    Kentaikyo,
+    /// 作業員名簿
+    WorkerRoster,
+    /// 暴対法誓約書
+    AntiGangPledge,
+}
+
+impl StandardDoc {
+    pub const ALL: [StandardDoc; 11] = [
+        StandardDoc::Permit,
+        StandardDoc::OfficeNumber,
+        StandardDoc::LaborInsurance,
+        StandardDoc::SiteRepresentativeQualification,
+        StandardDoc::SiteRepresentativeEmployment,
+        StandardDoc::ChiefEngineerQualification,
+        StandardDoc::ChiefEngineerEmployment,
+        StandardDoc::ExtraWorkersComp,
+        StandardDoc::Kentaikyo,
+        StandardDoc::WorkerRoster,
+        StandardDoc::AntiGangPledge,
+    ];
+
+    /// 書類コード（書類キーの接頭番号）
+    pub fn code(&self) -> &'static str {
+        match self {
+            StandardDoc::Permit => "01",
+            StandardDoc::OfficeNumber => "02",
+            StandardDoc::LaborInsurance => "03",
+            StandardDoc::SiteRepresentativeQualification => "041",
+            StandardDoc::SiteRepresentativeEmployment => "042",
+            StandardDoc::ChiefEngineerQualification => "051",
+            StandardDoc::ChiefEngineerEmployment => "052",
+            StandardDoc::ExtraWorkersComp => "06",
+            StandardDoc::Kentaikyo => "07",
+            StandardDoc::WorkerRoster => "08",
+            StandardDoc::AntiGangPledge => "09",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StandardDoc::Permit => "建設業許可",
+            StandardDoc::OfficeNumber => "事業所番号",
+            StandardDoc::LaborInsurance => "労働保険番号",
+            StandardDoc::SiteRepresentativeQualification => "現場代理人資格",
+            StandardDoc::SiteRepresentativeEmployment => "現場代理人在籍",
+            StandardDoc::ChiefEngineerQualification => "主任技術者資格",
+            StandardDoc::ChiefEngineerEmployment => "主任技術者在籍",
+            StandardDoc::ExtraWorkersComp => "法定外労災",
+            StandardDoc::Kentaikyo => "建退共",
+            StandardDoc::WorkerRoster => "作業員名簿",
+            StandardDoc::AntiGangPledge => "暴対法誓約書",
+        }
+    }
+
+    /// 新規作成時の書類キー（例: 041_現場代理人資格）
+    pub fn key(&self) -> String {
+        format!("{}_{}", self.code(), self.label())
+    }
+
+    pub fn from_code(code: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|d| d.code() == code)
+    }
+}
+
+/// 書類種別（書類キーを解釈したもの）
+///
+/// 書類キーは「コード_名称」形式（例: 041_現場代理人資格）。
+/// コードが標準書類と一致すれば名称が異なっても同じ種別とみなす。
+/// 保存データ（Contractor.docs）のキーは文字列のまま変えず、参照時に解釈するため、
+/// 旧データや独自の書類キーもそのまま読み書きできる
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DocKind {
+    key: String,
+    standard: Option<StandardDoc>,
+}
+
+impl DocKind {
+    pub fn parse(key: &str) -> Self {
+        let standard = doc_code(key).and_then(StandardDoc::from_code);
+        DocKind { key: key.to_string(), standard }
+    }
+
+    /// 標準書類の場合はその種別
+    pub fn standard(&self) -> Option<StandardDoc> {
+        self.standard
+    }
+
+    pub fn is(&self, doc: StandardDoc) -> bool {
+        self.standard == Some(doc)
+    }
+
+    /// 書類コード（キーに接頭番号がなければNone）
+    pub fn code(&self) -> Option<&str> {
+        doc_code(&self.key)
+    }
+
+    /// 表示名（標準書類は既定名称、独自書類はキーから接頭番号を除いたもの）
+    pub fn label(&self) -> String {
+        if let Some(doc) = self.standard {
+            return doc.label().to_string();
+        }
+        let name = match self.code() {
+            Some(code) => &self.key[code.len()..],
+            None => self.key.as_str(),
+        };
+        name.replace('_', " ").trim().to_string()
+    }
+
+    /// 並び順のキー（コードの階層順: 01 < 041 < 0411 < 042 < 05 < 10 < 100 < 11、コードなしは末尾）
+    ///
+    /// 先頭2桁が大分類、3桁目以降が枝番のため、2桁に揃えたコードを文字列として比較する
+    pub fn sort_key(&self) -> (bool, String) {
+        match self.code() {
+            Some(code) => (false, format!("{:0>2}", code)),
+            None => (true, String::new()),
+        }
+    }
+}
+
+/// 書類キーの接頭番号（「数字_」で始まる場合）
+fn doc_code(key: &str) -> Option<&str> {
+    let (code, _) = key.split_once('_')?;
+    (!code.is_empty() && code.chars().all(|c| c.is_ascii_digit())).then_some(code)
+}
+
+impl Ord for DocKind {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.sort_key().cmp(&other.sort_key()).then_with(|| self.key.cmp(&other.key))
+    }
+}
+
+impl PartialOrd for DocKind {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// ============================================
+// 作業員名簿
+// ============================================
+
+/// 作業員
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Worker {
+    #[serde(default)]
+    pub name: String,
+    /// 生年月日 (yyyy-MM-dd)
+    #[serde(default)]
+    pub birth_date: Option<String>,
+    /// 職種・役割（例: 職長、オペレーター）
+    #[serde(default)]
+    pub role: String,
+    /// 血液型（例: A, O RH+）
+    #[serde(default)]
+    pub blood_type: Option<String>,
+    #[serde(default)]
+    pub qualifications: Vec<WorkerQualification>,
+    /// 直近の健康診断受診日 (yyyy-MM-dd)
+    #[serde(default)]
+    pub health_check_date: Option<String>,
+    #[serde(default)]
+    pub insurance: WorkerInsurance,
+    /// 建設キャリアアップシステム（CCUS）技能者ID（14桁）
+    #[serde(default)]
+    pub ccus_worker_id: Option<String>,
+}
+
+/// 作業員の資格（特別教育・技能講習・免許）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct WorkerQualification {
+    #[serde(default)]
+    pub kind: QualificationKind,
+    /// 資格名（例: 車両系建設機械運転技能講習）
+    #[serde(default)]
+    pub name: String,
+    #[serde(default)]
+    pub number: Option<String>,
+    /// 有効期限 (yyyy-MM-dd)。期限のない修了証は None
+    #[serde(default)]
+    pub expiry: Option<String>,
+}
+
+/// 資格区分
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum QualificationKind {
+    /// 特別教育
+    SpecialEducation,
+    /// 技能講習
+    SkillTraining,
+    /// 免許
+    License,
+    #[default]
+    Other,
+}
+
+impl QualificationKind {
+    pub const ALL: [QualificationKind; 4] = [
+        QualificationKind::SpecialEducation,
+        QualificationKind::SkillTraining,
+        QualificationKind::License,
+        QualificationKind::Other,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            QualificationKind::SpecialEducation => "特別教育",
+            QualificationKind::SkillTraining => "技能講習",
+            QualificationKind::License => "免許",
+            QualificationKind::Other => "その他",
+        }
+    }
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            QualificationKind::SpecialEducation => "special_education",
+            QualificationKind::SkillTraining => "skill_training",
+            QualificationKind::License => "license",
+            QualificationKind::Other => "other",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL.into_iter().find(|k| k.key() == key).unwrap_or_default()
+    }
+}
+
+/// 作業員の社会保険加入状況
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct WorkerInsurance {
+    /// 健康保険
+    #[serde(default)]
+    pub health: bool,
+    /// 厚生年金
+    #[serde(default)]
+    pub pension: bool,
+    /// 雇用保険
+    #[serde(default)]
+    pub employment: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -83,16 +407,590 @@ pub struct DocStatus {
     pub check_result: Option<CheckResultData>,
     #[serde(default)]
     pub last_checked: Option<String>,
+    /// 資格書類（041/051）の対象者。技術者資格登録簿のPerson.id
+    #[serde(default)]
+    pub person_id: Option<String>,
+    /// 手入力した書類項目（キーはAIチェックのextracted_fieldsと共通）
+    #[serde(default)]
+    pub fields: HashMap<String, String>,
+    /// 変更履歴（追記のみ、古い順）
+    #[serde(default)]
+    pub history: Vec<DocChange>,
+    /// AIチェック結果の履歴（古い順、最新はcheck_resultと同じ）
+    #[serde(default)]
+    pub check_history: Vec<CheckRun>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+/// 書類ごとに保持するAIチェック結果の件数
+pub const CHECK_HISTORY_LIMIT: usize = 10;
+
+impl DocStatus {
+    /// 書類項目の値（手入力を優先し、なければAIチェックの抽出値）
+    pub fn field_value(&self, key: &str) -> Option<&str> {
+        self.fields.get(key)
+            .or_else(|| self.check_result.as_ref().and_then(|r| r.extracted_fields.get(key)))
+            .map(|v| v.trim())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// AIチェック結果を反映し、履歴に追加（上限を超えた古い結果は削除）
+    pub fn push_check_result(&mut self, result: CheckResultData, checked_at: &str, file_id: Option<String>) {
+        // 履歴導入前の結果は前回分として残す
+        if self.check_history.is_empty() {
+            if let Some(previous) = self.check_result.take() {
+                self.check_history.push(CheckRun {
+                    checked_at: self.last_checked.clone().unwrap_or_default(),
+                    file_id: None,
+                    result: previous,
+                });
+            }
+        }
+        self.check_history.push(CheckRun {
+            checked_at: checked_at.to_string(),
+            file_id,
+            result: result.clone(),
+        });
+        let overflow = self.check_history.len().saturating_sub(CHECK_HISTORY_LIMIT);
+        self.check_history.drain(..overflow);
+        self.check_result = Some(result);
+        self.last_checked = Some(checked_at.to_string());
+    }
+
+    /// 直近2回のAIチェック結果の差分（履歴が2件未満はNone）
+    pub fn latest_check_diff(&self) -> Option<CheckResultDiff> {
+        let [.., previous, latest] = self.check_history.as_slice() else {
+            return None;
+        };
+        Some(latest.result.diff(&previous.result))
+    }
+
+    /// 値が変わった場合のみ変更履歴に追記
+    pub fn record_change(&mut self, field: DocChangeField, old: Option<String>, new: Option<String>, source: DocChangeSource, actor: &str, at: &str) {
+        if old == new {
+            return;
+        }
+        self.history.push(DocChange {
+            at: at.to_string(),
+            actor: actor.to_string(),
+            field,
+            old,
+            new,
+            source,
+        });
+    }
+}
+
+// ============================================
+// 書類の変更履歴
+// ============================================
+
+/// 変更された項目
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DocChangeField {
+    /// 提出状況
+    Status,
+    /// 書類URL
+    Url,
+    /// AIチェックの判定
+    AiStatus,
+}
+
+impl DocChangeField {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DocChangeField::Status => "提出状況",
+            DocChangeField::Url => "URL",
+            DocChangeField::AiStatus => "AI判定",
+        }
+    }
+}
+
+/// 変更の経路
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DocChangeSource {
+    /// 編集画面での手入力
+    Manual,
+    /// コンテキストメニューの「修正版を採用」
+    AdoptFixedVersion,
+    /// フォルダ内の最新ファイルへの自動切替（ビューワ・AIチェック読み込み時）
+    LatestFile,
+    /// AIチェック結果の反映
+    AiCheck,
+    /// ローカルのルールチェック結果の反映
+    RuleCheck,
+}
+
+impl DocChangeSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DocChangeSource::Manual => "手動編集",
+            DocChangeSource::AdoptFixedVersion => "修正版採用",
+            DocChangeSource::LatestFile => "最新ファイル自動切替",
+            DocChangeSource::AiCheck => "AIチェック",
+            DocChangeSource::RuleCheck => "ルールチェック",
+        }
+    }
+}
+
+/// 書類の変更履歴1件
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DocChange {
+    /// 変更日時（ISO 8601）
+    pub at: String,
+    /// 操作者名（設定の「操作者名」、未設定は空）
+    #[serde(default)]
+    pub actor: String,
+    pub field: DocChangeField,
+    #[serde(default)]
+    pub old: Option<String>,
+    #[serde(default)]
+    pub new: Option<String>,
+    pub source: DocChangeSource,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
 pub struct Contract {
     #[serde(default)]
     pub name: String,
     #[serde(default)]
     pub url: Option<String>,
+    /// 下請負人（業者名）
     #[serde(default)]
     pub contractor: Option<String>,
+    /// 請負代金額（円）
+    #[serde(default)]
+    pub amount: Option<u64>,
+    /// 契約日 (yyyy-MM-dd)
+    #[serde(default)]
+    pub contract_date: Option<String>,
+    #[serde(default)]
+    pub tax: TaxHandling,
+    /// 工種（例: 舗装工事）
+    #[serde(default)]
+    pub work_type: Option<String>,
+}
+
+impl Contract {
+    /// 下請契約か（業者・金額のない行は台帳等のリンク）
+    pub fn is_subcontract(&self) -> bool {
+        self.contractor.as_deref().is_some_and(|c| !c.trim().is_empty()) || self.amount.is_some()
+    }
+
+    /// 税込金額
+    pub fn amount_with_tax(&self) -> Option<u64> {
+        self.amount.map(|a| match self.tax {
+            TaxHandling::Included => a,
+            TaxHandling::Excluded => a + a * CONSUMPTION_TAX_PERCENT / 100,
+        })
+    }
+}
+
+/// 消費税率（%）
+pub const CONSUMPTION_TAX_PERCENT: u64 = 10;
+
+/// 契約金額の税区分
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TaxHandling {
+    /// 税込
+    #[default]
+    Included,
+    /// 税抜
+    Excluded,
+}
+
+impl TaxHandling {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaxHandling::Included => "税込",
+            TaxHandling::Excluded => "税抜",
+        }
+    }
+}
+
+// ============================================
+// 技術者資格登録簿（プロジェクト横断）
+// ============================================
+
+/// 登録簿の人物（現場代理人・主任技術者・監理技術者など）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct Person {
+    pub id: String,
+    #[serde(default)]
+    pub name: String,
+    /// 所属会社
+    #[serde(default)]
+    pub company: String,
+    #[serde(default)]
+    pub qualifications: Vec<PersonQualification>,
+}
+
+/// 技術者資格（施工管理技士、監理技術者資格者証など）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct PersonQualification {
+    /// 資格名（例: 1級土木施工管理技士）
+    #[serde(default)]
+    pub name: String,
+    /// 資格者証・合格証明書の番号
+    #[serde(default)]
+    pub number: Option<String>,
+    /// 有効期限 (yyyy-MM-dd)。期限のない資格は None
+    #[serde(default)]
+    pub expiry: Option<String>,
+    /// 資格者証の写しへのリンク
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+// ============================================
+// 入力フィールド（OCRの記入欄・帳票テンプレート共通）
+// ============================================
+
+/// 入力フィールドのタイプ
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FieldType {
+    /// 日付入力
+    Date,
+    /// テキスト入力
+    Text,
+    /// 署名
+    Signature,
+    /// 選択肢
+    Select,
+    /// チェックボックス
+    Checkbox,
+}
+
+impl FieldType {
+    pub const ALL: [FieldType; 5] = [
+        FieldType::Text,
+        FieldType::Date,
+        FieldType::Signature,
+        FieldType::Select,
+        FieldType::Checkbox,
+    ];
+
+    /// 表示名
+    pub fn label(&self) -> &'static str {
+        match self {
+            FieldType::Date => "日付",
+            FieldType::Text => "テキスト",
+            FieldType::Signature => "署名・押印",
+            FieldType::Select => "選択肢",
+            FieldType::Checkbox => "チェック",
+        }
+    }
+
+    /// HTML input typeを取得
+    pub fn input_type(&self) -> &'static str {
+        match self {
+            FieldType::Date => "date",
+            FieldType::Text => "text",
+            FieldType::Signature => "text", // 署名は別途処理
+            FieldType::Select => "text",
+            FieldType::Checkbox => "checkbox",
+        }
+    }
+
+    /// プレースホルダーテキストを取得
+    pub fn placeholder(&self) -> &'static str {
+        match self {
+            FieldType::Date => "YYYY-MM-DD",
+            FieldType::Text => "入力してください",
+            FieldType::Signature => "署名",
+            FieldType::Select => "選択してください",
+            FieldType::Checkbox => "",
+        }
+    }
+}
+
+/// フィールドの位置情報（OCRで検出した座標）
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct FieldPosition {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+// ============================================
+// 帳票テンプレート（書類種別ごとの記入欄、プロジェクト横断）
+// ============================================
+
+/// 帳票テンプレート（OCRビューで記入欄の領域を描いて作成）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct FormTemplate {
+    /// 書類種別（OCRドキュメントのdoc_type、例: 09_暴対法誓約書）
+    pub doc_type: String,
+    #[serde(default)]
+    pub fields: Vec<TemplateField>,
+    /// 最終更新日時（ISO 8601）
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+impl FormTemplate {
+    /// 書類種別のテンプレートを検索
+    pub fn find<'a>(templates: &'a [FormTemplate], doc_type: &str) -> Option<&'a FormTemplate> {
+        templates.iter().find(|t| t.doc_type == doc_type)
+    }
+}
+
+fn first_page() -> u32 {
+    1
+}
+
+/// テンプレートの記入欄（位置はページ内の比率 0.0〜1.0）
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TemplateField {
+    pub name: String,
+    pub field_type: FieldType,
+    /// ページ番号（1始まり）
+    #[serde(default = "first_page")]
+    pub page: u32,
+    pub position: FieldPosition,
+    /// 抽出結果のキー（extracted_fieldsと共通。未設定は項目名）
+    #[serde(default)]
+    pub key: Option<String>,
+    /// 目印の文字列（例: 代表者）。書類ごとの位置ずれを目印からの相対位置で補正する
+    #[serde(default)]
+    pub anchor: Option<String>,
+    /// テンプレート作成時の目印の位置
+    #[serde(default)]
+    pub anchor_position: Option<FieldPosition>,
+}
+
+impl TemplateField {
+    /// 抽出結果のキー
+    pub fn extract_key(&self) -> &str {
+        self.key.as_deref().filter(|k| !k.is_empty()).unwrap_or(&self.name)
+    }
+}
+
+// ============================================
+// OCR結果（Document AIのトークン）
+// ============================================
+
+/// OCRで検出されたテキストトークン
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrToken {
+    pub text: String,
+    pub page: u32,
+    pub normalized: NormalizedCoords,
+    pub pixels: PixelCoords,
+    pub page_size: PageSize,
+}
+
+/// 正規化された座標 (0.0〜1.0)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NormalizedCoords {
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+}
+
+/// ピクセル座標
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PixelCoords {
+    pub x: i32,
+    pub y: i32,
+    pub width: i32,
+    pub height: i32,
+}
+
+/// ページサイズ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PageSize {
+    pub width: f64,
+    pub height: f64,
+}
+
+/// OCRドキュメント
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OcrDocument {
+    pub contractor: String,
+    pub doc_type: String,
+    /// 1ページ目の画像（page_images がない場合に使う）
+    pub image_url: String,
+    pub tokens: Vec<OcrToken>,
+    /// ページ画像（1ページ目から順）
+    #[serde(default)]
+    pub page_images: Vec<String>,
+}
+
+/// ページサイズが分からない場合の既定値（A4・約200dpi）
+const DEFAULT_PAGE_SIZE: (f64, f64) = (1681.0, 2378.0);
+
+impl OcrDocument {
+    /// ページ数（ページ画像の数とトークンのページ番号の大きい方）
+    pub fn page_count(&self) -> u32 {
+        let from_tokens = self.tokens.iter().map(|t| t.page).max().unwrap_or(1);
+        from_tokens.max(self.page_images.len() as u32).max(1)
+    }
+
+    /// ページ画像のURL（ページ番号は1始まり）
+    pub fn page_image(&self, page: u32) -> Option<&str> {
+        let url = if self.page_images.is_empty() {
+            (page == 1).then_some(self.image_url.as_str())
+        } else {
+            page.checked_sub(1).and_then(|i| self.page_images.get(i as usize)).map(String::as_str)
+        };
+        url.filter(|u| !u.is_empty())
+    }
+
+    /// ページサイズ（そのページのトークンから）
+    pub fn page_size(&self, page: u32) -> (f64, f64) {
+        self.tokens
+            .iter()
+            .find(|t| t.page == page)
+            .map(|t| (t.page_size.width, t.page_size.height))
+            .unwrap_or(DEFAULT_PAGE_SIZE)
+    }
+}
+
+// ============================================
+// 書類提出依頼
+// ============================================
+
+/// 書類提出依頼の文面テンプレート
+///
+/// 件名・本文中の `{project_name}` `{contractor_name}` `{doc_list}` などの
+/// プレースホルダーは依頼書生成時に置換される。
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RequestLetterTemplate {
+    #[serde(default)]
+    pub subject: String,
+    #[serde(default)]
+    pub body: String,
+}
+
+impl Default for RequestLetterTemplate {
+    fn default() -> Self {
+        RequestLetterTemplate {
+            subject: "【書類提出のお願い】{project_name}".to_string(),
+            body: "{contractor_name} 御中\n\n\
+                   いつも大変お世話になっております。\n\
+                   {project_name}（発注者: {client}）の施工体制書類について、\n\
+                   下記{doc_count}件の書類のご提出・差し替えをお願いいたします。\n\n\
+                   {doc_list}\n\n\
+                   ご多忙のところ恐れ入りますが、よろしくお願い申し上げます。\n\n\
+                   {today}\n\
+                   現場代理人 {site_representative}"
+                .to_string(),
+        }
+    }
+}
+
+// ============================================
+// ダッシュボードの絞り込み
+// ============================================
+
+/// ダッシュボードの絞り込み・並び替え条件
+///
+/// 書類の状態条件（未提出・期限切れなど）は、いずれかに該当する書類を表示する
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct DashboardFilter {
+    /// 未提出
+    #[serde(default)]
+    pub missing: bool,
+    /// 期限切れ
+    #[serde(default)]
+    pub expired: bool,
+    /// N日以内に期限切れ
+    #[serde(default)]
+    pub expiring_days: Option<u32>,
+    /// AIチェックで要対応
+    #[serde(default)]
+    pub ai_error: bool,
+    /// AIチェックで要確認
+    #[serde(default)]
+    pub ai_warning: bool,
+    /// AIチェック未実施
+    #[serde(default)]
+    pub unchecked: bool,
+    /// 業者の役割（完全一致）
+    #[serde(default)]
+    pub role: Option<String>,
+    /// 業者名・書類名・備考の部分一致
+    #[serde(default)]
+    pub text: String,
+    #[serde(default)]
+    pub sort: DashboardSort,
+}
+
+impl DashboardFilter {
+    /// 書類の状態条件が指定されているか
+    pub fn has_doc_conditions(&self) -> bool {
+        self.missing || self.expired || self.expiring_days.is_some() || self.ai_error || self.ai_warning || self.unchecked
+    }
+
+    /// 何らかの絞り込みが指定されているか
+    pub fn is_active(&self) -> bool {
+        self.has_doc_conditions() || self.role.is_some() || !self.text.trim().is_empty()
+    }
+}
+
+/// 業者カードの並び順
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DashboardSort {
+    /// 登録順
+    #[default]
+    Registered,
+    /// 業者名順
+    Name,
+    /// 未提出が多い順
+    MostMissing,
+    /// AI指摘が多い順
+    MostIssues,
+    /// 有効期限が近い順
+    NearestExpiry,
+}
+
+impl DashboardSort {
+    pub const ALL: [DashboardSort; 5] = [
+        DashboardSort::Registered,
+        DashboardSort::Name,
+        DashboardSort::MostMissing,
+        DashboardSort::MostIssues,
+        DashboardSort::NearestExpiry,
+    ];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            DashboardSort::Registered => "registered",
+            DashboardSort::Name => "name",
+            DashboardSort::MostMissing => "most_missing",
+            DashboardSort::MostIssues => "most_issues",
+            DashboardSort::NearestExpiry => "nearest_expiry",
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DashboardSort::Registered => "登録順",
+            DashboardSort::Name => "業者名順",
+            DashboardSort::MostMissing => "未提出が多い順",
+            DashboardSort::MostIssues => "AI指摘が多い順",
+            DashboardSort::NearestExpiry => "期限が近い順",
+        }
+    }
+
+    pub fn from_key(key: &str) -> Self {
+        Self::ALL.into_iter().find(|s| s.key() == key).unwrap_or_default()
+    }
+}
+
+/// 名前を付けて保存した絞り込み条件（例: 今週の督促対象）
+#[derive(Debug, Clone, Serialize, Deserialize, Default, PartialEq)]
+pub struct SavedView {
+    pub name: String,
+    #[serde(default)]
+    pub filter: DashboardFilter,
 }
 
 // ============================================
@@ -113,6 +1011,77 @@ pub struct CheckResultData {
     /// 例: {"representative_name": "山田太郎", "qualification_number": "12345"}
     #[serde(default)]
     pub extracted_fields: HashMap<String, String>,
+    /// チェックしたプロバイダー（checker::DocumentChecker::name。未設定はAIチェック）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checker: Option<String>,
+}
+
+impl CheckResultData {
+    /// 指摘（error/warning）の項目
+    fn issues(&self) -> impl Iterator<Item = &CheckItem> {
+        self.items.iter().filter(|i| matches!(i.item_type.as_str(), "error" | "warning"))
+    }
+
+    /// 指摘のうち、otherに同じメッセージの指摘がないもの
+    fn issues_not_in(&self, other: &CheckResultData) -> Vec<CheckItem> {
+        self.issues()
+            .filter(|i| !other.issues().any(|o| o.message.trim() == i.message.trim()))
+            .cloned()
+            .collect()
+    }
+
+    /// 未記入項目のうち、otherにないもの
+    fn missing_fields_not_in(&self, other: &CheckResultData) -> Vec<String> {
+        self.missing_fields.iter()
+            .filter(|f| !other.missing_fields.iter().any(|o| o.field.trim() == f.field.trim()))
+            .map(|f| f.field.clone())
+            .collect()
+    }
+
+    /// 位置の分かる指摘（error/warning）と未記入項目（表示文言と位置）
+    pub fn located_findings(&self) -> Vec<(String, FindingLocation)> {
+        let issues = self.issues().filter_map(|i| Some((i.message.clone(), i.finding_location()?)));
+        let fields = self.missing_fields.iter().filter_map(|f| Some((format!("未記入: {}", f.field), f.finding_location()?)));
+        issues.chain(fields).collect()
+    }
+
+    /// 前回の結果との差分（指摘はメッセージ、未記入項目は項目名で照合）
+    pub fn diff(&self, previous: &CheckResultData) -> CheckResultDiff {
+        CheckResultDiff {
+            status_before: previous.status.clone(),
+            status_after: self.status.clone(),
+            resolved: previous.issues_not_in(self),
+            added: self.issues_not_in(previous),
+            resolved_fields: previous.missing_fields_not_in(self),
+            added_fields: self.missing_fields_not_in(previous),
+        }
+    }
+}
+
+/// AIチェック1回分の記録
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckRun {
+    /// チェック日時（ISO 8601）
+    pub checked_at: String,
+    /// チェックしたファイルのGoogle DriveファイルID
+    #[serde(default)]
+    pub file_id: Option<String>,
+    pub result: CheckResultData,
+}
+
+/// 2回のAIチェック結果の差分
+#[derive(Debug, Clone, Default)]
+pub struct CheckResultDiff {
+    pub status_before: String,
+    pub status_after: String,
+    /// 解消した指摘（前回のみ）
+    pub resolved: Vec<CheckItem>,
+    /// 新たな指摘（今回のみ）
+    pub added: Vec<CheckItem>,
+    /// 記入された未記入項目
+    pub resolved_fields: Vec<String>,
+    /// 新たな未記入項目
+    pub added_fields: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -120,12 +1089,55 @@ pub struct CheckItem {
     #[serde(rename = "type")]
     pub item_type: String,
     pub message: String,
+    /// 指摘箇所のページ（1始まり）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// 指摘箇所の領域（ページ内の比率 0.0〜1.0）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<FieldPosition>,
+}
+
+impl CheckItem {
+    pub fn finding_location(&self) -> Option<FindingLocation> {
+        FindingLocation::new(self.page, self.bbox.as_ref())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CheckMissingField {
     pub field: String,
+    /// 位置の説明（例: 右下の署名欄）
     pub location: String,
+    /// 記入欄のページ（1始まり）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub page: Option<u32>,
+    /// 記入欄の領域（ページ内の比率 0.0〜1.0）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub bbox: Option<FieldPosition>,
+}
+
+impl CheckMissingField {
+    pub fn finding_location(&self) -> Option<FindingLocation> {
+        FindingLocation::new(self.page, self.bbox.as_ref())
+    }
+}
+
+/// 指摘・未記入項目の書類上の位置（クリックで書類の該当箇所を表示）
+#[derive(Debug, Clone, PartialEq)]
+pub struct FindingLocation {
+    /// ページ（1始まり。領域だけ分かる場合は1ページ目）
+    pub page: u32,
+    pub bbox: Option<FieldPosition>,
+}
+
+impl FindingLocation {
+    /// ページ・領域のいずれかがあれば位置とする
+    pub fn new(page: Option<u32>, bbox: Option<&FieldPosition>) -> Option<Self> {
+        if page.is_none() && bbox.is_none() {
+            return None;
+        }
+        Some(FindingLocation { page: page.unwrap_or(1).max(1), bbox: bbox.cloned() })
+    }
 }
 
 // ============================================
@@ -143,6 +1155,9 @@ pub struct CheckResultTooltipState {
     pub doc_label: String,
     pub check_result: Option<CheckResultData>,
     pub last_checked: Option<String>,
+    /// 前回のAIチェック結果との差分
+    pub check_diff: Option<CheckResultDiff>,
+    pub history: Vec<DocChange>,
     pub hover_timer_id: Option<i32>,
 }
 
@@ -158,14 +1173,16 @@ pub struct ContextMenuState {
     pub doc_label: String,
     pub url: Option<String>,
     pub has_check_result: bool,
+    pub history: Vec<DocChange>,
 }
 
 // ============================================
 // ビューモード
 // ============================================
 
-#[derive(Clone, PartialEq)]
+#[derive(Clone, PartialEq, Default)]
 pub enum ViewMode {
+    #[default]
     Dashboard,
     OcrViewer,
     ApiKeySetup,
@@ -182,6 +1199,8 @@ pub enum ViewMode {
         url: String,
         doc_key: String,
         contractor_id: String,
+        /// 指定した場合、開いた直後にそのページを表示して指摘箇所を強調する
+        locate: Option<FindingLocation>,
     },
     SpreadsheetViewer {
         contractor: String,
@@ -189,18 +1208,22 @@ pub enum ViewMode {
         url: String,
         doc_key: String,
         contractor_id: String,
+        /// trueの場合、開いた直後にAI自動修正モードに入る
+        auto_fix: bool,
     },
     PdfEditor {
         contractor: String,
         doc_type: String,
         original_url: String,
+        doc_key: String,
+        contractor_id: String,
     },
-}
-
-impl Default for ViewMode {
-    fn default() -> Self {
-        ViewMode::Dashboard
-    }
+    /// 書類提出依頼（業者ごとの依頼書・メール下書き）
+    RequestLetters,
+    /// 技術者資格登録簿
+    PersonRegistry,
+    /// AI一括チェック（業者単位・全体）
+    BatchCheck,
 }
 
 // ============================================
@@ -272,3 +1295,108 @@ pub fn detect_file_type(url: &str) -> DocFileType {
         DocFileType::Unknown
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn doc_kind_parses_standard_codes() {
+        let kind = DocKind::parse("041_現場代理人資格");
+        assert_eq!(kind.standard(), Some(StandardDoc::SiteRepresentativeQualification));
+        assert_eq!(kind.code(), Some("041"));
+        assert_eq!(kind.label(), "現場代理人資格");
+        // 名称が違ってもコードが一致すれば同じ種別
+        assert!(DocKind::parse("07_建退共証明").is(StandardDoc::Kentaikyo));
+        for doc in StandardDoc::ALL {
+            assert_eq!(DocKind::parse(&doc.key()).standard(), Some(doc));
+        }
+    }
+
+    #[test]
+    fn doc_kind_custom_keys() {
+        let custom = DocKind::parse("10_安全衛生_計画書");
+        assert_eq!(custom.standard(), None);
+        assert_eq!(custom.code(), Some("10"));
+        assert_eq!(custom.label(), "安全衛生 計画書");
+
+        let no_code = DocKind::parse("施工計画書");
+        assert_eq!(no_code.code(), None);
+        assert_eq!(no_code.label(), "施工計画書");
+        assert_eq!(DocKind::parse("A1_図面").code(), None);
+    }
+
+    #[test]
+    fn doc_kind_sort_order_follows_code_hierarchy() {
+        let mut keys = vec!["施工計画書", "10_その他", "052_主任技術者在籍", "05_旧様式", "041_現場代理人資格", "02_事業所番号", "01_建設業許可", "042_現場代理人在籍"];
+        keys.sort_by_cached_key(|k| DocKind::parse(k));
+        assert_eq!(keys, ["01_建設業許可", "02_事業所番号", "041_現場代理人資格", "042_現場代理人在籍", "05_旧様式", "052_主任技術者在籍", "10_その他", "施工計画書"]);
+    }
+
+    fn check_result(status: &str, issues: &[(&str, &str)], missing: &[&str]) -> CheckResultData {
+        CheckResultData {
+            status: status.to_string(),
+            items: issues
+                .iter()
+                .map(|(t, m)| CheckItem { item_type: t.to_string(), message: m.to_string(), page: None, bbox: None })
+                .collect(),
+            missing_fields: missing
+                .iter()
+                .map(|f| CheckMissingField { field: f.to_string(), location: String::new(), page: None, bbox: None })
+                .collect(),
+            ..CheckResultData::default()
+        }
+    }
+
+    #[test]
+    fn check_result_diff_matches_messages_and_fields() {
+        let previous = check_result("error", &[("error", "押印なし"), ("warning", " 日付が古い "), ("ok", "許可番号あり")], &["氏名", "日付"]);
+        let latest = check_result("warning", &[("warning", "日付が古い"), ("warning", "宛先が違う"), ("ok", "押印あり")], &["日付", "住所"]);
+        let diff = latest.diff(&previous);
+        assert_eq!((diff.status_before.as_str(), diff.status_after.as_str()), ("error", "warning"));
+        // ok の項目は指摘として扱わない。前後の空白は無視して照合
+        assert_eq!(diff.resolved.iter().map(|i| i.message.as_str()).collect::<Vec<_>>(), ["押印なし"]);
+        assert_eq!(diff.added.iter().map(|i| i.message.as_str()).collect::<Vec<_>>(), ["宛先が違う"]);
+        assert_eq!(diff.resolved_fields, ["氏名"]);
+        assert_eq!(diff.added_fields, ["住所"]);
+    }
+
+    #[test]
+    fn located_findings_skip_ok_items_and_unlocated() {
+        let mut result = check_result("error", &[("error", "押印なし"), ("ok", "許可番号あり"), ("warning", "日付が古い")], &["氏名", "日付"]);
+        let bbox = FieldPosition { x: 0.1, y: 0.2, width: 0.3, height: 0.05 };
+        result.items[0].page = Some(2);
+        result.items[1].page = Some(1);
+        result.missing_fields[1].bbox = Some(bbox.clone());
+        let findings = result.located_findings();
+        assert_eq!(
+            findings,
+            [
+                ("押印なし".to_string(), FindingLocation { page: 2, bbox: None }),
+                // 領域だけ分かる場合は1ページ目
+                ("未記入: 日付".to_string(), FindingLocation { page: 1, bbox: Some(bbox) }),
+            ]
+        );
+    }
+
+    #[test]
+    fn latest_check_diff_needs_two_runs() {
+        let run = |result| CheckRun { checked_at: String::new(), file_id: None, result };
+        let mut doc: DocStatus = serde_json::from_str(r#"{"status": true}"#).unwrap();
+        assert!(doc.latest_check_diff().is_none());
+        doc.check_history.push(run(check_result("ok", &[], &[])));
+        assert!(doc.latest_check_diff().is_none());
+        doc.check_history.push(run(check_result("error", &[("error", "押印なし")], &[])));
+        let diff = doc.latest_check_diff().unwrap();
+        assert_eq!((diff.status_before.as_str(), diff.added.len()), ("ok", 1));
+    }
+
+    #[test]
+    fn doc_kind_sort_order_with_three_and_four_digit_codes() {
+        let mut keys = vec!["11_追加", "100_枝番", "0411_枝番", "1_一桁", "10_その他", "1001_枝番", "042_在籍", "041_資格"];
+        keys.sort_by_cached_key(|k| DocKind::parse(k));
+        assert_eq!(keys, ["1_一桁", "041_資格", "0411_枝番", "042_在籍", "10_その他", "100_枝番", "1001_枝番", "11_追加"]);
+        // 同じ並び順にならない（コードの桁数が違えば別の位置）
+        assert_ne!(DocKind::parse("10_a").sort_key(), DocKind::parse("100_a").sort_key());
+    }
+}
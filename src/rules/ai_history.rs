@@ -0,0 +1,142 @@
+//! AIチェック結果の前回比較
+//!
+//! 書類ごとに直近2回のAIチェック結果を比べ、解消した指摘と新たな指摘を一覧にする。
+//! 履歴が1回分しかない書類は対象外
+
+use crate::models::{DocKind, ProjectData};
+use crate::{CheckResult, CheckStatus};
+
+/// AIチェックの判定の表示名
+fn status_label(status: &str) -> &str {
+    match status {
+        "ok" => "OK",
+        "warning" => "要確認",
+        "error" => "要対応",
+        _ => "不明",
+    }
+}
+
+/// 判定の重さ（不明は要確認と同じ扱い）
+fn severity(status: &str) -> u8 {
+    match status {
+        "ok" => 0,
+        "error" => 2,
+        _ => 1,
+    }
+}
+
+/// 全書類のAIチェック結果を前回と比較
+pub fn run_ai_history_check(project: &ProjectData) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    for contractor in &project.contractors {
+        for (doc_key, doc) in contractor.sorted_docs() {
+            let Some(diff) = doc.latest_check_diff() else {
+                continue;
+            };
+            let label = DocKind::parse(doc_key).label();
+            let mut push = |status, message: String| results.push(CheckResult {
+                contractor_name: contractor.name.clone(),
+                doc_name: label.clone(),
+                status,
+                message,
+            });
+
+            if diff.status_before != diff.status_after {
+                // 悪化は今回の判定の重さ、改善は解消として扱う
+                let status = if severity(&diff.status_after) <= severity(&diff.status_before) {
+                    CheckStatus::Ok
+                } else if diff.status_after == "error" {
+                    CheckStatus::Error
+                } else {
+                    CheckStatus::Warning
+                };
+                push(status, format!(
+                    "判定: {} → {}",
+                    status_label(&diff.status_before),
+                    status_label(&diff.status_after)
+                ));
+            }
+            for item in &diff.resolved {
+                push(CheckStatus::Ok, format!("解消: {}", item.message));
+            }
+            for field in &diff.resolved_fields {
+                push(CheckStatus::Ok, format!("記入済み: {}", field));
+            }
+            for item in &diff.added {
+                let status = if item.item_type == "error" { CheckStatus::Error } else { CheckStatus::Warning };
+                push(status, format!("新規: {}", item.message));
+            }
+            for field in &diff.added_fields {
+                push(CheckStatus::Warning, format!("新たな未記入: {}", field));
+            }
+            if diff.resolved.is_empty() && diff.added.is_empty() && diff.resolved_fields.is_empty() && diff.added_fields.is_empty() {
+                push(CheckStatus::Ok, "前回から指摘の増減なし".to_string());
+            }
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+
+    /// AIチェック結果（指摘は (種別, メッセージ)）
+    fn result(status: &str, items: &[(&str, &str)], missing: &[&str]) -> Value {
+        json!({
+            "status": status,
+            "items": items.iter().map(|(t, m)| json!({ "type": t, "message": m })).collect::<Vec<_>>(),
+            "missing_fields": missing.iter().map(|f| json!({ "field": f, "location": "" })).collect::<Vec<_>>()
+        })
+    }
+
+    /// 1業者1書類で、チェック履歴が runs のプロジェクト
+    fn project(runs: &[Value]) -> ProjectData {
+        let history: Vec<_> = runs
+            .iter()
+            .enumerate()
+            .map(|(i, r)| json!({ "checked_at": format!("2026-10-{:02}T09:00:00Z", i + 1), "result": r }))
+            .collect();
+        serde_json::from_value(json!({
+            "project_name": "テスト工事",
+            "contractors": [{
+                "id": "c1",
+                "name": "山田建設",
+                "role": "一次下請",
+                "docs": { "09_暴対法誓約書": { "status": true, "check_history": history } }
+            }]
+        }))
+        .unwrap()
+    }
+
+    fn rows(project: &ProjectData) -> Vec<(CheckStatus, String)> {
+        run_ai_history_check(project).into_iter().map(|r| (r.status, r.message)).collect()
+    }
+
+    #[test]
+    fn verdict_change_status_follows_direction() {
+        let regression = rows(&project(&[result("ok", &[], &[]), result("error", &[("error", "押印なし")], &[])]));
+        assert_eq!(regression[0], (CheckStatus::Error, "判定: OK → 要対応".to_string()));
+        assert_eq!(regression[1], (CheckStatus::Error, "新規: 押印なし".to_string()));
+
+        let to_warning = rows(&project(&[result("ok", &[], &[]), result("warning", &[], &["日付"])]));
+        assert_eq!(to_warning[0], (CheckStatus::Warning, "判定: OK → 要確認".to_string()));
+        assert_eq!(to_warning[1], (CheckStatus::Warning, "新たな未記入: 日付".to_string()));
+
+        let improved = rows(&project(&[result("error", &[("error", "押印なし")], &[]), result("warning", &[], &[])]));
+        assert_eq!(improved[0], (CheckStatus::Ok, "判定: 要対応 → 要確認".to_string()));
+        assert_eq!(improved[1], (CheckStatus::Ok, "解消: 押印なし".to_string()));
+    }
+
+    #[test]
+    fn compares_only_the_latest_two_runs() {
+        assert!(rows(&project(&[result("error", &[], &[])])).is_empty());
+        let unchanged = rows(&project(&[
+            result("error", &[("error", "古い指摘")], &[]),
+            result("warning", &[("warning", "要確認")], &[]),
+            result("warning", &[("warning", "要確認")], &[]),
+        ]));
+        assert_eq!(unchanged, [(CheckStatus::Ok, "前回から指摘の増減なし".to_string())]);
+    }
+}
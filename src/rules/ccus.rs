@@ -0,0 +1,239 @@
+//! 建設キャリアアップシステム（CCUS）ID
+//!
+//! 事業者ID・技能者IDの形式チェック、CCUSから出力したCSVの取り込み、
+//! 登録状況の集計とCSV出力
+
+use crate::models::{Contractor, ProjectData};
+use crate::utils::csv::{parse_csv, to_csv};
+use crate::{CheckResult, CheckStatus};
+use super::digits_only;
+
+/// CCUS ID（事業者ID・技能者ID）の桁数
+pub const CCUS_ID_LEN: usize = 14;
+
+/// CCUS IDを検証し、区切りを除いた14桁に正規化
+pub fn normalize_ccus_id(text: &str) -> Result<String, String> {
+    let digits = digits_only(text).ok_or_else(|| format!("数字以外の文字が含まれています（{}）", text))?;
+    if digits.len() != CCUS_ID_LEN {
+        return Err(format!("{}桁ではありません（{}桁: {}）", CCUS_ID_LEN, digits.len(), text));
+    }
+    Ok(digits)
+}
+
+/// 業者のCCUS登録状況
+#[derive(Debug, Clone, Default)]
+pub struct CcusStatus {
+    /// 事業者IDが有効な形式で登録済み
+    pub business_registered: bool,
+    /// 技能者IDが未登録（または形式不正）の作業員数
+    pub workers_missing: usize,
+}
+
+impl CcusStatus {
+    pub fn of(contractor: &Contractor) -> Self {
+        let is_valid = |id: &Option<String>| id.as_deref().is_some_and(|id| normalize_ccus_id(id).is_ok());
+        CcusStatus {
+            business_registered: is_valid(&contractor.ccus_business_id),
+            workers_missing: contractor.workers.iter().filter(|w| !is_valid(&w.ccus_worker_id)).count(),
+        }
+    }
+}
+
+/// 全業者のCCUS登録チェック
+pub fn run_ccus_check(project: &ProjectData) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    for contractor in &project.contractors {
+        let result = |doc_name: &str, status, message: String| CheckResult {
+            contractor_name: contractor.name.clone(),
+            doc_name: doc_name.to_string(),
+            status,
+            message,
+        };
+
+        match contractor.ccus_business_id.as_deref().filter(|id| !id.trim().is_empty()) {
+            None => results.push(result("CCUS事業者ID", CheckStatus::Warning, "未登録".to_string())),
+            Some(id) => match normalize_ccus_id(id) {
+                Ok(id) => results.push(result("CCUS事業者ID", CheckStatus::Ok, id)),
+                Err(e) => results.push(result("CCUS事業者ID", CheckStatus::Error, e)),
+            },
+        }
+
+        for worker in &contractor.workers {
+            let doc_name = format!("CCUS技能者ID: {}", if worker.name.is_empty() { "(氏名未入力)" } else { &worker.name });
+            match worker.ccus_worker_id.as_deref().filter(|id| !id.trim().is_empty()) {
+                None => results.push(result(&doc_name, CheckStatus::Warning, "未登録".to_string())),
+                Some(id) => match normalize_ccus_id(id) {
+                    Ok(id) => results.push(result(&doc_name, CheckStatus::Ok, id)),
+                    Err(e) => results.push(result(&doc_name, CheckStatus::Error, e)),
+                },
+            }
+        }
+    }
+    results
+}
+
+// ============================================
+// CSV取り込み
+// ============================================
+
+/// CSV取り込み結果
+#[derive(Debug, Clone, Default)]
+pub struct CcusImportSummary {
+    pub contractors_updated: usize,
+    pub workers_updated: usize,
+    /// 名簿と照合できなかった行（事業者名・氏名）
+    pub unmatched: Vec<String>,
+    /// 形式不正でスキップしたID
+    pub invalid: Vec<String>,
+}
+
+/// 会社名の照合用正規化（法人格・空白を除去）
+fn normalize_company(name: &str) -> String {
+    let mut s: String = name.chars().filter(|c| !c.is_whitespace()).collect();
+    for suffix in ["株式会社", "有限会社", "合同会社", "(株)", "（株）", "㈱", "(有)", "（有）", "㈲"] {
+        s = s.replace(suffix, "");
+    }
+    s
+}
+
+/// 氏名の照合用正規化（空白を除去）
+fn normalize_person(name: &str) -> String {
+    name.chars().filter(|c| !c.is_whitespace()).collect()
+}
+
+/// CCUSから出力したCSV（事業者一覧・技能者一覧）を取り込み、IDを業者・作業員に設定
+///
+/// 見出し行から「事業者ID」「技能者ID」「事業者名（商号）」「氏名」列を探して照合する。
+/// 技能者の所属は事業者ID → 事業者名の順で特定し、見つからなければ全業者の名簿から氏名で探す
+pub fn import_ccus_csv(contractors: &mut [Contractor], text: &str) -> Result<CcusImportSummary, String> {
+    let rows = parse_csv(text);
+    let header_idx = rows.iter()
+        .position(|r| r.iter().any(|h| h.contains("事業者ID") || h.contains("技能者ID")))
+        .ok_or("CCUSのCSVではありません（「事業者ID」「技能者ID」列が見つかりません）")?;
+    let header = &rows[header_idx];
+
+    let find_col = |pred: &dyn Fn(&str) -> bool| header.iter().position(|h| pred(h.trim()));
+    let business_id_col = find_col(&|h| h.contains("事業者ID"));
+    let worker_id_col = find_col(&|h| h.contains("技能者ID"));
+    let company_col = find_col(&|h| h.contains("事業者名") || h.contains("商号") || h.contains("会社名"));
+    let person_col = find_col(&|h| h.contains("氏名") && !h.contains("カナ") && !h.contains("ｶﾅ") && !h.contains("フリガナ"));
+
+    let mut summary = CcusImportSummary::default();
+    let cell = |row: &Vec<String>, col: Option<usize>| {
+        col.and_then(|c| row.get(c)).map(|v| v.trim().to_string()).filter(|v| !v.is_empty())
+    };
+
+    for row in &rows[header_idx + 1..] {
+        let business_id = cell(row, business_id_col);
+        let company = cell(row, company_col);
+
+        // 所属業者の特定
+        let by_id = business_id.as_deref()
+            .and_then(|id| normalize_ccus_id(id).ok())
+            .and_then(|id| contractors.iter().position(|c| {
+                c.ccus_business_id.as_deref().and_then(|cid| normalize_ccus_id(cid).ok()).as_deref() == Some(id.as_str())
+            }));
+        let by_name = company.as_deref().and_then(|name| {
+            let name = normalize_company(name);
+            contractors.iter().position(|c| normalize_company(&c.name) == name)
+        });
+        let contractor_idx = by_id.or(by_name);
+
+        match cell(row, worker_id_col) {
+            // 技能者の行
+            Some(worker_id) => {
+                let Some(person) = cell(row, person_col) else { continue };
+                let worker_id = match normalize_ccus_id(&worker_id) {
+                    Ok(id) => id,
+                    Err(e) => {
+                        summary.invalid.push(format!("{}: {}", person, e));
+                        continue;
+                    }
+                };
+                let person_key = normalize_person(&person);
+                let candidates: Vec<usize> = match contractor_idx {
+                    Some(i) => vec![i],
+                    None => (0..contractors.len()).collect(),
+                };
+                let found = candidates.into_iter().find_map(|ci| {
+                    contractors[ci].workers.iter()
+                        .position(|w| normalize_person(&w.name) == person_key)
+                        .map(|wi| (ci, wi))
+                });
+                match found {
+                    Some((ci, wi)) => {
+                        let worker = &mut contractors[ci].workers[wi];
+                        if worker.ccus_worker_id.as_deref() != Some(worker_id.as_str()) {
+                            worker.ccus_worker_id = Some(worker_id);
+                            summary.workers_updated += 1;
+                        }
+                    }
+                    None => summary.unmatched.push(match company {
+                        Some(c) => format!("{}（{}）", person, c),
+                        None => person,
+                    }),
+                }
+            }
+            // 事業者の行
+            None => {
+                let Some(business_id) = business_id else { continue };
+                let business_id = match normalize_ccus_id(&business_id) {
+                    Ok(id) => id,
+                    Err(e) => {
+                        summary.invalid.push(format!("{}: {}", company.unwrap_or_default(), e));
+                        continue;
+                    }
+                };
+                match by_name {
+                    Some(ci) => {
+                        let contractor = &mut contractors[ci];
+                        if contractor.ccus_business_id.as_deref() != Some(business_id.as_str()) {
+                            contractor.ccus_business_id = Some(business_id);
+                            summary.contractors_updated += 1;
+                        }
+                    }
+                    None => summary.unmatched.push(company.unwrap_or(business_id)),
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+// ============================================
+// CSV出力
+// ============================================
+
+/// 業者・作業員のCCUS登録状況CSV
+pub fn build_ccus_csv(project: &ProjectData) -> String {
+    let mut rows = vec![
+        ["業者名", "役割", "CCUS事業者ID", "事業者登録", "作業員", "技能者ID", "技能者登録"]
+            .iter().map(|s| s.to_string()).collect::<Vec<_>>(),
+    ];
+    let registered = |id: &Option<String>| match id.as_deref().filter(|id| !id.trim().is_empty()) {
+        None => "未登録".to_string(),
+        Some(id) if normalize_ccus_id(id).is_ok() => "登録済".to_string(),
+        Some(_) => "形式不正".to_string(),
+    };
+
+    for c in &project.contractors {
+        let base = [
+            c.name.clone(),
+            c.role.clone(),
+            c.ccus_business_id.clone().unwrap_or_default(),
+            registered(&c.ccus_business_id),
+        ];
+        if c.workers.is_empty() {
+            rows.push(base.iter().cloned().chain(["".to_string(), "".to_string(), "".to_string()]).collect());
+        }
+        for w in &c.workers {
+            rows.push(base.iter().cloned().chain([
+                w.name.clone(),
+                w.ccus_worker_id.clone().unwrap_or_default(),
+                registered(&w.ccus_worker_id),
+            ]).collect());
+        }
+    }
+    to_csv(&rows)
+}
@@ -0,0 +1,646 @@
+//! 書類種別ごとのチェックリスト
+//!
+//! AIチェック（react-app の gemini.ts / scripts/document_prompts.py）で確認している項目を
+//! Rust から参照できる形で定義する。チェック結果（items・missing_fields・extracted_fields）と
+//! 照合して、各行を「確認済み」「不備あり」「未評価」に分類する
+
+use crate::models::{CheckResultData, DocKind, StandardDoc};
+use crate::utils::search::normalize_for_search;
+
+/// チェックリストの1行
+pub struct ChecklistItem {
+    /// 確認する内容
+    pub label: &'static str,
+    /// 対応する抽出項目（extracted_fields のキー）
+    pub field: Option<&'static str>,
+    /// 期待する記載・書式
+    pub format: &'static str,
+    /// よくある誤り
+    pub mistakes: &'static [&'static str],
+    /// チェック項目・未記入項目の文言と照合する語句（「番号」「一般」のように他の行の指摘にも
+    /// 含まれる短い語は使わない）
+    pub keywords: &'static [&'static str],
+}
+
+// ============================================
+// 定義
+// ============================================
+
+const PERMIT: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "許可を受けた者の商号が業者名と一致する",
+        field: None,
+        format: "許可通知書・許可証明書の商号又は名称",
+        mistakes: &["商号変更前の通知書", "親会社・関連会社の許可"],
+        keywords: &["商号", "業者名"],
+    },
+    ChecklistItem {
+        label: "許可行政庁・一般/特定",
+        field: Some("permit_category"),
+        format: "国土交通大臣 / ○○県知事、一般 / 特定",
+        mistakes: &["一般と特定の取り違え"],
+        keywords: &["許可行政庁", "一般建設業", "特定建設業", "一般・特定"],
+    },
+    ChecklistItem {
+        label: "許可番号",
+        field: Some("permit_number"),
+        format: "(般-5) 第12345号（6桁以内）",
+        mistakes: &["許可年度の数字を番号と読み違える"],
+        keywords: &["許可番号"],
+    },
+    ChecklistItem {
+        label: "許可業種",
+        field: Some("permit_trades"),
+        format: "土木工事業、舗装工事業 など29業種の名称・略号",
+        mistakes: &["施工する工事の業種が含まれていない"],
+        keywords: &["業種"],
+    },
+    ChecklistItem {
+        label: "許可年月日（有効期間5年）",
+        field: Some("permit_date"),
+        format: "yyyy-MM-dd",
+        mistakes: &["更新前の古い通知書", "有効期間満了日との取り違え"],
+        keywords: &["許可年月日", "有効期間", "期限", "日付"],
+    },
+];
+
+const OFFICE_NUMBER: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "雇用保険事業所番号",
+        field: Some("employment_office_number"),
+        format: "1301-123456-7（4桁-6桁-1桁）",
+        mistakes: &["被保険者番号との取り違え", "チェックディジットの誤記"],
+        keywords: &["雇用保険", "事業所番号"],
+    },
+    ChecklistItem {
+        label: "健康保険 事業所整理記号・保険者番号",
+        field: Some("health_insurer_number"),
+        format: "整理記号 01-イロハ、保険者番号8桁（国民健康保険は6桁）",
+        mistakes: &["記号・番号（被保険者）との取り違え"],
+        keywords: &["健康保険", "整理記号", "保険者番号"],
+    },
+    ChecklistItem {
+        label: "事業所名が業者名と一致する",
+        field: None,
+        format: "適用事業所の名称",
+        mistakes: &["支店・営業所単位の番号"],
+        keywords: &["事業所名", "業者名"],
+    },
+];
+
+const LABOR_INSURANCE: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "労働保険番号",
+        field: Some("labor_insurance_number"),
+        format: "13-1-01-123456-000（14桁）",
+        mistakes: &["所掌・管轄の桁の欠落", "雇用保険事業所番号との取り違え"],
+        keywords: &["労働保険番号"],
+    },
+    ChecklistItem {
+        label: "口座振替日（引落日）",
+        field: None,
+        format: "領収済通知書・口座振替結果のお知らせの日付",
+        mistakes: &["申告書のみで納付が確認できない"],
+        keywords: &["口座振替", "引落", "納付"],
+    },
+    ChecklistItem {
+        label: "口座名義が業者名と一致する",
+        field: None,
+        format: "法人名義の口座",
+        mistakes: &["代表者個人の口座"],
+        keywords: &["口座名義", "業者名"],
+    },
+];
+
+const SITE_REPRESENTATIVE_QUALIFICATION: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "現場代理人の氏名",
+        field: Some("representative_name"),
+        format: "フルネーム（登録した現場代理人と同じ）",
+        mistakes: &["別の技術者の資格証"],
+        keywords: &["氏名", "現場代理人"],
+    },
+    ChecklistItem {
+        label: "資格番号",
+        field: Some("qualification_number"),
+        format: "合格証明書・免許証・資格者証の番号",
+        mistakes: &["交付番号と受験番号の取り違え"],
+        keywords: &["資格番号", "登録番号", "証明書番号", "交付番号"],
+    },
+    ChecklistItem {
+        label: "資格の種類・有効期限",
+        field: None,
+        format: "技術検定・免許の名称、有効期限（ある場合）",
+        mistakes: &["有効期限切れの資格者証", "講習修了証のみ"],
+        keywords: &["資格", "有効期限", "期限"],
+    },
+];
+
+const SITE_REPRESENTATIVE_EMPLOYMENT: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "現場代理人の氏名",
+        field: Some("representative_name"),
+        format: "フルネーム（登録した現場代理人と同じ）",
+        mistakes: &["家族の保険証"],
+        keywords: &["氏名", "現場代理人"],
+    },
+    ChecklistItem {
+        label: "事業所名が業者名と一致する",
+        field: None,
+        format: "健康保険証・在籍証明書・雇用証明書・社員証の事業所名",
+        mistakes: &["国民健康保険証（事業所名がない）", "出向元の会社名"],
+        keywords: &["事業所", "会社名", "業者名"],
+    },
+];
+
+const CHIEF_ENGINEER_QUALIFICATION: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "主任技術者の氏名",
+        field: Some("chief_engineer_name"),
+        format: "フルネーム（登録した主任技術者と同じ）",
+        mistakes: &["別の技術者の資格証"],
+        keywords: &["氏名", "主任技術者"],
+    },
+    ChecklistItem {
+        label: "資格番号",
+        field: Some("qualification_number"),
+        format: "合格証明書・免許証・資格者証の番号",
+        mistakes: &["交付番号と受験番号の取り違え"],
+        keywords: &["資格番号", "登録番号", "証明書番号", "交付番号"],
+    },
+    ChecklistItem {
+        label: "資格が許可業種の主任技術者要件を満たす",
+        field: None,
+        format: "業種に対応する技術検定・免許、または実務経験",
+        mistakes: &["業種に対応しない資格", "有効期限切れの資格者証"],
+        keywords: &["資格", "業種", "有効期限", "期限"],
+    },
+];
+
+const CHIEF_ENGINEER_EMPLOYMENT: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "主任技術者の氏名",
+        field: Some("chief_engineer_name"),
+        format: "フルネーム（登録した主任技術者と同じ）",
+        mistakes: &["家族の保険証"],
+        keywords: &["氏名", "主任技術者"],
+    },
+    ChecklistItem {
+        label: "事業所名が業者名と一致する（直接的かつ恒常的な雇用）",
+        field: None,
+        format: "健康保険証・在籍証明書・雇用証明書・社員証の事業所名",
+        mistakes: &["国民健康保険証（事業所名がない）", "出向・派遣の技術者"],
+        keywords: &["事業所", "会社名", "業者名", "雇用"],
+    },
+];
+
+const EXTRA_WORKERS_COMP: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "保険会社名・保険種別",
+        field: None,
+        format: "加入者証・保険証券の保険会社名",
+        mistakes: &["見積書・申込書のみ"],
+        keywords: &["保険会社", "保険種別"],
+    },
+    ChecklistItem {
+        label: "被保険者が業者名と一致する",
+        field: None,
+        format: "被保険者（加入者）の法人名",
+        mistakes: &["元請・組合名義の証明"],
+        keywords: &["被保険者", "加入者", "業者名"],
+    },
+    ChecklistItem {
+        label: "保険期間が工期をカバーしている",
+        field: None,
+        format: "始期・終期（終期が工期末以降）",
+        mistakes: &["前年度の証券", "工期中に満期を迎える"],
+        keywords: &["保険期間", "始期", "終期", "期限", "失効"],
+    },
+];
+
+const KENTAIKYO: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "建退共の加入が確認できる",
+        field: None,
+        format: "共済契約者証・掛金収納書",
+        mistakes: &["中退共（中小企業退職金共済）の書類"],
+        keywords: &["建退共", "退職金共済", "共済契約者"],
+    },
+    ChecklistItem {
+        label: "契約者名が業者名と一致する",
+        field: None,
+        format: "共済契約者の法人名",
+        mistakes: &["元請の証紙購入証明"],
+        keywords: &["契約者", "業者名"],
+    },
+];
+
+const WORKER_ROSTER: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "作業員の氏名",
+        field: None,
+        format: "全員のフルネーム・フリガナ",
+        mistakes: &["事業者名・職長欄のみ記入"],
+        keywords: &["氏名"],
+    },
+    ChecklistItem {
+        label: "生年月日・住所",
+        field: None,
+        format: "生年月日（年齢）・現住所",
+        mistakes: &["18歳未満の作業員の記載漏れ"],
+        keywords: &["生年月日", "年齢", "住所"],
+    },
+    ChecklistItem {
+        label: "資格・免許",
+        field: None,
+        format: "技能講習・特別教育・免許の名称",
+        mistakes: &["有資格作業の資格が未記載"],
+        keywords: &["資格", "免許", "技能講習"],
+    },
+    ChecklistItem {
+        label: "健康保険・年金・雇用保険の加入状況",
+        field: None,
+        format: "保険の名称（加入していない場合は「適用除外」）",
+        mistakes: &["空欄のまま（未加入との区別ができない）"],
+        keywords: &["健康保険", "年金", "雇用保険", "社会保険"],
+    },
+    ChecklistItem {
+        label: "雇入年月日",
+        field: None,
+        format: "yyyy-MM-dd（入場・退場・受入教育の日付は空欄でよい）",
+        mistakes: &["入場年月日との取り違え"],
+        keywords: &["雇入"],
+    },
+];
+
+const ANTI_GANG_PLEDGE: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "日付",
+        field: Some("pledge_date"),
+        format: "令和○年○月○日",
+        mistakes: &["「令和 年 月 日」のまま空欄", "提出日より後の日付"],
+        keywords: &["日付", "誓約日", "年月日"],
+    },
+    ChecklistItem {
+        label: "宛先（発注者・元請）",
+        field: Some("recipient"),
+        format: "○○株式会社 殿 / 御中",
+        mistakes: &["宛先が空欄", "自社名を宛先に記入"],
+        keywords: &["宛先", "発注者"],
+    },
+    ChecklistItem {
+        label: "誓約者の住所",
+        field: Some("address"),
+        format: "本店所在地",
+        mistakes: &["営業所の住所"],
+        keywords: &["住所", "所在地"],
+    },
+    ChecklistItem {
+        label: "誓約者の法人名",
+        field: Some("company_name"),
+        format: "商号又は名称（業者名と同じ）",
+        mistakes: &["略称・屋号のみ"],
+        keywords: &["法人名", "誓約者", "商号"],
+    },
+    ChecklistItem {
+        label: "代表者の役職と氏名",
+        field: Some("representative_name"),
+        format: "代表取締役 ○○ ○○",
+        mistakes: &["役職の記載漏れ", "担当者名の記入"],
+        keywords: &["代表者"],
+    },
+    ChecklistItem {
+        label: "押印",
+        field: None,
+        format: "代表者印（丸印）・角印",
+        mistakes: &["印欄が空欄"],
+        keywords: &["印鑑", "押印", "印欄"],
+    },
+];
+
+const SUBCONTRACT: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "契約日",
+        field: None,
+        format: "令和○年○月○日（着工日より前）",
+        mistakes: &["着工後の契約日"],
+        keywords: &["契約日", "日付"],
+    },
+    ChecklistItem {
+        label: "工事名・工事場所",
+        field: None,
+        format: "発注者との契約と同じ工事名",
+        mistakes: &["工事場所の記載漏れ"],
+        keywords: &["工事名", "工事場所"],
+    },
+    ChecklistItem {
+        label: "工期（着工日・完成日）",
+        field: None,
+        format: "着工日〜完成日",
+        mistakes: &["元請の工期を超える工期"],
+        keywords: &["工期", "着工", "完成"],
+    },
+    ChecklistItem {
+        label: "請負代金",
+        field: None,
+        format: "金額（消費税の内訳）",
+        mistakes: &["「別途見積」のみ"],
+        keywords: &["請負代金", "金額"],
+    },
+    ChecklistItem {
+        label: "双方の記名押印・収入印紙",
+        field: None,
+        format: "元請・下請の記名押印、契約金額に応じた収入印紙",
+        mistakes: &["収入印紙の貼付漏れ・消印漏れ"],
+        keywords: &["押印", "記名", "印紙"],
+    },
+];
+
+const LEDGER: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "工事名・工事場所・工期",
+        field: None,
+        format: "発注者との契約と同じ内容",
+        mistakes: &["工期の変更が反映されていない"],
+        keywords: &["工事名", "工事場所", "工期"],
+    },
+    ChecklistItem {
+        label: "発注者・元請負人の情報（許可番号含む）",
+        field: None,
+        format: "名称・住所・建設業許可番号",
+        mistakes: &["許可番号の記載漏れ"],
+        keywords: &["発注者", "元請", "許可番号"],
+    },
+    ChecklistItem {
+        label: "監理技術者・主任技術者の資格",
+        field: None,
+        format: "氏名・資格名・資格者証番号",
+        mistakes: &["専任の要否の記載漏れ"],
+        keywords: &["監理技術者", "主任技術者", "資格"],
+    },
+    ChecklistItem {
+        label: "下請負人の情報",
+        field: None,
+        format: "全ての下請負人の名称・工事内容・工期",
+        mistakes: &["二次以下の下請負人の記載漏れ"],
+        keywords: &["下請"],
+    },
+];
+
+const RESUBCONTRACT_NOTICE: &[ChecklistItem] = &[
+    ChecklistItem {
+        label: "通知日・宛先（元請負人）",
+        field: None,
+        format: "令和○年○月○日、元請負人の名称",
+        mistakes: &["直近上位の下請負人を宛先にしている"],
+        keywords: &["通知日", "宛先", "日付"],
+    },
+    ChecklistItem {
+        label: "再下請負人の情報",
+        field: None,
+        format: "社名・住所・建設業許可番号",
+        mistakes: &["許可番号の記載漏れ"],
+        keywords: &["再下請", "許可番号", "社名"],
+    },
+    ChecklistItem {
+        label: "工事内容・工期・契約金額",
+        field: None,
+        format: "再下請負契約の内容",
+        mistakes: &["工期が上位の工期を超える"],
+        keywords: &["工事内容", "工期", "金額"],
+    },
+    ChecklistItem {
+        label: "通知者の記名押印",
+        field: None,
+        format: "通知者の名称・代表者・押印",
+        mistakes: &["押印漏れ"],
+        keywords: &["押印", "記名"],
+    },
+];
+
+/// 標準書類以外でチェックリストがある書類（名称で判定）
+const NAMED_CHECKLISTS: &[(&str, &[ChecklistItem])] = &[
+    ("再下請負通知書", RESUBCONTRACT_NOTICE),
+    ("下請負契約書", SUBCONTRACT),
+    ("施工体制台帳", LEDGER),
+];
+
+/// 書類キーに対応するチェックリスト（定義のない書類は空）
+pub fn checklist_for_doc(doc_key: &str) -> &'static [ChecklistItem] {
+    let kind = DocKind::parse(doc_key);
+    match kind.standard() {
+        Some(StandardDoc::Permit) => PERMIT,
+        Some(StandardDoc::OfficeNumber) => OFFICE_NUMBER,
+        Some(StandardDoc::LaborInsurance) => LABOR_INSURANCE,
+        Some(StandardDoc::SiteRepresentativeQualification) => SITE_REPRESENTATIVE_QUALIFICATION,
+        Some(StandardDoc::SiteRepresentativeEmployment) => SITE_REPRESENTATIVE_EMPLOYMENT,
+        Some(StandardDoc::ChiefEngineerQualification) => CHIEF_ENGINEER_QUALIFICATION,
+        Some(StandardDoc::ChiefEngineerEmployment) => CHIEF_ENGINEER_EMPLOYMENT,
+        Some(StandardDoc::ExtraWorkersComp) => EXTRA_WORKERS_COMP,
+        Some(StandardDoc::Kentaikyo) => KENTAIKYO,
+        Some(StandardDoc::WorkerRoster) => WORKER_ROSTER,
+        Some(StandardDoc::AntiGangPledge) => ANTI_GANG_PLEDGE,
+        None => {
+            let label = kind.label();
+            NAMED_CHECKLISTS.iter().find(|(name, _)| label.contains(name)).map(|(_, items)| *items).unwrap_or(&[])
+        }
+    }
+}
+
+// ============================================
+// 評価
+// ============================================
+
+/// チェックリストの行の評価
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChecklistState {
+    /// 確認済み（抽出値がある、または OK の指摘がある）
+    Satisfied,
+    /// 不備あり（警告・エラーの指摘、または未記入項目がある）
+    Failed,
+    /// チェック結果に該当する記載がない
+    NotEvaluated,
+}
+
+impl ChecklistState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChecklistState::Satisfied => "確認済み",
+            ChecklistState::Failed => "不備あり",
+            ChecklistState::NotEvaluated => "未評価",
+        }
+    }
+
+    pub fn icon(&self) -> &'static str {
+        match self {
+            ChecklistState::Satisfied => "✓",
+            ChecklistState::Failed => "✗",
+            ChecklistState::NotEvaluated => "−",
+        }
+    }
+
+    /// CSSクラス名
+    pub fn class(&self) -> &'static str {
+        match self {
+            ChecklistState::Satisfied => "satisfied",
+            ChecklistState::Failed => "failed",
+            ChecklistState::NotEvaluated => "not-evaluated",
+        }
+    }
+}
+
+/// 評価したチェックリストの行
+#[derive(Clone)]
+pub struct ChecklistLine {
+    pub item: &'static ChecklistItem,
+    pub state: ChecklistState,
+    /// 判定の根拠（指摘の文言・抽出値）
+    pub evidence: Option<String>,
+}
+
+/// 文言がいずれかの語句を含むか（表記ゆれを正規化して比較）
+fn mentions(text: &str, keywords: &[&str]) -> bool {
+    let text = normalize_for_search(text);
+    keywords.iter().any(|k| text.contains(&normalize_for_search(k)))
+}
+
+/// チェック結果と照合してチェックリストを評価する
+///
+/// 警告・エラーの指摘と未記入項目を優先し、次に抽出値、OK の指摘の順に判定する
+pub fn evaluate_checklist(doc_key: &str, result: &CheckResultData) -> Vec<ChecklistLine> {
+    checklist_for_doc(doc_key)
+        .iter()
+        .map(|item| {
+            let issue = result
+                .items
+                .iter()
+                .find(|i| matches!(i.item_type.as_str(), "warning" | "error") && mentions(&i.message, item.keywords))
+                .map(|i| i.message.clone());
+            let missing = result
+                .missing_fields
+                .iter()
+                .find(|f| mentions(&f.field, item.keywords))
+                .map(|f| format!("未記入: {}（{}）", f.field, f.location));
+            let extracted = item
+                .field
+                .and_then(|key| result.extracted_fields.get(key))
+                .filter(|v| !v.trim().is_empty())
+                .map(|v| format!("抽出値: {}", v));
+            let ok = result
+                .items
+                .iter()
+                .find(|i| i.item_type == "ok" && mentions(&i.message, item.keywords))
+                .map(|i| i.message.clone());
+
+            let (state, evidence) = match (issue.or(missing), extracted.or(ok)) {
+                (Some(e), _) => (ChecklistState::Failed, Some(e)),
+                (None, Some(e)) => (ChecklistState::Satisfied, Some(e)),
+                (None, None) => (ChecklistState::NotEvaluated, None),
+            };
+            ChecklistLine { item, state, evidence }
+        })
+        .collect()
+}
+
+/// 評価の件数（例: 確認済み3 / 不備あり1 / 未評価2）
+pub fn checklist_summary(lines: &[ChecklistLine]) -> String {
+    let count = |state: ChecklistState| lines.iter().filter(|l| l.state == state).count();
+    format!(
+        "確認済み{} / 不備あり{} / 未評価{}",
+        count(ChecklistState::Satisfied),
+        count(ChecklistState::Failed),
+        count(ChecklistState::NotEvaluated),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    const PERMIT_KEY: &str = "01_建設業許可";
+
+    fn result(items: &[(&str, &str)], missing: &[&str], extracted: &[(&str, &str)]) -> CheckResultData {
+        let items: Vec<_> = items.iter().map(|(t, m)| json!({ "type": t, "message": m })).collect();
+        let missing: Vec<_> = missing.iter().map(|f| json!({ "field": f, "location": "欄" })).collect();
+        let extracted: HashMap<_, _> = extracted.iter().copied().collect();
+        serde_json::from_value(json!({
+            "status": "warning",
+            "summary": "",
+            "items": items,
+            "missing_fields": missing,
+            "extracted_fields": extracted,
+        }))
+        .unwrap()
+    }
+
+    /// 行ごとの評価（ラベルと状態）
+    fn states(doc_key: &str, result: &CheckResultData) -> Vec<(&'static str, ChecklistState)> {
+        evaluate_checklist(doc_key, result).into_iter().map(|l| (l.item.label, l.state)).collect()
+    }
+
+    fn state_of(doc_key: &str, result: &CheckResultData, label: &str) -> ChecklistState {
+        states(doc_key, result).into_iter().find(|(l, _)| *l == label).map(|(_, s)| s).unwrap()
+    }
+
+    #[test]
+    fn empty_result_is_not_evaluated() {
+        let lines = evaluate_checklist(PERMIT_KEY, &result(&[], &[], &[]));
+        assert_eq!(lines.len(), PERMIT.len());
+        assert!(lines.iter().all(|l| l.state == ChecklistState::NotEvaluated && l.evidence.is_none()));
+        // 定義のない書類はチェックリストなし
+        assert!(evaluate_checklist("99_その他", &result(&[("error", "許可番号がない")], &[], &[])).is_empty());
+    }
+
+    #[test]
+    fn extracted_value_or_ok_item_satisfies() {
+        let r = result(&[("ok", "許可業種に土木工事業が含まれている")], &[], &[("permit_number", "(般-5) 第12345号"), ("permit_date", " ")]);
+        let lines = evaluate_checklist(PERMIT_KEY, &r);
+        let number = lines.iter().find(|l| l.item.label == "許可番号").unwrap();
+        assert_eq!((number.state, number.evidence.as_deref()), (ChecklistState::Satisfied, Some("抽出値: (般-5) 第12345号")));
+        assert_eq!(state_of(PERMIT_KEY, &r, "許可業種"), ChecklistState::Satisfied);
+        // 空の抽出値は確認済みにしない
+        assert_eq!(state_of(PERMIT_KEY, &r, "許可年月日（有効期間5年）"), ChecklistState::NotEvaluated);
+    }
+
+    #[test]
+    fn issues_and_missing_fields_fail_before_extracted_values() {
+        let r = result(
+            &[("warning", "許可番号の桁数が多い"), ("info", "業種の略号で記載")],
+            &["許可年月日"],
+            &[("permit_number", "第1234567号")],
+        );
+        let lines = evaluate_checklist(PERMIT_KEY, &r);
+        let number = lines.iter().find(|l| l.item.label == "許可番号").unwrap();
+        assert_eq!((number.state, number.evidence.as_deref()), (ChecklistState::Failed, Some("許可番号の桁数が多い")));
+        let date = lines.iter().find(|l| l.item.label == "許可年月日（有効期間5年）").unwrap();
+        assert_eq!((date.state, date.evidence.as_deref()), (ChecklistState::Failed, Some("未記入: 許可年月日（欄）")));
+        // info は判定に使わない
+        assert_eq!(state_of(PERMIT_KEY, &r, "許可業種"), ChecklistState::NotEvaluated);
+        assert_eq!(checklist_summary(&lines), "確認済み0 / 不備あり2 / 未評価3");
+    }
+
+    #[test]
+    fn keyword_matching_is_per_line() {
+        // 「許可番号」の指摘は許可行政庁・一般/特定の行を不備にしない
+        let r = result(&[("error", "許可番号が読み取れない"), ("warning", "一般的な様式と異なる")], &[], &[]);
+        assert_eq!(state_of(PERMIT_KEY, &r, "許可番号"), ChecklistState::Failed);
+        assert_eq!(state_of(PERMIT_KEY, &r, "許可行政庁・一般/特定"), ChecklistState::NotEvaluated);
+        let r = result(&[("warning", "特定建設業の許可が必要")], &[], &[]);
+        assert_eq!(state_of(PERMIT_KEY, &r, "許可行政庁・一般/特定"), ChecklistState::Failed);
+
+        // 資格者証の「番号」だけの指摘（技能者番号など）は資格番号の行に当てない
+        let key = StandardDoc::ChiefEngineerQualification.key();
+        let r = result(&[("warning", "CCUS技能者番号の記載がない")], &[], &[]);
+        assert_eq!(state_of(&key, &r, "資格番号"), ChecklistState::NotEvaluated);
+        let r = result(&[("warning", "合格証明書番号が不鮮明")], &[], &[]);
+        assert_eq!(state_of(&key, &r, "資格番号"), ChecklistState::Failed);
+
+        // 語句を含む行はすべて不備になる（「資格番号」の指摘は「資格」の行にも当たる）
+        assert_eq!(state_of(&key, &r, "資格が許可業種の主任技術者要件を満たす"), ChecklistState::NotEvaluated);
+        let r = result(&[("warning", "資格番号が不鮮明")], &[], &[]);
+        assert_eq!(state_of(&key, &r, "資格番号"), ChecklistState::Failed);
+        assert_eq!(state_of(&key, &r, "資格が許可業種の主任技術者要件を満たす"), ChecklistState::Failed);
+    }
+}
@@ -0,0 +1,310 @@
+//! 配置技術者（監理技術者・主任技術者）と専任の要否判定
+//!
+//! 元請の下請契約総額から監理技術者の要否を、請負代金額から専任の要否を判定し、
+//! 主任技術者欄・051書類が参照する登録簿の資格と照合する。
+//! - 監理技術者: 下請契約総額が5,000万円（建築一式は8,000万円）以上
+//! - 専任: 請負代金額が4,500万円（建築一式は9,000万円）以上（令和7年2月施行の金額）
+
+use crate::models::{Contractor, DocKind, Person, PersonQualification, ProjectData, StandardDoc};
+use crate::utils::format_yen;
+use crate::utils::registry::find_person;
+use crate::{CheckResult, CheckStatus};
+use super::ledger::{is_building_work, subcontract_total, threshold};
+use super::permit::{is_prime, trades_for_role};
+
+/// 専任が必要となる請負代金額
+pub const DEDICATED_THRESHOLD: u64 = 45_000_000;
+/// 専任が必要となる請負代金額（建築一式工事）
+pub const DEDICATED_THRESHOLD_BUILDING: u64 = 90_000_000;
+
+/// 配置すべき技術者の種別
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EngineerKind {
+    /// 監理技術者
+    Supervising,
+    /// 主任技術者
+    Chief,
+}
+
+impl EngineerKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EngineerKind::Supervising => "監理技術者",
+            EngineerKind::Chief => "主任技術者",
+        }
+    }
+}
+
+/// 専任の要否
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dedication {
+    NotRequired,
+    Required,
+    /// 民間工事: 個人住宅・長屋以外は専任が必要
+    RequiredUnlessResidence,
+    /// 請負代金額が未入力
+    Unknown,
+}
+
+/// 元請の配置技術者の判定結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct EngineerRequirement {
+    pub kind: EngineerKind,
+    pub dedication: Dedication,
+    /// 監理/主任の判定理由（画面表示用）
+    pub kind_reason: String,
+    /// 専任の判定理由（画面表示用）
+    pub dedication_reason: String,
+}
+
+/// 専任が必要となる請負代金額
+fn dedicated_threshold(building: bool) -> u64 {
+    if building { DEDICATED_THRESHOLD_BUILDING } else { DEDICATED_THRESHOLD }
+}
+
+/// 元請の配置技術者（監理/主任）と専任の要否を判定
+pub fn determine_engineer_requirement(project: &ProjectData) -> EngineerRequirement {
+    let supervising_threshold = threshold(project);
+    let (kind, kind_reason) = match subcontract_total(project) {
+        Some(total) if total.amount >= supervising_threshold => (EngineerKind::Supervising, format!(
+            "下請総額 {} が {} 以上のため監理技術者が必要",
+            format_yen(total.amount),
+            format_yen(supervising_threshold)
+        )),
+        Some(total) => (EngineerKind::Chief, format!(
+            "下請総額 {} が {} 未満のため主任技術者で可",
+            format_yen(total.amount),
+            format_yen(supervising_threshold)
+        )),
+        None => (EngineerKind::Chief, "下請契約金額が未入力のため主任技術者として判定".to_string()),
+    };
+
+    let dedicated_threshold = dedicated_threshold(is_building_work(project));
+    let (dedication, dedication_reason) = match project.contract_amount {
+        None => (Dedication::Unknown, "請負代金額が未入力のため専任の要否を判定できません".to_string()),
+        Some(amount) if amount < dedicated_threshold => (Dedication::NotRequired, format!(
+            "請負代金額 {} が {} 未満のため専任不要",
+            format_yen(amount),
+            format_yen(dedicated_threshold)
+        )),
+        Some(amount) if project.public_works => (Dedication::Required, format!(
+            "公共工事で請負代金額 {} が {} 以上のため専任が必要",
+            format_yen(amount),
+            format_yen(dedicated_threshold)
+        )),
+        Some(amount) => (Dedication::RequiredUnlessResidence, format!(
+            "民間工事で請負代金額 {} が {} 以上のため専任が必要（個人住宅・長屋を除く）",
+            format_yen(amount),
+            format_yen(dedicated_threshold)
+        )),
+    };
+
+    EngineerRequirement { kind, dedication, kind_reason, dedication_reason }
+}
+
+/// 資格が本日時点で有効か（期限のない資格は有効）
+fn is_valid(q: &PersonQualification, today: &str) -> bool {
+    q.expiry.as_deref().filter(|d| !d.is_empty()).is_none_or(|d| d >= today)
+}
+
+/// 監理技術者資格者証を持つか
+fn has_supervising_license(person: &Person, today: &str) -> bool {
+    person.qualifications.iter().any(|q| q.name.contains("監理技術者") && !q.name.contains("講習") && is_valid(q, today))
+}
+
+/// 監理技術者講習を修了しているか（修了後5年を有効期限として登録。技能講習などは対象外）
+fn has_supervising_training(person: &Person, today: &str) -> bool {
+    person.qualifications.iter().any(|q| q.name.contains("監理技術者講習") && is_valid(q, today))
+}
+
+/// 主任技術者の要件となる資格（施工管理技士・技術士・実務経験など）を持つか
+fn has_chief_qualification(person: &Person, today: &str) -> bool {
+    const KEYWORDS: [&str; 5] = ["施工管理技士", "技術士", "監理技術者", "建築士", "実務経験"];
+    person.qualifications.iter().any(|q| KEYWORDS.iter().any(|k| q.name.contains(k)) && is_valid(q, today))
+}
+
+/// 1名分の資格を技術者の要件と照合
+pub fn validate_engineer(
+    person: &Person,
+    requirement: &EngineerRequirement,
+    today: &str,
+) -> Vec<(CheckStatus, String)> {
+    let mut findings = Vec::new();
+    match requirement.kind {
+        EngineerKind::Supervising => {
+            if !has_supervising_license(person, today) {
+                findings.push((CheckStatus::Error, format!(
+                    "{}: 監理技術者が必要ですが、有効な監理技術者資格者証が登録されていません",
+                    person.name
+                )));
+            } else if requirement.dedication != Dedication::NotRequired && !has_supervising_training(person, today) {
+                findings.push((CheckStatus::Error, format!(
+                    "{}: 専任の監理技術者は監理技術者講習の修了（5年以内）が必要です",
+                    person.name
+                )));
+            }
+        }
+        EngineerKind::Chief => {
+            if !has_chief_qualification(person, today) {
+                findings.push((CheckStatus::Error, format!(
+                    "{}: 主任技術者の要件となる資格（施工管理技士・技術士・実務経験など）が登録されていません",
+                    person.name
+                )));
+            }
+        }
+    }
+    findings
+}
+
+/// 下請業者の主任技術者の専任要否（下請契約の請負代金額で判定）
+fn subcontractor_dedicated(project: &ProjectData, contractor: &Contractor) -> Option<u64> {
+    let building = trades_for_role(&contractor.role).iter().any(|t| t.name == "建築工事業");
+    let amount: u64 = project.contracts.iter()
+        .filter(|c| c.contractor.as_deref() == Some(contractor.name.as_str()))
+        .filter_map(|c| c.amount_with_tax())
+        .sum();
+    (amount >= dedicated_threshold(building)).then_some(amount)
+}
+
+/// 配置技術者チェック
+pub fn run_engineer_check(project: &ProjectData, registry: &[Person], today: &str) -> Vec<CheckResult> {
+    let requirement = determine_engineer_requirement(project);
+    let label = requirement.kind.label();
+    let mut results = Vec::new();
+    let project_result = |doc_name: &str, status, message: String| CheckResult {
+        contractor_name: project.project_name.clone(),
+        doc_name: doc_name.to_string(),
+        status,
+        message,
+    };
+
+    let dedication_status = match requirement.dedication {
+        Dedication::Unknown | Dedication::RequiredUnlessResidence => CheckStatus::Warning,
+        Dedication::Required | Dedication::NotRequired => CheckStatus::Ok,
+    };
+    results.push(project_result("配置技術者の判定", CheckStatus::Ok, requirement.kind_reason.clone()));
+    results.push(project_result("専任の判定", dedication_status, requirement.dedication_reason.clone()));
+
+    // 主任技術者欄（登録簿の人物）
+    let chief_name = project.chief_engineer.as_deref().filter(|n| !n.trim().is_empty());
+    let chief = project.chief_engineer_id.as_deref().and_then(|id| find_person(registry, id));
+    match (chief_name, chief) {
+        (None, _) => results.push(project_result(label, CheckStatus::Error, format!("{}が未設定です", label))),
+        (Some(name), None) => results.push(project_result(
+            label,
+            CheckStatus::Warning,
+            format!("{}: 登録簿と未連携のため{}の要件を確認できません", name, label),
+        )),
+        (Some(_), Some(person)) => {
+            let findings = validate_engineer(person, &requirement, today);
+            if findings.is_empty() {
+                results.push(project_result(label, CheckStatus::Ok, format!("{}: {}の要件を満たしています", person.name, label)));
+            } else {
+                results.extend(findings.into_iter().map(|(status, message)| project_result(label, status, message)));
+            }
+        }
+    }
+
+    // 元請の051書類（主任技術者欄と同一人物か、資格を満たすか）
+    for contractor in project.contractors.iter().filter(|c| is_prime(c)) {
+        for (doc_key, doc) in contractor.docs.iter().filter(|(k, _)| DocKind::parse(k).is(StandardDoc::ChiefEngineerQualification)) {
+            let Some(person) = doc.person_id.as_deref().and_then(|id| find_person(registry, id)) else {
+                continue;
+            };
+            let result = |status, message: String| CheckResult {
+                contractor_name: contractor.name.clone(),
+                doc_name: doc_key.clone(),
+                status,
+                message,
+            };
+            if chief.is_some_and(|c| c.id != person.id) {
+                results.push(result(CheckStatus::Warning, format!(
+                    "資格書類の人物（{}）が{}欄（{}）と異なります",
+                    person.name,
+                    label,
+                    chief_name.unwrap_or_default()
+                )));
+            }
+            if chief.is_none_or(|c| c.id != person.id) {
+                results.extend(validate_engineer(person, &requirement, today)
+                    .into_iter()
+                    .map(|(status, message)| result(status, message)));
+            }
+        }
+    }
+
+    // 下請業者の主任技術者の専任
+    for contractor in project.contractors.iter().filter(|c| !is_prime(c)) {
+        if let Some(amount) = subcontractor_dedicated(project, contractor) {
+            results.push(CheckResult {
+                contractor_name: contractor.name.clone(),
+                doc_name: "主任技術者".to_string(),
+                status: CheckStatus::Warning,
+                message: format!("下請契約 {} のため主任技術者の専任が必要です", format_yen(amount)),
+            });
+        }
+    }
+
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TODAY: &str = "2025-04-01";
+
+    fn person(qualifications: &[(&str, Option<&str>)]) -> Person {
+        Person {
+            id: "p1".to_string(),
+            name: "山田太郎".to_string(),
+            qualifications: qualifications
+                .iter()
+                .map(|(name, expiry)| PersonQualification {
+                    name: name.to_string(),
+                    expiry: expiry.map(str::to_string),
+                    ..PersonQualification::default()
+                })
+                .collect(),
+            ..Person::default()
+        }
+    }
+
+    fn requirement(kind: EngineerKind, dedication: Dedication) -> EngineerRequirement {
+        EngineerRequirement { kind, dedication, kind_reason: String::new(), dedication_reason: String::new() }
+    }
+
+    fn statuses(person: &Person, requirement: &EngineerRequirement) -> Vec<CheckStatus> {
+        validate_engineer(person, requirement, TODAY).into_iter().map(|(status, _)| status).collect()
+    }
+
+    #[test]
+    fn supervising_training_must_be_the_supervising_course() {
+        let dedicated = requirement(EngineerKind::Supervising, Dedication::Required);
+        let license = ("監理技術者資格者証", Some("2029-03-31"));
+        assert!(statuses(&person(&[license, ("監理技術者講習", Some("2027-03-31"))]), &dedicated).is_empty());
+        // 技能講習・安全衛生講習は監理技術者講習にならない
+        assert_eq!(
+            statuses(&person(&[license, ("玉掛け技能講習", None), ("安全衛生講習", None)]), &dedicated),
+            [CheckStatus::Error]
+        );
+        // 期限切れの講習
+        assert_eq!(statuses(&person(&[license, ("監理技術者講習", Some("2025-03-31"))]), &dedicated), [CheckStatus::Error]);
+        // 専任でなければ講習は問わない
+        assert!(statuses(&person(&[license]), &requirement(EngineerKind::Supervising, Dedication::NotRequired)).is_empty());
+    }
+
+    #[test]
+    fn supervising_license_excludes_the_course() {
+        let statuses = statuses(&person(&[("監理技術者講習", None)]), &requirement(EngineerKind::Supervising, Dedication::NotRequired));
+        assert_eq!(statuses, [CheckStatus::Error]);
+    }
+
+    #[test]
+    fn missing_chief_qualification_is_error() {
+        let chief = requirement(EngineerKind::Chief, Dedication::NotRequired);
+        assert!(statuses(&person(&[("1級土木施工管理技士", None)]), &chief).is_empty());
+        assert_eq!(statuses(&person(&[("玉掛け技能講習", None)]), &chief), [CheckStatus::Error]);
+        assert_eq!(statuses(&person(&[]), &chief), [CheckStatus::Error]);
+    }
+}
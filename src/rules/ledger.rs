@@ -0,0 +1,145 @@
+//! 施工体制台帳の作成義務判定
+//!
+//! 下請契約の総額（税込）と公共/民間の区分から、施工体制台帳・施工体系図の
+//! 作成義務を判定する。
+//! - 公共工事: 下請契約を締結すれば金額にかかわらず作成義務あり
+//! - 民間工事: 下請契約総額が5,000万円（建築一式は8,000万円）以上で作成義務あり
+
+use crate::models::{DocLink, ProjectData};
+use crate::utils::format_yen;
+use crate::{CheckResult, CheckStatus};
+use super::permit::{is_prime, trades_for_role, TOKUTEI_THRESHOLD, TOKUTEI_THRESHOLD_BUILDING};
+
+/// 下請契約総額（税込）
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubcontractTotal {
+    pub amount: u64,
+    /// 契約一覧の金額から算出したか（falseは手入力値）
+    pub computed: bool,
+    /// 金額未入力の下請契約数
+    pub missing_amounts: usize,
+}
+
+/// 下請契約総額を算出（契約一覧に金額がなければ手入力値）
+pub fn subcontract_total(project: &ProjectData) -> Option<SubcontractTotal> {
+    let subcontracts: Vec<_> = project.contracts.iter().filter(|c| c.is_subcontract()).collect();
+    let amounts: Vec<u64> = subcontracts.iter().filter_map(|c| c.amount_with_tax()).collect();
+    if amounts.is_empty() {
+        return project.subcontract_total.map(|amount| SubcontractTotal {
+            amount,
+            computed: false,
+            missing_amounts: 0,
+        });
+    }
+    Some(SubcontractTotal {
+        amount: amounts.iter().sum(),
+        computed: true,
+        missing_amounts: subcontracts.len() - amounts.len(),
+    })
+}
+
+/// 元請の工事が建築一式工事か（元請の役割から判定）
+pub fn is_building_work(project: &ProjectData) -> bool {
+    project.contractors.iter()
+        .filter(|c| is_prime(c))
+        .any(|c| trades_for_role(&c.role).iter().any(|t| t.name == "建築工事業"))
+}
+
+/// 特定建設業許可・施工体制台帳の基準額
+pub fn threshold(project: &ProjectData) -> u64 {
+    if is_building_work(project) { TOKUTEI_THRESHOLD_BUILDING } else { TOKUTEI_THRESHOLD }
+}
+
+/// 施工体制台帳の作成義務の判定結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct LedgerRequirement {
+    pub total: Option<SubcontractTotal>,
+    pub has_subcontracts: bool,
+    pub required: bool,
+    /// 判定理由（画面表示用）
+    pub reason: String,
+}
+
+/// 施工体制台帳・施工体系図の作成義務を判定
+pub fn determine_ledger_requirement(project: &ProjectData) -> LedgerRequirement {
+    let total = subcontract_total(project);
+    let has_subcontracts = project.contracts.iter().any(|c| c.is_subcontract())
+        || total.as_ref().is_some_and(|t| t.amount > 0);
+    let threshold = threshold(project);
+
+    let (required, reason) = if !has_subcontracts {
+        (false, "下請契約がないため作成義務なし".to_string())
+    } else if project.public_works {
+        (true, "公共工事で下請契約があるため作成義務あり".to_string())
+    } else {
+        match &total {
+            None => (false, "下請契約金額が未入力のため判定できません".to_string()),
+            Some(t) if t.amount >= threshold => (true, format!(
+                "民間工事で下請総額 {} が {} 以上のため作成義務あり",
+                format_yen(t.amount),
+                format_yen(threshold)
+            )),
+            Some(t) => (false, format!(
+                "民間工事で下請総額 {} が {} 未満のため作成義務なし",
+                format_yen(t.amount),
+                format_yen(threshold)
+            )),
+        }
+    };
+
+    LedgerRequirement { total, has_subcontracts, required, reason }
+}
+
+/// 施工体制台帳の要否チェック
+pub fn run_ledger_check(project: &ProjectData) -> Vec<CheckResult> {
+    let requirement = determine_ledger_requirement(project);
+    let result = |doc_name: &str, status, message: String| CheckResult {
+        contractor_name: project.project_name.clone(),
+        doc_name: doc_name.to_string(),
+        status,
+        message,
+    };
+    let mut results = Vec::new();
+
+    let status = if requirement.has_subcontracts && requirement.total.is_none() && !project.public_works {
+        CheckStatus::Warning
+    } else {
+        CheckStatus::Ok
+    };
+    results.push(result("作成義務の判定", status, requirement.reason.clone()));
+
+    if let Some(total) = requirement.total.as_ref().filter(|t| t.missing_amounts > 0) {
+        results.push(result(
+            "下請契約",
+            CheckStatus::Warning,
+            format!("金額未入力の下請契約が{}件あります（総額は入力済みの{}のみ）", total.missing_amounts, format_yen(total.amount)),
+        ));
+    }
+
+    // 契約の相手方が業者一覧にない
+    for contract in project.contracts.iter().filter(|c| c.is_subcontract()) {
+        if let Some(name) = contract.contractor.as_deref().filter(|n| !n.trim().is_empty()) {
+            if !project.contractors.iter().any(|c| c.name == name) {
+                results.push(result(
+                    &contract.name,
+                    CheckStatus::Warning,
+                    format!("下請負人「{}」が業者一覧にありません", name),
+                ));
+            }
+        }
+    }
+
+    let mut check_doc = |label: &str, doc: &Option<DocLink>, required: bool| {
+        match doc {
+            None if required => results.push(result(label, CheckStatus::Error, format!("{}（未登録）", requirement.reason))),
+            Some(d) if required && !d.status => results.push(result(label, CheckStatus::Warning, "作成義務がありますが未完了です".to_string())),
+            Some(d) if d.status => results.push(result(label, CheckStatus::Ok, "作成済み".to_string())),
+            _ => {}
+        }
+    };
+    check_doc("施工体制台帳", &project.project_docs.sekou_taisei_daicho, requirement.required);
+    check_doc("施工体系図", &project.project_docs.sekou_taikeizu, requirement.required);
+    check_doc("下請契約書", &project.project_docs.shitauke_keiyaku, requirement.has_subcontracts);
+
+    results
+}
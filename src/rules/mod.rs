@@ -0,0 +1,77 @@
+//! 法令・書類ルールモジュール
+//!
+//! プロジェクトデータに対する検証ルールをまとめる。
+//! 各ルールはチェック結果パネル用の `CheckResult` を返す。
+
+pub mod ai_history;
+pub mod ccus;
+pub mod checklist;
+pub mod engineer;
+pub mod ledger;
+pub mod numbers;
+pub mod permit;
+pub mod persons;
+pub mod workers;
+
+pub use ai_history::run_ai_history_check;
+pub use ccus::run_ccus_check;
+pub use engineer::run_engineer_check;
+pub use ledger::run_ledger_check;
+pub use numbers::run_number_check;
+pub use permit::run_permit_check;
+pub use persons::run_person_check;
+pub use workers::run_worker_check;
+
+use crate::models::{DocKind, DocStatus, StandardDoc};
+use crate::CheckStatus;
+
+/// 書類項目の入力定義（DocEditorの入力欄）
+pub struct FieldSpec {
+    /// DocStatus.fields / extracted_fields のキー
+    pub key: &'static str,
+    pub label: &'static str,
+    pub placeholder: &'static str,
+    /// 日付入力か
+    pub date: bool,
+}
+
+/// 書類項目ごとの検証結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldIssue {
+    pub key: &'static str,
+    pub status: CheckStatus,
+    pub message: String,
+}
+
+/// 書類キーに対応する入力項目（項目定義のない書類は空）
+pub fn field_specs_for_doc(doc_key: &str) -> &'static [FieldSpec] {
+    match DocKind::parse(doc_key).standard() {
+        Some(StandardDoc::Permit) => permit::PERMIT_FIELDS,
+        Some(StandardDoc::OfficeNumber) => numbers::OFFICE_FIELDS,
+        Some(StandardDoc::LaborInsurance) => numbers::LABOR_FIELDS,
+        _ => &[],
+    }
+}
+
+/// 区切り文字（ハイフン・空白）を除いた数字列。数字以外を含む場合は None
+pub fn digits_only(text: &str) -> Option<String> {
+    let mut digits = String::new();
+    for c in text.chars() {
+        match c {
+            '0'..='9' => digits.push(c),
+            '０'..='９' => digits.push(char::from_u32(c as u32 - '０' as u32 + '0' as u32)?),
+            '-' | '－' | 'ー' | '‐' | ' ' | '　' => {}
+            _ => return None,
+        }
+    }
+    Some(digits)
+}
+
+/// 書類項目の形式エラー（手入力・AI抽出値の両方が対象）
+pub fn field_issues_for_doc(doc_key: &str, doc: &DocStatus) -> Vec<FieldIssue> {
+    match DocKind::parse(doc_key).standard() {
+        Some(StandardDoc::Permit) => permit::field_issues(doc),
+        Some(kind) => numbers::field_issues(kind, doc),
+        None => Vec::new(),
+    }
+}
@@ -0,0 +1,207 @@
+//! 保険関係番号の形式チェック
+//!
+//! 02_事業所番号・03_労働保険番号に記載される番号の構造を検証する。
+//! AIが誤読しやすい桁数・区分コード・チェックディジットを機械的に確認する
+
+use crate::models::{DocKind, DocStatus, ProjectData, StandardDoc};
+use crate::{CheckResult, CheckStatus};
+use super::{digits_only, FieldIssue, FieldSpec};
+
+
+pub const OFFICE_FIELDS: &[FieldSpec] = &[
+    FieldSpec { key: "employment_office_number", label: "雇用保険事業所番号", placeholder: "1301-123456-7", date: false },
+    FieldSpec { key: "health_office_symbol", label: "健康保険 事業所整理記号", placeholder: "01-イロハ", date: false },
+    FieldSpec { key: "health_insurer_number", label: "健康保険 保険者番号", placeholder: "01130012", date: false },
+];
+
+pub const LABOR_FIELDS: &[FieldSpec] = &[
+    FieldSpec { key: "labor_insurance_number", label: "労働保険番号", placeholder: "13-1-01-123456-000", date: false },
+];
+
+/// 労働保険番号（府県2桁・所掌1桁・管轄2桁・基幹番号6桁・枝番号3桁）
+pub fn validate_labor_insurance_number(text: &str) -> Result<(), String> {
+    let digits = digits_only(text).ok_or("数字とハイフン以外の文字が含まれています")?;
+    if digits.len() != 14 {
+        return Err(format!("14桁（府県2・所掌1・管轄2・基幹番号6・枝番号3）ではありません（{}桁）", digits.len()));
+    }
+    let prefecture: u32 = digits[0..2].parse().unwrap_or(0);
+    if !(1..=47).contains(&prefecture) {
+        return Err(format!("府県コード {} が不正です（01〜47）", &digits[0..2]));
+    }
+    let jurisdiction_type = &digits[2..3];
+    if jurisdiction_type != "1" && jurisdiction_type != "3" {
+        return Err(format!("所掌 {} が不正です（1: 労働基準監督署 / 3: 公共職業安定所）", jurisdiction_type));
+    }
+    if &digits[3..5] == "00" {
+        return Err("管轄 00 は存在しません".to_string());
+    }
+    Ok(())
+}
+
+/// 雇用保険事業所番号（4桁-6桁-チェックディジット1桁）
+///
+/// チェックディジットは先頭10桁を数値として7で割った余り
+pub fn validate_employment_office_number(text: &str) -> Result<(), String> {
+    let digits = digits_only(text).ok_or("数字とハイフン以外の文字が含まれています")?;
+    if digits.len() != 11 {
+        return Err(format!("11桁（4桁-6桁-1桁）ではありません（{}桁）", digits.len()));
+    }
+    let body: u64 = digits[0..10].parse().map_err(|_| "数値に変換できません".to_string())?;
+    let expected = (body % 7) as u32;
+    let actual = digits[10..11].parse::<u32>().unwrap_or(u32::MAX);
+    if actual != expected {
+        return Err(format!("チェックディジットが一致しません（記載: {} / 計算値: {}）", actual, expected));
+    }
+    Ok(())
+}
+
+/// 健康保険の保険者番号
+///
+/// 8桁（法別番号2・都道府県2・保険者別番号3・検証番号1）、国民健康保険は6桁（法別番号なし）。
+/// 検証番号は検証番号より前の桁に右から2,1,2,1…を掛け、各積の各桁の和を10から引いた下1桁
+pub fn validate_health_insurer_number(text: &str) -> Result<(), String> {
+    let digits = digits_only(text).ok_or("数字以外の文字が含まれています")?;
+    let nums: Vec<u32> = digits.chars().filter_map(|c| c.to_digit(10)).collect();
+    // 都道府県番号の位置
+    let prefecture_at = match nums.len() {
+        6 => 0,
+        8 => 2,
+        n => return Err(format!("8桁（国民健康保険は6桁）ではありません（{}桁）", n)),
+    };
+    let (body, check) = nums.split_at(nums.len() - 1);
+    let sum: u32 = body.iter().rev().enumerate()
+        .map(|(i, d)| {
+            let p = if i % 2 == 0 { d * 2 } else { *d };
+            p / 10 + p % 10
+        })
+        .sum();
+    let expected = (10 - sum % 10) % 10;
+    if check[0] != expected {
+        return Err(format!("検証番号が一致しません（記載: {} / 計算値: {}）", check[0], expected));
+    }
+    let prefecture = nums[prefecture_at] * 10 + nums[prefecture_at + 1];
+    if !(1..=47).contains(&prefecture) {
+        return Err(format!("都道府県番号 {:02} が不正です", prefecture));
+    }
+    Ok(())
+}
+
+/// 健康保険の事業所整理記号（協会けんぽ: 数字2桁＋カナ1〜4文字、例: 01-イロハ）
+///
+/// 健康保険組合は独自形式のため、不一致は警告に留める
+pub fn validate_health_office_symbol(text: &str) -> Result<(), String> {
+    let compact: String = text.chars().filter(|c| !matches!(c, '-' | '－' | '‐' | ' ' | '　')).collect();
+    let digit_count = compact.chars().take_while(|c| c.is_ascii_digit() || ('０'..='９').contains(c)).count();
+    let kana: Vec<char> = compact.chars().skip(digit_count).collect();
+    let is_kana = |c: &char| ('ァ'..='ヶ').contains(c) || ('ぁ'..='ゖ').contains(c) || *c == 'ー';
+    if digit_count == 2 && (1..=4).contains(&kana.len()) && kana.iter().all(is_kana) {
+        Ok(())
+    } else {
+        Err("協会けんぽの形式（数字2桁＋カナ1〜4文字）と一致しません".to_string())
+    }
+}
+
+/// 書類項目ごとの形式エラー（手入力 > AI抽出値の順に採用した値を検証）
+pub fn field_issues(kind: StandardDoc, doc: &DocStatus) -> Vec<FieldIssue> {
+    type Validator = fn(&str) -> Result<(), String>;
+    let validators: &[(&str, Validator, CheckStatus)] = match kind {
+        StandardDoc::OfficeNumber => &[
+            ("employment_office_number", validate_employment_office_number, CheckStatus::Error),
+            ("health_office_symbol", validate_health_office_symbol, CheckStatus::Warning),
+            ("health_insurer_number", validate_health_insurer_number, CheckStatus::Error),
+        ],
+        StandardDoc::LaborInsurance => &[("labor_insurance_number", validate_labor_insurance_number, CheckStatus::Error)],
+        _ => &[],
+    };
+
+    validators.iter()
+        .filter_map(|(key, validate, status)| {
+            let value = doc.field_value(key)?;
+            validate(value).err().map(|message| FieldIssue {
+                key,
+                status: status.clone(),
+                message: format!("{}（{}）", message, value),
+            })
+        })
+        .collect()
+}
+
+/// 全業者の番号形式チェック
+pub fn run_number_check(project: &ProjectData) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    for contractor in &project.contractors {
+        let docs = contractor.sorted_docs().into_iter().filter(|(k, _)| {
+            matches!(DocKind::parse(k).standard(), Some(StandardDoc::OfficeNumber | StandardDoc::LaborInsurance))
+        });
+
+        for (doc_key, doc) in docs {
+            let specs = super::field_specs_for_doc(doc_key);
+            let result = |status, message: String| CheckResult {
+                contractor_name: contractor.name.clone(),
+                doc_name: doc_key.clone(),
+                status,
+                message,
+            };
+
+            if specs.iter().all(|s| doc.field_value(s.key).is_none()) {
+                results.push(result(CheckStatus::Warning, "番号が未入力です（書類編集で入力するか、AIチェックで抽出してください）".to_string()));
+                continue;
+            }
+
+            let issues = super::field_issues_for_doc(doc_key, doc);
+            if issues.is_empty() {
+                results.push(result(CheckStatus::Ok, "番号形式OK".to_string()));
+            } else {
+                results.extend(issues.into_iter().map(|issue| {
+                    let label = specs.iter().find(|s| s.key == issue.key).map(|s| s.label).unwrap_or(issue.key);
+                    result(issue.status, format!("{}: {}", label, issue.message))
+                }));
+            }
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labor_insurance_number() {
+        assert!(validate_labor_insurance_number("13-1-01-123456-000").is_ok());
+        assert!(validate_labor_insurance_number("27301987654001").is_ok());
+        // 桁数・府県・所掌・管轄
+        assert!(validate_labor_insurance_number("13-1-01-123456-00").is_err());
+        assert!(validate_labor_insurance_number("48-1-01-123456-000").is_err());
+        assert!(validate_labor_insurance_number("13-2-01-123456-000").is_err());
+        assert!(validate_labor_insurance_number("13-1-00-123456-000").is_err());
+        assert!(validate_labor_insurance_number("13-1-01-12345A-000").is_err());
+    }
+
+    #[test]
+    fn employment_office_number_mod7() {
+        // 1301123456 % 7 = 3
+        assert!(validate_employment_office_number("1301-123456-3").is_ok());
+        assert!(validate_employment_office_number("5001-000010-6").is_ok());
+        assert!(validate_employment_office_number("1301-123456-4").is_err());
+        assert!(validate_employment_office_number("1301-123456").is_err());
+    }
+
+    #[test]
+    fn health_insurer_number_8_digits() {
+        // 協会けんぽ東京支部
+        assert!(validate_health_insurer_number("01130012").is_ok());
+        assert!(validate_health_insurer_number("01130013").is_err());
+        // 都道府県番号 48
+        assert!(validate_health_insurer_number("01480011").is_err());
+    }
+
+    #[test]
+    fn health_insurer_number_6_digits() {
+        // 国民健康保険（新宿区）
+        assert!(validate_health_insurer_number("131045").is_ok());
+        assert!(validate_health_insurer_number("131046").is_err());
+        assert!(validate_health_insurer_number("481044").is_err());
+        assert!(validate_health_insurer_number("1310451").is_err());
+    }
+}
@@ -0,0 +1,470 @@
+//! 建設業許可チェック
+//!
+//! 01_建設業許可の許可情報（手入力またはAI抽出値）を検証する。
+//! - 許可日から5年の有効期間
+//! - 許可業種が業者の役割（工種）を含むか
+//! - 元請の下請契約総額が特定建設業許可の基準額以上の場合、特定許可か
+
+use crate::models::{Contractor, DocKind, DocStatus, ProjectData, StandardDoc};
+use crate::utils::date::{add_days, add_years, normalize_date};
+use crate::utils::format_yen;
+use crate::{CheckResult, CheckStatus};
+use super::{FieldIssue, FieldSpec};
+
+
+/// 特定建設業許可が必要となる下請契約総額（建築一式以外）
+pub const TOKUTEI_THRESHOLD: u64 = 50_000_000;
+/// 特定建設業許可が必要となる下請契約総額（建築一式）
+pub const TOKUTEI_THRESHOLD_BUILDING: u64 = 80_000_000;
+
+/// 更新手続きを促す日数（満了の90日前から警告。更新申請自体は満了の30日前まで）
+const RENEWAL_WARNING_DAYS: i64 = 90;
+
+/// 許可情報の入力項目（キーはAIチェックのextracted_fieldsと共通）
+pub const PERMIT_FIELDS: &[FieldSpec] = &[
+    FieldSpec { key: "permit_authority", label: "許可行政庁", placeholder: "国土交通大臣 / ○○県知事", date: false },
+    FieldSpec { key: "permit_category", label: "一般/特定", placeholder: "一般 / 特定", date: false },
+    FieldSpec { key: "permit_number", label: "許可番号", placeholder: "(般-5) 第12345号", date: false },
+    FieldSpec { key: "permit_trades", label: "許可業種", placeholder: "土木工事業、舗装工事業", date: false },
+    FieldSpec { key: "permit_date", label: "許可年月日", placeholder: "yyyy-MM-dd", date: true },
+];
+
+// ============================================
+// 建設業の業種（29業種）
+// ============================================
+
+/// 建設業法の業種
+#[derive(Debug)]
+pub struct Trade {
+    /// 業種名（例: 舗装工事業）
+    pub name: &'static str,
+    /// 略称（例: 舗装）
+    pub short: &'static str,
+    /// 許可通知書での一文字略号（例: 舗）
+    pub abbr: &'static str,
+    /// 業者の役割から業種を推定するための語句
+    pub keywords: &'static [&'static str],
+}
+
+pub const TRADES: &[Trade] = &[
+    Trade { name: "土木工事業", short: "土木", abbr: "土", keywords: &[] },
+    Trade { name: "建築工事業", short: "建築", abbr: "建", keywords: &[] },
+    Trade { name: "大工工事業", short: "大工", abbr: "大", keywords: &["型枠"] },
+    Trade { name: "左官工事業", short: "左官", abbr: "左", keywords: &[] },
+    Trade { name: "とび・土工工事業", short: "とび・土工", abbr: "と", keywords: &["とび", "鳶", "土工", "足場", "コンクリート", "杭", "掘削"] },
+    Trade { name: "石工事業", short: "石工", abbr: "石", keywords: &["石積"] },
+    Trade { name: "屋根工事業", short: "屋根", abbr: "屋", keywords: &[] },
+    Trade { name: "電気工事業", short: "電気", abbr: "電", keywords: &["電工"] },
+    Trade { name: "管工事業", short: "管工", abbr: "管", keywords: &["配管", "空調", "衛生設備"] },
+    Trade { name: "タイル・れんが・ブロック工事業", short: "タイル", abbr: "タ", keywords: &["れんが", "ブロック"] },
+    Trade { name: "鋼構造物工事業", short: "鋼構造物", abbr: "鋼", keywords: &["鉄骨", "橋梁"] },
+    Trade { name: "鉄筋工事業", short: "鉄筋", abbr: "筋", keywords: &[] },
+    Trade { name: "舗装工事業", short: "舗装", abbr: "舗", keywords: &["切削", "アスファルト"] },
+    Trade { name: "しゅんせつ工事業", short: "しゅんせつ", abbr: "しゅ", keywords: &["浚渫"] },
+    Trade { name: "板金工事業", short: "板金", abbr: "板", keywords: &[] },
+    Trade { name: "ガラス工事業", short: "ガラス", abbr: "ガ", keywords: &[] },
+    Trade { name: "塗装工事業", short: "塗装", abbr: "塗", keywords: &["区画線", "路面標示"] },
+    Trade { name: "防水工事業", short: "防水", abbr: "防", keywords: &[] },
+    Trade { name: "内装仕上工事業", short: "内装", abbr: "内", keywords: &["クロス", "軽天"] },
+    Trade { name: "機械器具設置工事業", short: "機械器具", abbr: "機", keywords: &["プラント"] },
+    Trade { name: "熱絶縁工事業", short: "熱絶縁", abbr: "絶", keywords: &["保温"] },
+    Trade { name: "電気通信工事業", short: "電気通信", abbr: "通", keywords: &["通信"] },
+    Trade { name: "造園工事業", short: "造園", abbr: "園", keywords: &["植栽"] },
+    Trade { name: "さく井工事業", short: "さく井", abbr: "井", keywords: &["井戸"] },
+    Trade { name: "建具工事業", short: "建具", abbr: "具", keywords: &["サッシ"] },
+    Trade { name: "水道施設工事業", short: "水道施設", abbr: "水", keywords: &["上水道", "下水道"] },
+    Trade { name: "消防施設工事業", short: "消防施設", abbr: "消", keywords: &["消火設備"] },
+    Trade { name: "清掃施設工事業", short: "清掃施設", abbr: "清", keywords: &[] },
+    Trade { name: "解体工事業", short: "解体", abbr: "解", keywords: &[] },
+];
+
+/// テキスト中の業種を検出
+///
+/// 長い語句から順に照合し、一致した部分を消して「電気通信」が「電気」にも
+/// 一致するような重複を防ぐ
+fn scan_trades(text: &str, with_keywords: bool) -> Vec<&'static Trade> {
+    let mut terms: Vec<(&str, usize)> = TRADES.iter().enumerate()
+        .flat_map(|(i, t)| {
+            let keywords: &[&str] = if with_keywords { t.keywords } else { &[] };
+            [t.name, t.short].into_iter().chain(keywords.iter().copied()).map(move |term| (term, i))
+        })
+        .collect();
+    terms.sort_by_key(|(term, _)| std::cmp::Reverse(term.chars().count()));
+
+    let mut rest = text.to_string();
+    let mut found: Vec<usize> = Vec::new();
+    for (term, i) in terms {
+        if rest.contains(term) {
+            rest = rest.replace(term, " ");
+            if !found.contains(&i) {
+                found.push(i);
+            }
+        }
+    }
+    found.sort();
+    found.into_iter().map(|i| &TRADES[i]).collect()
+}
+
+/// 許可業種の記載を解析（業種名・略称・一文字略号の列記に対応）
+pub fn parse_permit_trades(text: &str) -> Vec<&'static Trade> {
+    let found = scan_trades(text, false);
+    if !found.is_empty() {
+        return found;
+    }
+    // 「土・と・舗」のような略号列記
+    text.split(|c: char| "・、,，/ 　".contains(c))
+        .filter_map(|token| TRADES.iter().find(|t| t.abbr == token.trim()))
+        .collect()
+}
+
+/// 業者の役割（例: 切削工、区画線）から業種を推定
+pub fn trades_for_role(role: &str) -> Vec<&'static Trade> {
+    scan_trades(role, true)
+}
+
+// ============================================
+// 許可情報
+// ============================================
+
+/// 許可行政庁の区分
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PermitAuthority {
+    /// 国土交通大臣許可
+    Minister,
+    /// 都道府県知事許可
+    Governor,
+}
+
+/// 一般/特定の区分
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PermitCategory {
+    General,
+    Special,
+}
+
+impl PermitCategory {
+    pub fn label(&self) -> &'static str {
+        match self {
+            PermitCategory::General => "一般",
+            PermitCategory::Special => "特定",
+        }
+    }
+}
+
+/// 書類から読み取った許可情報
+#[derive(Debug, Clone, Default)]
+pub struct PermitData {
+    pub authority: Option<PermitAuthority>,
+    pub category: Option<PermitCategory>,
+    pub number: Option<String>,
+    pub trades: Vec<&'static Trade>,
+    /// 許可年月日（正規化前の記載）
+    pub permit_date_text: Option<String>,
+}
+
+impl PermitData {
+    /// 書類項目（手入力 > AI抽出値）から許可情報を組み立て
+    ///
+    /// 許可番号の「(般-5)」「(特-5)」表記や行政庁名からも区分を補完する
+    pub fn from_doc(doc: &DocStatus) -> Self {
+        let authority_text = doc.field_value("permit_authority").unwrap_or_default();
+        let category_text = doc.field_value("permit_category").unwrap_or_default();
+        let number_text = doc.field_value("permit_number").map(str::to_string);
+        let hint = format!("{} {} {}", authority_text, category_text, number_text.as_deref().unwrap_or_default());
+
+        let authority = if hint.contains("大臣") {
+            Some(PermitAuthority::Minister)
+        } else if hint.contains("知事") {
+            Some(PermitAuthority::Governor)
+        } else {
+            None
+        };
+        let category = if category_text.contains("特") || hint.contains("特-") {
+            Some(PermitCategory::Special)
+        } else if category_text.contains("般") || hint.contains("般-") {
+            Some(PermitCategory::General)
+        } else {
+            None
+        };
+
+        PermitData {
+            authority,
+            category,
+            number: number_text,
+            trades: doc.field_value("permit_trades").map(parse_permit_trades).unwrap_or_default(),
+            permit_date_text: doc.field_value("permit_date").map(str::to_string),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.authority.is_none()
+            && self.category.is_none()
+            && self.number.is_none()
+            && self.trades.is_empty()
+            && self.permit_date_text.is_none()
+    }
+}
+
+/// 許可番号の数字部分（「第012345号」→ "012345"）
+///
+/// 「第」がない場合は「(般-5)」などの区分の後ろから読む（区分内の数字を番号とみなさない）
+fn permit_number_digits(number: &str) -> Option<String> {
+    let after = number
+        .rsplit_once('第')
+        .or_else(|| number.rsplit_once([')', '）']))
+        .map(|(_, r)| r)
+        .unwrap_or(number);
+    let digits: String = after.chars()
+        .skip_while(|c| !c.is_ascii_digit())
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    (!digits.is_empty()).then_some(digits)
+}
+
+/// 許可情報の項目ごとの形式エラー
+pub fn field_issues(doc: &DocStatus) -> Vec<FieldIssue> {
+    let mut issues = Vec::new();
+    if let Some(number) = doc.field_value("permit_number") {
+        if permit_number_digits(number).is_none_or(|d| d.len() > 6) {
+            issues.push(FieldIssue {
+                key: "permit_number",
+                status: CheckStatus::Error,
+                message: format!("許可番号は6桁以内の数字です（{}）", number),
+            });
+        }
+    }
+    if let Some(trades) = doc.field_value("permit_trades") {
+        if parse_permit_trades(trades).is_empty() {
+            issues.push(FieldIssue {
+                key: "permit_trades",
+                status: CheckStatus::Warning,
+                message: format!("業種名を判定できません（{}）", trades),
+            });
+        }
+    }
+    if let Some(date) = doc.field_value("permit_date") {
+        if normalize_date(date).is_none() {
+            issues.push(FieldIssue {
+                key: "permit_date",
+                status: CheckStatus::Error,
+                message: format!("日付として解釈できません（{}）", date),
+            });
+        }
+    }
+    issues
+}
+
+/// 許可の有効期間満了日（許可日の5年後の前日）
+pub fn permit_expiry(permit_date: &str) -> Option<String> {
+    add_days(&add_years(permit_date, 5)?, -1)
+}
+
+/// 元請かどうか（役割に「元請」を含む）
+pub fn is_prime(contractor: &Contractor) -> bool {
+    contractor.role.contains("元請")
+}
+
+/// 1業者分の許可検証
+pub fn validate_permit(
+    permit: &PermitData,
+    contractor: &Contractor,
+    subcontract_total: Option<u64>,
+    today: &str,
+) -> Vec<(CheckStatus, String)> {
+    let mut findings = Vec::new();
+
+    if permit.authority.is_none() {
+        findings.push((CheckStatus::Warning, "許可行政庁（大臣/知事）が不明".to_string()));
+    }
+    match permit.number.as_deref() {
+        None => findings.push((CheckStatus::Warning, "許可番号が未入力".to_string())),
+        Some(number) => match permit_number_digits(number) {
+            Some(d) if d.len() <= 6 => {}
+            _ => findings.push((CheckStatus::Error, format!("許可番号の形式が不正: {}", number))),
+        },
+    }
+
+    // 有効期間（5年）
+    match permit.permit_date_text.as_deref() {
+        None => findings.push((CheckStatus::Warning, "許可年月日が未入力".to_string())),
+        Some(text) => match normalize_date(text).and_then(|d| permit_expiry(&d)) {
+            None => findings.push((CheckStatus::Error, format!("許可年月日を解釈できません: {}", text))),
+            Some(expiry) => {
+                let warning_date = add_days(today, RENEWAL_WARNING_DAYS).unwrap_or_else(|| today.to_string());
+                if expiry.as_str() < today {
+                    findings.push((CheckStatus::Error, format!("許可の有効期間が満了しています（{}まで）", expiry)));
+                } else if expiry <= warning_date {
+                    findings.push((CheckStatus::Warning, format!("許可の有効期間が間もなく満了します（{}まで）", expiry)));
+                }
+            }
+        },
+    }
+
+    // 許可業種と役割（工種）の照合
+    let role_trades = trades_for_role(&contractor.role);
+    if permit.trades.is_empty() {
+        findings.push((CheckStatus::Warning, "許可業種が未入力".to_string()));
+    } else if role_trades.is_empty() {
+        // 役割が「元請」のみの場合は工種が分からないため照合しない
+        if contractor.role.trim() != "元請" {
+            findings.push((CheckStatus::Warning, format!("役割「{}」から業種を判定できません", contractor.role)));
+        }
+    } else if !role_trades.iter().any(|t| permit.trades.iter().any(|p| p.name == t.name)) {
+        let required: Vec<_> = role_trades.iter().map(|t| t.name).collect();
+        findings.push((CheckStatus::Error, format!(
+            "役割「{}」に必要な業種（{}）の許可がありません",
+            contractor.role,
+            required.join(" / ")
+        )));
+    }
+
+    // 特定建設業許可の要否（元請のみ）
+    if let Some(total) = subcontract_total.filter(|_| is_prime(contractor)) {
+        let building = role_trades.iter().any(|t| t.name == "建築工事業")
+            || (role_trades.is_empty() && permit.trades.iter().any(|t| t.name == "建築工事業"));
+        let threshold = if building { TOKUTEI_THRESHOLD_BUILDING } else { TOKUTEI_THRESHOLD };
+        if total >= threshold && permit.category != Some(PermitCategory::Special) {
+            findings.push((CheckStatus::Error, format!(
+                "下請契約総額 {} が{}以上のため特定建設業許可が必要です（現在: {}）",
+                format_yen(total),
+                format_yen(threshold),
+                permit.category.map(|c| c.label()).unwrap_or("区分不明")
+            )));
+        }
+    }
+
+    findings
+}
+
+/// 全業者の建設業許可チェック
+pub fn run_permit_check(project: &ProjectData, today: &str) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    let subcontract_total = super::ledger::subcontract_total(project);
+    for contractor in &project.contractors {
+        let Some((doc_key, doc)) = contractor.docs.iter().find(|(k, _)| DocKind::parse(k).is(StandardDoc::Permit)) else {
+            continue;
+        };
+        let result = |status, message: String| CheckResult {
+            contractor_name: contractor.name.clone(),
+            doc_name: doc_key.clone(),
+            status,
+            message,
+        };
+
+        let permit = PermitData::from_doc(doc);
+        if permit.is_empty() {
+            results.push(result(
+                CheckStatus::Warning,
+                "許可情報が未入力です（書類編集で入力するか、AIチェックで抽出してください）".to_string(),
+            ));
+            continue;
+        }
+
+        let findings = validate_permit(&permit, contractor, subcontract_total.as_ref().map(|t| t.amount), today);
+        if findings.is_empty() {
+            let trades: Vec<_> = permit.trades.iter().map(|t| t.short).collect();
+            results.push(result(CheckStatus::Ok, format!("有効（{}）", trades.join("・"))));
+        } else {
+            results.extend(findings.into_iter().map(|(status, message)| result(status, message)));
+        }
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn contractor(role: &str) -> Contractor {
+        Contractor {
+            id: "c1".to_string(),
+            name: "テスト建設".to_string(),
+            role: role.to_string(),
+            email: None,
+            ccus_business_id: None,
+            docs: HashMap::new(),
+            workers: Vec::new(),
+        }
+    }
+
+    fn permit(category: PermitCategory, trades: &str) -> PermitData {
+        PermitData {
+            authority: Some(PermitAuthority::Governor),
+            category: Some(category),
+            number: Some("(般-5) 第12345号".to_string()),
+            trades: parse_permit_trades(trades),
+            permit_date_text: Some("2024-04-01".to_string()),
+        }
+    }
+
+    fn needs_tokutei(permit: &PermitData, role: &str, total: u64) -> bool {
+        validate_permit(permit, &contractor(role), Some(total), "2025-04-01")
+            .iter()
+            .any(|(_, message)| message.contains("特定建設業許可が必要"))
+    }
+
+    #[test]
+    fn permit_expiry_is_five_years_minus_one_day() {
+        assert_eq!(permit_expiry("2024-04-01").as_deref(), Some("2029-03-31"));
+        assert_eq!(permit_expiry("2020-01-01").as_deref(), Some("2024-12-31"));
+        // 閏日は平年の2/28に丸めてから前日
+        assert_eq!(permit_expiry("2024-02-29").as_deref(), Some("2029-02-27"));
+        assert_eq!(permit_expiry("2024-13-01"), None);
+    }
+
+    #[test]
+    fn parse_permit_trades_by_name_short_and_abbr() {
+        let names = |text: &str| parse_permit_trades(text).iter().map(|t| t.name).collect::<Vec<_>>();
+        assert_eq!(names("土木工事業、舗装工事業"), ["土木工事業", "舗装工事業"]);
+        // 「電気通信」は「電気」に重複一致しない
+        assert_eq!(names("電気通信"), ["電気通信工事業"]);
+        assert_eq!(names("電気・電気通信"), ["電気工事業", "電気通信工事業"]);
+        assert_eq!(names("土・と・舗"), ["土木工事業", "とび・土工工事業", "舗装工事業"]);
+        assert!(names("不明な業種").is_empty());
+    }
+
+    #[test]
+    fn permit_number_digits_skip_category_prefix() {
+        assert_eq!(permit_number_digits("(般-5) 第012345号").as_deref(), Some("012345"));
+        assert_eq!(permit_number_digits("（特-31）第1234号").as_deref(), Some("1234"));
+        // 「第」がなくても区分の数字は番号にしない
+        assert_eq!(permit_number_digits("(般-5)12345").as_deref(), Some("12345"));
+        assert_eq!(permit_number_digits("（般-5）").as_deref(), None);
+        assert_eq!(permit_number_digits("12345").as_deref(), Some("12345"));
+    }
+
+    #[test]
+    fn permit_number_field_rejects_category_only() {
+        let issues = |number: &str| {
+            let doc: DocStatus = serde_json::from_value(serde_json::json!({
+                "status": true,
+                "fields": { "permit_number": number }
+            }))
+            .unwrap();
+            field_issues(&doc).into_iter().filter(|i| i.key == "permit_number").count()
+        };
+        assert_eq!(issues("(般-5)12345"), 0);
+        assert_eq!(issues("(般-5)"), 1);
+        assert_eq!(issues("(般-5)1234567"), 1);
+    }
+
+    #[test]
+    fn tokutei_threshold_is_50m_yen() {
+        let general = permit(PermitCategory::General, "土木工事業");
+        assert!(!needs_tokutei(&general, "元請", TOKUTEI_THRESHOLD - 1));
+        assert!(needs_tokutei(&general, "元請", TOKUTEI_THRESHOLD));
+        // 特定許可があれば不要
+        assert!(!needs_tokutei(&permit(PermitCategory::Special, "土木工事業"), "元請", TOKUTEI_THRESHOLD));
+        // 下請は対象外
+        assert!(!needs_tokutei(&general, "舗装", TOKUTEI_THRESHOLD));
+    }
+
+    #[test]
+    fn tokutei_threshold_is_80m_yen_for_building() {
+        let building = permit(PermitCategory::General, "建築工事業");
+        assert!(!needs_tokutei(&building, "元請", TOKUTEI_THRESHOLD));
+        assert!(!needs_tokutei(&building, "元請", TOKUTEI_THRESHOLD_BUILDING - 1));
+        assert!(needs_tokutei(&building, "元請", TOKUTEI_THRESHOLD_BUILDING));
+    }
+}
@@ -0,0 +1,104 @@
+//! 技術者資格チェック
+//!
+//! 現場代理人・主任技術者と各社の資格書類（041/051）が参照する登録簿の資格について、
+//! 有効期限を工期と照合する
+
+use crate::models::{DocKind, Person, ProjectData, StandardDoc};
+use crate::utils::registry::find_person;
+use crate::{CheckResult, CheckStatus};
+
+/// 書類キーが登録簿を参照する資格書類（現場代理人資格・主任技術者資格）か
+pub fn is_person_doc(doc_key: &str) -> bool {
+    matches!(
+        DocKind::parse(doc_key).standard(),
+        Some(StandardDoc::SiteRepresentativeQualification | StandardDoc::ChiefEngineerQualification)
+    )
+}
+
+/// 1名分の資格を工期と照合
+///
+/// 工期が未設定の場合は本日時点の有効性のみ確認する
+pub fn validate_person(
+    person: &Person,
+    period_start: Option<&str>,
+    period_end: Option<&str>,
+    today: &str,
+) -> Vec<(CheckStatus, String)> {
+    if person.qualifications.is_empty() {
+        return vec![(CheckStatus::Warning, "資格が登録されていません".to_string())];
+    }
+
+    let mut findings = Vec::new();
+    for q in &person.qualifications {
+        let name = if q.name.is_empty() { "(資格名未入力)" } else { q.name.as_str() };
+        let Some(expiry) = q.expiry.as_deref().filter(|d| !d.is_empty()) else {
+            continue;
+        };
+        if expiry < today {
+            findings.push((CheckStatus::Error, format!("{}が期限切れ: {}", name, expiry)));
+        } else if let Some(start) = period_start.filter(|s| expiry < *s) {
+            findings.push((CheckStatus::Error, format!("{}が工期開始（{}）前に期限切れ: {}", name, start, expiry)));
+        } else if let Some(end) = period_end.filter(|e| expiry < *e) {
+            findings.push((CheckStatus::Warning, format!("{}が工期中（〜{}）に期限切れ: {}", name, end, expiry)));
+        }
+    }
+    findings
+}
+
+/// 全技術者の資格チェック
+pub fn run_person_check(project: &ProjectData, registry: &[Person], today: &str) -> Vec<CheckResult> {
+    let period_start = project.period_start.as_deref().filter(|s| !s.is_empty());
+    let period_end = project.period_end.as_deref().filter(|s| !s.is_empty());
+    let mut results = Vec::new();
+
+    let mut push_person = |contractor_name: &str, doc_name: String, warn_unlinked: bool, person_id: Option<&str>| {
+        let result = |status, message: String| CheckResult {
+            contractor_name: contractor_name.to_string(),
+            doc_name: doc_name.clone(),
+            status,
+            message,
+        };
+        match person_id.filter(|id| !id.is_empty()) {
+            None => {
+                if warn_unlinked {
+                    results.push(result(CheckStatus::Warning, "登録簿と未連携のため資格を確認できません".to_string()));
+                }
+            }
+            Some(id) => match find_person(registry, id) {
+                None => results.push(result(CheckStatus::Error, format!("登録簿に存在しない人物です（ID: {}）", id))),
+                Some(person) => {
+                    let findings = validate_person(person, period_start, period_end, today);
+                    if findings.is_empty() {
+                        results.push(result(CheckStatus::Ok, format!("{}: 工期中有効", person.name)));
+                    } else {
+                        results.extend(findings.into_iter().map(|(status, message)| {
+                            result(status, format!("{}: {}", person.name, message))
+                        }));
+                    }
+                }
+            },
+        }
+    };
+
+    push_person(
+        &project.project_name,
+        "現場代理人".to_string(),
+        project.site_representative.as_deref().is_some_and(|n| !n.trim().is_empty()),
+        project.site_representative_id.as_deref(),
+    );
+    push_person(
+        &project.project_name,
+        "主任技術者".to_string(),
+        project.chief_engineer.as_deref().is_some_and(|n| !n.trim().is_empty()),
+        project.chief_engineer_id.as_deref(),
+    );
+
+    for contractor in &project.contractors {
+        for (doc_key, doc) in contractor.sorted_docs().into_iter().filter(|(k, _)| is_person_doc(k)) {
+            // 未連携の書類は提出済みの場合のみ警告
+            push_person(&contractor.name, doc_key.clone(), doc.status, doc.person_id.as_deref());
+        }
+    }
+
+    results
+}
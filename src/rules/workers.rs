@@ -0,0 +1,98 @@
+//! 作業員名簿チェック
+//!
+//! 作業員ごとに健康診断の受診日（1年以内）、資格の有効期限、
+//! 社会保険の加入状況を検証する
+
+use crate::models::{ProjectData, Worker};
+use crate::utils::date::{add_days, add_years};
+use crate::{CheckResult, CheckStatus};
+
+/// 期限切れ間近として警告する日数
+const EXPIRY_WARNING_DAYS: i64 = 30;
+
+/// 作業員1名分の検証
+pub fn validate_worker(worker: &Worker, today: &str) -> Vec<(CheckStatus, String)> {
+    let mut findings = Vec::new();
+    let warning_date = add_days(today, EXPIRY_WARNING_DAYS).unwrap_or_else(|| today.to_string());
+
+    // 健康診断: 受診日から1年以内
+    match worker.health_check_date.as_deref().filter(|d| !d.is_empty()) {
+        None => findings.push((CheckStatus::Warning, "健康診断の受診日が未登録".to_string())),
+        Some(date) => match add_years(date, 1) {
+            None => findings.push((CheckStatus::Warning, format!("健康診断日の形式が不正: {}", date))),
+            Some(due) if due.as_str() < today => {
+                findings.push((CheckStatus::Error, format!("健康診断が1年以上前です（受診日: {}）", date)));
+            }
+            Some(due) if due.as_str() <= warning_date.as_str() => {
+                findings.push((CheckStatus::Warning, format!("健康診断の再受診期限が近づいています（期限: {}）", due)));
+            }
+            Some(_) => {}
+        },
+    }
+
+    // 資格: 期限切れの資格で就労させない
+    for q in &worker.qualifications {
+        let Some(expiry) = q.expiry.as_deref().filter(|d| !d.is_empty()) else {
+            continue;
+        };
+        let name = if q.name.is_empty() { q.kind.label() } else { q.name.as_str() };
+        if expiry < today {
+            findings.push((CheckStatus::Error, format!("{}（{}）が期限切れ: {}", name, q.kind.label(), expiry)));
+        } else if expiry <= warning_date.as_str() {
+            findings.push((CheckStatus::Warning, format!("{}（{}）の期限間近: {}", name, q.kind.label(), expiry)));
+        }
+    }
+
+    // 社会保険
+    let missing: Vec<_> = [
+        (worker.insurance.health, "健康保険"),
+        (worker.insurance.pension, "厚生年金"),
+        (worker.insurance.employment, "雇用保険"),
+    ]
+    .into_iter()
+    .filter(|(enrolled, _)| !enrolled)
+    .map(|(_, label)| label)
+    .collect();
+    if !missing.is_empty() {
+        findings.push((CheckStatus::Warning, format!("社会保険未加入: {}", missing.join("・"))));
+    }
+
+    findings
+}
+
+/// 全業者の作業員名簿チェック
+pub fn run_worker_check(project: &ProjectData, today: &str) -> Vec<CheckResult> {
+    let mut results = Vec::new();
+    for contractor in &project.contractors {
+        if contractor.workers.is_empty() {
+            results.push(CheckResult {
+                contractor_name: contractor.name.clone(),
+                doc_name: "作業員名簿".to_string(),
+                status: CheckStatus::Warning,
+                message: "作業員が登録されていません".to_string(),
+            });
+            continue;
+        }
+
+        for worker in &contractor.workers {
+            let doc_name = format!("作業員名簿: {}", if worker.name.is_empty() { "(氏名未入力)" } else { &worker.name });
+            let findings = validate_worker(worker, today);
+            if findings.is_empty() {
+                results.push(CheckResult {
+                    contractor_name: contractor.name.clone(),
+                    doc_name,
+                    status: CheckStatus::Ok,
+                    message: "OK".to_string(),
+                });
+            } else {
+                results.extend(findings.into_iter().map(|(status, message)| CheckResult {
+                    contractor_name: contractor.name.clone(),
+                    doc_name: doc_name.clone(),
+                    status,
+                    message,
+                }));
+            }
+        }
+    }
+    results
+}
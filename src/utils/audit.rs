@@ -0,0 +1,86 @@
+//! 書類の変更履歴（監査ログ）
+//!
+//! 提出状況・URL・AI判定の変更を、操作者名（localStorage）と日時付きで
+//! DocStatus.history に追記する
+
+use crate::models::{DocChange, DocChangeField, DocChangeSource, DocStatus, ProjectData};
+
+const ACTOR_NAME_KEY: &str = "sekou_taisei_actor_name";
+
+/// 操作者名を保存
+pub fn save_actor_name(name: &str) {
+    if let Some(window) = web_sys::window() {
+        if let Ok(Some(storage)) = window.local_storage() {
+            let _ = storage.set_item(ACTOR_NAME_KEY, name.trim());
+        }
+    }
+}
+
+/// 操作者名を取得（未設定は空文字）
+pub fn get_actor_name() -> String {
+    web_sys::window()
+        .and_then(|w| w.local_storage().ok().flatten())
+        .and_then(|s| s.get_item(ACTOR_NAME_KEY).ok().flatten())
+        .unwrap_or_default()
+}
+
+/// 現在日時（ISO 8601）
+pub fn now_iso() -> String {
+    js_sys::Date::new_0().to_iso_string().as_string().unwrap_or_default()
+}
+
+/// 提出状況の表示値
+fn status_value(status: bool) -> Option<String> {
+    Some(if status { "提出済" } else { "未提出" }.to_string())
+}
+
+/// AIチェックの判定値
+fn ai_status_value(doc: &DocStatus) -> Option<String> {
+    doc.check_result.as_ref().map(|r| r.status.clone())
+}
+
+/// 1書類の変更を履歴に追記（before が None の書類は新規追加として提出状況・URLを記録）
+pub fn record_doc_change(before: Option<&DocStatus>, after: &mut DocStatus, source: DocChangeSource, actor: &str, at: &str) {
+    let (old_status, old_url, old_ai) = match before {
+        Some(b) => (status_value(b.status), b.url.clone(), ai_status_value(b)),
+        None => (None, None, None),
+    };
+    let (new_status, new_url, new_ai) = (status_value(after.status), after.url.clone(), ai_status_value(after));
+    after.record_change(DocChangeField::Status, old_status, new_status, source, actor, at);
+    after.record_change(DocChangeField::Url, old_url, new_url, source, actor, at);
+    after.record_change(DocChangeField::AiStatus, old_ai, new_ai, source, actor, at);
+}
+
+/// 編集前後のプロジェクトを比較し、変更のあった書類に履歴を追記
+///
+/// 履歴は編集対象外のため、編集前の履歴を引き継いでから追記する
+pub fn record_project_changes(before: &ProjectData, after: &mut ProjectData, source: DocChangeSource) {
+    let actor = get_actor_name();
+    let at = now_iso();
+    for contractor in &mut after.contractors {
+        let old_docs = before.contractors.iter().find(|c| c.id == contractor.id).map(|c| &c.docs);
+        for (key, doc) in contractor.docs.iter_mut() {
+            let old = old_docs.and_then(|d| d.get(key));
+            if let Some(old) = old {
+                doc.history = old.history.clone();
+            }
+            record_doc_change(old, doc, source, &actor, &at);
+        }
+    }
+}
+
+/// 履歴1件の表示文（例: 2026-10-18 09:30 山田 提出状況: 未提出 → 提出済（手動編集））
+pub fn format_change(change: &DocChange) -> String {
+    let at = change.at.get(..16).unwrap_or(&change.at).replace('T', " ");
+    let value = |v: &Option<String>| v.clone().unwrap_or_else(|| "なし".to_string());
+    let actor = if change.actor.is_empty() { "操作者未設定" } else { change.actor.as_str() };
+    format!(
+        "{} {} {}: {} → {}（{}）",
+        at,
+        actor,
+        change.field.label(),
+        value(&change.old),
+        value(&change.new),
+        change.source.label()
+    )
+}
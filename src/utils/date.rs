@@ -0,0 +1,169 @@
+//! 日付計算ユーティリティ
+//!
+//! yyyy-MM-dd 形式の文字列を対象にした最小限のグレゴリオ暦計算
+
+/// yyyy-MM-dd を (年, 月, 日) に分解（不正な日付は None）
+pub fn parse_ymd(date: &str) -> Option<(i32, u32, u32)> {
+    let mut parts = date.trim().splitn(3, '-');
+    let y = parts.next()?.parse::<i32>().ok()?;
+    let m = parts.next()?.parse::<u32>().ok()?;
+    // ISO日時（yyyy-MM-ddTHH:mm:ss）の時刻部分は無視
+    let d = parts.next()?.chars().take_while(|c| c.is_ascii_digit()).collect::<String>().parse::<u32>().ok()?;
+    ((1..=12).contains(&m) && d >= 1 && d <= days_in_month(y, m)).then_some((y, m, d))
+}
+
+/// (年, 月, 日) を yyyy-MM-dd に整形
+pub fn format_ymd(y: i32, m: u32, d: u32) -> String {
+    format!("{:04}-{:02}-{:02}", y, m, d)
+}
+
+pub fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || (year % 400 == 0)
+}
+
+pub fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        1 | 3 | 5 | 7 | 8 | 10 | 12 => 31,
+        4 | 6 | 9 | 11 => 30,
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        _ => 0,
+    }
+}
+
+/// 1970-01-01からの通算日数
+fn to_days(y: i32, m: u32, d: u32) -> i64 {
+    // Howard Hinnantのdays_from_civil
+    let y = if m <= 2 { y - 1 } else { y } as i64;
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let m = m as i64;
+    let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + d as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146097 + doe - 719468
+}
+
+/// 通算日数から (年, 月, 日) へ
+fn from_days(days: i64) -> (i32, u32, u32) {
+    let z = days + 719468;
+    let era = if z >= 0 { z } else { z - 146096 } / 146097;
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = (yoe + era * 400 + if m <= 2 { 1 } else { 0 }) as i32;
+    (y, m, d)
+}
+
+/// 日数を加算（負数で減算）
+pub fn add_days(date: &str, days: i64) -> Option<String> {
+    let (y, m, d) = parse_ymd(date)?;
+    let (y, m, d) = from_days(to_days(y, m, d) + days);
+    Some(format_ymd(y, m, d))
+}
+
+/// 年数を加算（2/29は平年では2/28に丸める）
+pub fn add_years(date: &str, years: i32) -> Option<String> {
+    let (y, m, d) = parse_ymd(date)?;
+    let y = y + years;
+    Some(format_ymd(y, m, d.min(days_in_month(y, m))))
+}
+
+/// 文字列中の日付表記の範囲（「年」「月」「日」がこの順に近くに並ぶもの。元号・数字から「日」まで）
+///
+/// 空白を除き数字を半角にした文字列（utils::search::normalize_chars）を対象にする。
+/// 空欄の日付（「令和 年 月 日」）も範囲として返すため、値は normalize_date で確かめる
+pub fn find_date_spans(text: &[char]) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut from = 0;
+    for y in (0..text.len()).filter(|&i| text[i] == '年') {
+        if y < from {
+            continue;
+        }
+        let Some(m) = text[y + 1..].iter().take(4).position(|c| *c == '月').map(|p| y + 1 + p) else {
+            continue;
+        };
+        let Some(d) = text[m + 1..].iter().take(4).position(|c| *c == '日').map(|p| m + 1 + p) else {
+            continue;
+        };
+        // 年の前の元号・数字
+        let mut start = y;
+        while start > from && (text[start - 1].is_ascii_digit() || text[start - 1] == '元') {
+            start -= 1;
+        }
+        for era in ["令和", "平成", "昭和"] {
+            let era: Vec<char> = era.chars().collect();
+            if start >= from + era.len() && text[start - era.len()..start] == era[..] {
+                start -= era.len();
+            }
+        }
+        spans.push((start, d + 1));
+        from = d + 1;
+    }
+    spans
+}
+
+/// 書類に記載された日付表記を yyyy-MM-dd に正規化
+///
+/// 対応: 2024-04-01 / 2024/4/1 / 2024年4月1日 / 令和6年4月1日 / 平成31年4月1日（元年表記も可）
+pub fn normalize_date(text: &str) -> Option<String> {
+    let text: String = text.chars()
+        .filter(|c| !c.is_whitespace())
+        // 全角数字を半角に
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - '０' as u32 + '0' as u32).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+
+    let (base, rest) = [("令和", 2018), ("平成", 1988), ("昭和", 1925)]
+        .into_iter()
+        .find_map(|(era, base)| text.strip_prefix(era).map(|r| (Some(base), r.to_string())))
+        .unwrap_or((None, text.clone()));
+    let rest = rest.replacen("元年", "1年", 1);
+
+    let nums: Vec<u32> = rest
+        .split(|c: char| !c.is_ascii_digit())
+        .filter(|s| !s.is_empty())
+        .take(3)
+        .filter_map(|s| s.parse().ok())
+        .collect();
+    let [y, m, d] = nums[..] else {
+        return None;
+    };
+    let y = match base {
+        Some(base) => base + y as i32,
+        None => y as i32,
+    };
+    parse_ymd(&format_ymd(y, m, d)).map(|(y, m, d)| format_ymd(y, m, d))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_date_formats() {
+        assert_eq!(normalize_date("2024-04-01").as_deref(), Some("2024-04-01"));
+        assert_eq!(normalize_date("2024/4/1").as_deref(), Some("2024-04-01"));
+        assert_eq!(normalize_date("2024年4月1日").as_deref(), Some("2024-04-01"));
+        assert_eq!(normalize_date("２０２４年 ４月 １日").as_deref(), Some("2024-04-01"));
+    }
+
+    #[test]
+    fn normalize_date_japanese_eras() {
+        assert_eq!(normalize_date("令和6年4月1日").as_deref(), Some("2024-04-01"));
+        assert_eq!(normalize_date("令和元年5月1日").as_deref(), Some("2019-05-01"));
+        assert_eq!(normalize_date("平成31年4月30日").as_deref(), Some("2019-04-30"));
+        assert_eq!(normalize_date("昭和64年1月7日").as_deref(), Some("1989-01-07"));
+    }
+
+    #[test]
+    fn normalize_date_rejects_invalid_or_blank() {
+        assert_eq!(normalize_date("2023-02-29"), None);
+        assert_eq!(normalize_date("令和 年 月 日"), None);
+        assert_eq!(normalize_date(""), None);
+    }
+}
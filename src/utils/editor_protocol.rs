@@ -0,0 +1,309 @@
+//! Reactエディタ（editor/index.html）との連携プロトコル
+//!
+//! iframeとのpostMessageは `EditorMessage` に集約し、バージョン（v）と送信元オリジンを
+//! 確認してから登録済みハンドラへ配信する。windowのmessageリスナーは1つだけ登録し、
+//! コンポーネントのハンドラは破棄時に解除する。
+//! React側の定義は react-app/src/utils/editorProtocol.ts（変更時は両方を更新し、バージョンを上げる）
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+
+use leptos::on_cleanup;
+use serde::{Deserialize, Serialize};
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::models::{CheckResultData, FindingLocation};
+use crate::utils::log_trace::log_error;
+
+/// プロトコルのバージョン（メッセージのvフィールド）
+pub const PROTOCOL_VERSION: u32 = 2;
+
+// ============================================
+// メッセージ定義
+// ============================================
+
+/// iframeとの間でやり取りするメッセージ（typeで判別、フィールドはcamelCase）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case", rename_all_fields = "camelCase")]
+pub enum EditorMessage {
+    /// PDF編集画面から戻る
+    Back,
+    /// PDFビューワから戻る
+    ViewerBack,
+    /// PDFビューワから編集画面へ
+    ViewerEdit,
+    /// AIチェック画面から保存せずに戻る
+    AiCheckCancel,
+    /// スプレッドシートのAIチェック画面から戻る
+    SpreadsheetCheckCancel,
+    /// AIチェック結果の保存
+    AiCheckResult {
+        result: CheckResultData,
+        contractor: String,
+        #[serde(default)]
+        contractor_id: Option<String>,
+        #[serde(default)]
+        doc_type: Option<String>,
+        doc_key: String,
+        #[serde(default)]
+        file_id: Option<String>,
+    },
+    /// AI一括チェックのワーカーから1件分の結果（失敗時はerror）
+    BatchCheckResult {
+        job_id: String,
+        contractor: String,
+        doc_key: String,
+        #[serde(default)]
+        file_id: Option<String>,
+        #[serde(default)]
+        result: Option<CheckResultData>,
+        #[serde(default)]
+        error: Option<String>,
+    },
+    /// スプレッドシートの修正版を採用した（GAS側でURL更新済み）
+    FixedVersionAdopted {
+        new_file_id: String,
+        #[serde(default)]
+        file_name: Option<String>,
+        contractor: String,
+        #[serde(default)]
+        contractor_id: Option<String>,
+        #[serde(default)]
+        doc_type: Option<String>,
+        doc_key: String,
+    },
+    /// APIキー設定の完了
+    ApikeySetupComplete,
+    /// APIキー設定の中止
+    ApikeySetupCancel,
+}
+
+impl EditorMessage {
+    /// メッセージ種別（ログ用。内容は含めない）
+    pub fn kind(&self) -> &'static str {
+        match self {
+            EditorMessage::Back => "back",
+            EditorMessage::ViewerBack => "viewer-back",
+            EditorMessage::ViewerEdit => "viewer-edit",
+            EditorMessage::AiCheckCancel => "ai-check-cancel",
+            EditorMessage::SpreadsheetCheckCancel => "spreadsheet-check-cancel",
+            EditorMessage::AiCheckResult { .. } => "ai-check-result",
+            EditorMessage::BatchCheckResult { .. } => "batch-check-result",
+            EditorMessage::FixedVersionAdopted { .. } => "fixed-version-adopted",
+            EditorMessage::ApikeySetupComplete => "apikey-setup-complete",
+            EditorMessage::ApikeySetupCancel => "apikey-setup-cancel",
+        }
+    }
+}
+
+/// バージョン付きのメッセージ
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Envelope {
+    v: u32,
+    #[serde(flatten)]
+    message: EditorMessage,
+}
+
+// ============================================
+// 受信・配信
+// ============================================
+
+type Handler = Rc<dyn Fn(&EditorMessage)>;
+
+thread_local! {
+    static HANDLERS: RefCell<Vec<(usize, Handler)>> = const { RefCell::new(Vec::new()) };
+    static NEXT_HANDLER_ID: Cell<usize> = const { Cell::new(0) };
+    static LISTENER_INSTALLED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// 自アプリのオリジン（editor/index.htmlは同一オリジンで配信）
+fn own_origin() -> Option<String> {
+    web_sys::window()?.location().origin().ok()
+}
+
+/// messageイベントを検証してメッセージに変換（対象外はNone）
+fn parse_event(event: &web_sys::MessageEvent) -> Option<EditorMessage> {
+    if own_origin().as_deref() != Some(event.origin().as_str()) {
+        return None;
+    }
+    let data = event.data();
+    // typeのないメッセージ（拡張機能・開発ツール等）は対象外
+    js_sys::Reflect::get(&data, &JsValue::from_str("type")).ok()?.as_string()?;
+    match serde_wasm_bindgen::from_value::<Envelope>(data) {
+        Ok(envelope) if envelope.v == PROTOCOL_VERSION => Some(envelope.message),
+        Ok(envelope) => {
+            log_error("editor-protocol", &format!("未対応のバージョン: v{}（対応: v{}）", envelope.v, PROTOCOL_VERSION));
+            None
+        }
+        Err(e) => {
+            log_error("editor-protocol", &format!("メッセージの解析に失敗: {:?}", e));
+            None
+        }
+    }
+}
+
+/// 登録済みハンドラへ配信
+fn dispatch(event: web_sys::MessageEvent) {
+    let Some(message) = parse_event(&event) else {
+        return;
+    };
+    // ハンドラ内で登録・解除されても良いように複製してから呼ぶ
+    let handlers: Vec<Handler> = HANDLERS.with(|h| h.borrow().iter().map(|(_, f)| f.clone()).collect());
+    for handler in handlers {
+        handler(&message);
+    }
+}
+
+/// windowのmessageリスナーを登録（初回のみ）
+fn install_listener() {
+    if LISTENER_INSTALLED.with(|i| i.replace(true)) {
+        return;
+    }
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let listener = Closure::<dyn FnMut(web_sys::MessageEvent)>::new(dispatch);
+    let _ = window.add_event_listener_with_callback("message", listener.as_ref().unchecked_ref());
+    // アプリ全体で1つだけなので保持し続ける
+    listener.forget();
+}
+
+/// エディタからのメッセージを受け取るハンドラを登録（現在のコンポーネント破棄時に解除）
+pub fn on_editor_message(handler: impl Fn(&EditorMessage) + 'static) {
+    install_listener();
+    let id = NEXT_HANDLER_ID.with(|n| n.replace(n.get() + 1));
+    HANDLERS.with(|h| h.borrow_mut().push((id, Rc::new(handler))));
+    on_cleanup(move || {
+        HANDLERS.with(|h| h.borrow_mut().retain(|(i, _)| *i != id));
+    });
+}
+
+// ============================================
+// 起動URL
+// ============================================
+
+/// エディタの起動モード（URLのmode）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditorMode {
+    /// PDF編集（modeなし）
+    Edit,
+    /// PDFビューワ
+    View,
+    /// PDFのAIチェック
+    Check,
+    /// スプレッドシートのAIチェック
+    SpreadsheetCheck,
+    /// AI一括チェックのワーカー（非表示）
+    BatchCheck,
+    /// APIキー設定
+    ApiKey,
+}
+
+impl EditorMode {
+    fn key(&self) -> Option<&'static str> {
+        match self {
+            EditorMode::Edit => None,
+            EditorMode::View => Some("view"),
+            EditorMode::Check => Some("check"),
+            EditorMode::SpreadsheetCheck => Some("spreadsheet-check"),
+            EditorMode::BatchCheck => Some("batch-check"),
+            EditorMode::ApiKey => Some("apikey"),
+        }
+    }
+}
+
+/// editor/index.html の起動URL
+#[derive(Debug, Clone)]
+pub struct EditorUrl {
+    mode: EditorMode,
+    params: Vec<(&'static str, String)>,
+}
+
+impl EditorUrl {
+    pub fn new(mode: EditorMode) -> Self {
+        EditorUrl { mode, params: Vec::new() }
+    }
+
+    fn param(mut self, key: &'static str, value: &str) -> Self {
+        self.params.push((key, value.to_string()));
+        self
+    }
+
+    /// Google DriveのファイルID
+    pub fn file_id(self, file_id: &str) -> Self {
+        self.param("fileId", file_id)
+    }
+
+    /// GAS URL（未設定の場合は付けない）
+    pub fn gas_url(self, gas_url: Option<&str>) -> Self {
+        match gas_url.filter(|u| !u.is_empty()) {
+            Some(url) => self.param("gasUrl", url),
+            None => self,
+        }
+    }
+
+    /// 対象の書類（業者名・業者ID・書類名・書類キー）
+    pub fn doc(self, contractor: &str, contractor_id: &str, doc_type: &str, doc_key: &str) -> Self {
+        self.param("contractor", contractor)
+            .param("contractorId", contractor_id)
+            .param("docType", doc_type)
+            .param("docKey", doc_key)
+    }
+
+    /// スプレッドシートIDとシートID（gid）
+    pub fn spreadsheet(self, spreadsheet_id: &str, gid: Option<&str>) -> Self {
+        let url = self.param("spreadsheetId", spreadsheet_id);
+        match gid {
+            Some(gid) => url.param("gid", gid),
+            None => url,
+        }
+    }
+
+    /// 工事名（事業所名の照合用）
+    pub fn project_name(self, project_name: &str) -> Self {
+        self.param("projectName", project_name)
+    }
+
+    /// Excelファイル（Drive上のファイルIDで取得）
+    pub fn excel(self, file_id: &str) -> Self {
+        self.param("isExcel", "true").file_id(file_id)
+    }
+
+    /// 開いた直後に表示するページと強調する領域（PDFビューワ）
+    pub fn locate(self, at: Option<&FindingLocation>) -> Self {
+        let Some(at) = at else {
+            return self;
+        };
+        let url = self.param("page", &at.page.to_string());
+        match &at.bbox {
+            Some(b) => url.param("highlight", &format!("{},{},{},{}", b.x, b.y, b.width, b.height)),
+            None => url,
+        }
+    }
+
+    /// 一括チェックのジョブID（結果の照合用）
+    pub fn job_id(self, job_id: &str) -> Self {
+        self.param("jobId", job_id)
+    }
+
+    /// AI自動修正モード
+    pub fn auto_fix(self, enabled: bool) -> Self {
+        if enabled { self.param("autoFix", "true") } else { self }
+    }
+
+    pub fn build(&self) -> String {
+        let mut query: Vec<String> = Vec::new();
+        if let Some(mode) = self.mode.key() {
+            query.push(format!("mode={}", mode));
+        }
+        query.extend(self.params.iter().map(|(key, value)| {
+            format!("{}={}", key, js_sys::encode_uri_component(value))
+        }));
+        if query.is_empty() {
+            "editor/index.html".to_string()
+        } else {
+            format!("editor/index.html?{}", query.join("&"))
+        }
+    }
+}
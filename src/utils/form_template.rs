@@ -0,0 +1,48 @@
+//! 帳票テンプレートのLocalStorage管理とJSON入出力
+//!
+//! テンプレートは書類種別ごとに共通のため、プロジェクトデータとは別キーで保存する
+
+use crate::models::FormTemplate;
+
+const TEMPLATES_KEY: &str = "sekou_taisei_form_templates";
+
+/// テンプレートを保存
+pub fn save_form_templates(templates: &[FormTemplate]) {
+    if let Some(window) = web_sys::window() {
+        if let Ok(Some(storage)) = window.local_storage() {
+            if let Ok(json) = serde_json::to_string(templates) {
+                let _ = storage.set_item(TEMPLATES_KEY, &json);
+            }
+        }
+    }
+}
+
+/// テンプレートを読み込み（未保存・破損時は空）
+pub fn load_form_templates() -> Vec<FormTemplate> {
+    let load = || -> Option<Vec<FormTemplate>> {
+        let window = web_sys::window()?;
+        let storage = window.local_storage().ok()??;
+        let json = storage.get_item(TEMPLATES_KEY).ok()??;
+        serde_json::from_str(&json).ok()
+    };
+    load().unwrap_or_default()
+}
+
+/// JSONに書き出し
+pub fn export_form_templates(templates: &[FormTemplate]) -> Result<String, String> {
+    serde_json::to_string_pretty(templates).map_err(|e| format!("JSON変換失敗: {}", e))
+}
+
+/// JSONから読み込み、同じ書類種別のテンプレートは置き換える（戻り値は読み込んだ件数）
+pub fn import_form_templates(templates: &mut Vec<FormTemplate>, json: &str) -> Result<usize, String> {
+    let imported: Vec<FormTemplate> = serde_json::from_str(json)
+        .map_err(|e| format!("テンプレートJSONの解析に失敗しました: {}", e))?;
+    let count = imported.len();
+    for template in imported {
+        match templates.iter_mut().find(|t| t.doc_type == template.doc_type) {
+            Some(existing) => *existing = template,
+            None => templates.push(template),
+        }
+    }
+    Ok(count)
+}
@@ -89,8 +89,7 @@ pub fn clear_gas_url() {
 pub fn init_gas_from_url_params() -> Option<String> {
     let window = web_sys::window()?;
     let search = window.location().search().ok()?;
-    if search.starts_with("?gas=") {
-        let encoded = &search[5..];
+    if let Some(encoded) = search.strip_prefix("?gas=") {
         let decoded = js_sys::decode_uri_component(encoded).ok()?.as_string()?;
         save_gas_url(&decoded);
         // URLからパラメータを削除
@@ -2,7 +2,6 @@
 //! すべての操作とイベントを自動記録し、後から確認できるようにする
 
 use wasm_bindgen::prelude::*;
-use wasm_bindgen_futures::JsFuture;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
 
@@ -67,6 +66,7 @@ impl LogTrace {
         self.log("info", category, message, Some(data));
     }
 
+    #[allow(dead_code)]
     pub fn warn(&mut self, category: &str, message: &str) {
         self.log("warn", category, message, None);
     }
@@ -99,7 +99,6 @@ impl LogTrace {
                 if let Ok(Some(json_str)) = storage.get_item(STORAGE_KEY) {
                     if let Ok(logs) = serde_json::from_str::<Vec<LogEntry>>(&json_str) {
                         self.logs = logs.into_iter().collect();
-                        return;
                     }
                 }
             }
@@ -146,7 +145,7 @@ impl LogTrace {
                             let _ = a.set_attribute("href", &url);
                             let _ = a.set_attribute("download", &filename);
                             if let Some(element) = a.dyn_ref::<web_sys::HtmlElement>() {
-                                let _ = element.click();
+                                element.click();
                             }
                             let _ = web_sys::Url::revoke_object_url(&url);
                         }
@@ -174,6 +173,7 @@ pub fn log_info_with_data(category: &str, message: &str, data: serde_json::Value
     });
 }
 
+#[allow(dead_code)]
 pub fn log_warn(category: &str, message: &str) {
     LOG_TRACE.with(|trace| {
         trace.borrow_mut().warn(category, message);
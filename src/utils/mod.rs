@@ -1,10 +1,24 @@
 //! ユーティリティモジュール
 
+pub mod audit;
 pub mod cache;
+pub mod csv;
+pub mod date;
+pub mod editor_protocol;
+pub mod form_template;
 pub mod gas;
 pub mod log_trace;
+pub mod ocr_blank;
+pub mod ocr_extract;
+pub mod ocr_import;
+pub mod ocr_layout;
+pub mod registry;
+pub mod router;
+pub mod search;
 
 use base64::Engine;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
 
 // 共通ヘルパー
 
@@ -21,3 +35,129 @@ pub fn decode_base64(data: &str) -> Option<String> {
         .ok()
         .and_then(|bytes| String::from_utf8(bytes).ok())
 }
+
+/// 金額を3桁区切りの円表記に（例: 50,000,000円）
+pub fn format_yen(amount: u64) -> String {
+    let digits = amount.to_string();
+    let mut out = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i).is_multiple_of(3) {
+            out.push(',');
+        }
+        out.push(c);
+    }
+    out.push('円');
+    out
+}
+
+/// 金額入力を解釈（カンマ区切り・全角数字・「億」「万」の単位可。例: 1,000万 → 10000000）
+pub fn parse_yen(text: &str) -> Option<u64> {
+    let text: String = text
+        .chars()
+        .filter(|c| !matches!(c, ',' | '，' | '円') && !c.is_whitespace())
+        .map(|c| match c {
+            '０'..='９' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+            _ => c,
+        })
+        .collect();
+    if text.is_empty() {
+        return None;
+    }
+    let mut total: u64 = 0;
+    let mut rest = text.as_str();
+    for (unit, scale) in [('億', 100_000_000), ('万', 10_000)] {
+        if let Some((head, tail)) = rest.split_once(unit) {
+            total = total.checked_add(head.parse::<u64>().ok()?.checked_mul(scale)?)?;
+            rest = tail;
+        }
+    }
+    if rest.is_empty() {
+        Some(total)
+    } else {
+        total.checked_add(rest.parse().ok()?)
+    }
+}
+
+/// テキストをファイルとしてダウンロード（Blob + aタグ）
+pub fn download_file(filename: &str, mime_type: &str, content: &str) {
+    let Some(document) = web_sys::window().and_then(|w| w.document()) else {
+        return;
+    };
+
+    let blob_parts = js_sys::Array::new();
+    blob_parts.push(&JsValue::from_str(content));
+    let options = web_sys::BlobPropertyBag::new();
+    options.set_type(mime_type);
+
+    if let Ok(blob) = web_sys::Blob::new_with_str_sequence_and_options(&blob_parts, &options) {
+        if let Ok(url) = web_sys::Url::create_object_url_with_blob(&blob) {
+            if let Ok(a) = document.create_element("a") {
+                let _ = a.set_attribute("href", &url);
+                let _ = a.set_attribute("download", filename);
+                if let Some(element) = a.dyn_ref::<web_sys::HtmlElement>() {
+                    element.click();
+                }
+                let _ = web_sys::Url::revoke_object_url(&url);
+            }
+        }
+    }
+}
+
+/// ファイルの内容をテキストとして読み込む
+///
+/// UTF-8として不正なバイト列はShift_JIS（Excel・CCUSなどの出力既定）として解釈する
+pub fn read_file_as_text<F>(file: &web_sys::File, on_load: F)
+where
+    F: Fn(String) + 'static,
+{
+    let Ok(reader) = web_sys::FileReader::new() else {
+        return;
+    };
+    let reader_clone = reader.clone();
+    let onload = Closure::wrap(Box::new(move |_: web_sys::Event| {
+        if let Ok(result) = reader_clone.result() {
+            let bytes = js_sys::Uint8Array::new(&result).to_vec();
+            let text = match String::from_utf8(bytes) {
+                Ok(text) => text,
+                Err(e) => web_sys::TextDecoder::new_with_label("shift_jis")
+                    .and_then(|d| d.decode_with_u8_array(e.as_bytes()))
+                    .unwrap_or_default(),
+            };
+            on_load(text);
+        }
+    }) as Box<dyn FnMut(_)>);
+    reader.set_onload(Some(onload.as_ref().unchecked_ref()));
+    onload.forget();
+    let _ = reader.read_as_array_buffer(file);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_yen_accepts_separators_and_units() {
+        assert_eq!(parse_yen("50000000"), Some(50_000_000));
+        assert_eq!(parse_yen("50,000,000円"), Some(50_000_000));
+        assert_eq!(parse_yen("１，０００万円"), Some(10_000_000));
+        assert_eq!(parse_yen("1,000万"), Some(10_000_000));
+        assert_eq!(parse_yen("1億2,500万"), Some(125_000_000));
+        assert_eq!(parse_yen("3万5000"), Some(35_000));
+    }
+
+    #[test]
+    fn parse_yen_rejects_partial_input() {
+        assert_eq!(parse_yen(""), None);
+        assert_eq!(parse_yen("万"), None);
+        assert_eq!(parse_yen("1,000万円くらい"), None);
+        assert_eq!(parse_yen("-5"), None);
+    }
+
+    #[test]
+    fn format_yen_groups_thousands() {
+        assert_eq!(format_yen(0), "0円");
+        assert_eq!(format_yen(1000), "1,000円");
+        assert_eq!(format_yen(45_000_000), "45,000,000円");
+        assert_eq!(parse_yen(&format_yen(45_000_000)), Some(45_000_000));
+    }
+}
@@ -0,0 +1,145 @@
+//! 記入欄の未記入検出（OCRトークンと帳票テンプレートの比較）
+//!
+//! 帳票テンプレートの記入欄ごとに、領域内のOCRトークンから記入の有無を判定し、
+//! 空欄を位置付きの MissingField として返す（AIを使わない）。
+//! 欄内に印字されている「印」「年 月 日」などは記入とみなさない。
+//! 押印だけの署名欄は文字として認識されないため未記入と判定される
+
+use super::date::normalize_date;
+use super::ocr_extract::{field_region, region_text};
+use super::ocr_layout::OcrLayout;
+use crate::models::{FieldType, FormTemplate, OcrDocument};
+use crate::MissingField;
+
+/// 記入がなくても欄内に印字されている文字（印欄・日付欄・区切り記号）
+const PRINTED_CHARS: &str = "印㊞年月日令和平成昭和元□☐:：・/()（）";
+
+/// チェック欄の記入とみなす記号
+const CHECK_MARKS: &str = "✓✔レ☑☒■●○〇xXvV";
+
+// ============================================
+// 判定
+// ============================================
+
+/// 欄内の文字列が記入済みか（フィールドタイプ別）
+fn is_filled(field_type: &FieldType, text: &str) -> bool {
+    match field_type {
+        // 日付として読めること（「令和 年 月 日」のままは未記入）
+        FieldType::Date => normalize_date(text).is_some(),
+        FieldType::Checkbox => text.chars().any(|c| CHECK_MARKS.contains(c)),
+        FieldType::Text | FieldType::Signature | FieldType::Select => {
+            text.chars().any(|c| !c.is_whitespace() && !PRINTED_CHARS.contains(c))
+        }
+    }
+}
+
+/// 書類種別のテンプレートの記入欄のうち、未記入の欄（位置は目印で補正した書類上の位置）
+pub fn detect_blank_fields(doc: &OcrDocument, layout: &OcrLayout, templates: &[FormTemplate]) -> Vec<MissingField> {
+    let Some(template) = FormTemplate::find(templates, &doc.doc_type) else {
+        return Vec::new();
+    };
+    template
+        .fields
+        .iter()
+        .filter_map(|field| {
+            let (region, _) = field_region(layout, &doc.tokens, field);
+            let (text, _) = region_text(layout, &doc.tokens, field.page, &region);
+            (!is_filled(&field.field_type, &text)).then(|| MissingField {
+                field_name: field.name.clone(),
+                field_type: field.field_type.clone(),
+                value: text,
+                position: Some(region),
+                page: Some(field.page),
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ocr_layout::group_tokens;
+    use serde_json::json;
+
+    const DOC_TYPE: &str = "09_暴対法誓約書";
+
+    fn doc(tokens: &[(&str, f64, f64)]) -> OcrDocument {
+        let tokens: Vec<_> = tokens
+            .iter()
+            .map(|(text, x, y)| {
+                json!({
+                    "text": text,
+                    "page": 1,
+                    "normalized": { "x": x, "y": y, "width": 0.02, "height": 0.02 },
+                    "pixels": { "x": 0, "y": 0, "width": 20, "height": 20 },
+                    "page_size": { "width": 1000.0, "height": 1000.0 }
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({ "contractor": "", "doc_type": DOC_TYPE, "image_url": "", "tokens": tokens })).unwrap()
+    }
+
+    /// 氏名欄（左上）・日付欄（左下）・同意欄（右下）のテンプレート
+    fn templates() -> Vec<FormTemplate> {
+        let field = |name: &str, field_type: &str, x: f64, y: f64| {
+            json!({ "name": name, "field_type": field_type, "position": { "x": x, "y": y, "width": 0.3, "height": 0.1 } })
+        };
+        vec![serde_json::from_value(json!({
+            "doc_type": DOC_TYPE,
+            "fields": [
+                field("氏名", "text", 0.1, 0.1),
+                field("日付", "date", 0.1, 0.5),
+                field("同意", "checkbox", 0.6, 0.5)
+            ]
+        }))
+        .unwrap()]
+    }
+
+    fn blank_names(doc: &OcrDocument) -> Vec<String> {
+        detect_blank_fields(doc, &group_tokens(&doc.tokens), &templates()).into_iter().map(|b| b.field_name).collect()
+    }
+
+    #[test]
+    fn printed_characters_are_not_filled() {
+        assert!(!is_filled(&FieldType::Text, " 印 "));
+        assert!(!is_filled(&FieldType::Signature, "（ ㊞ ）"));
+        assert!(is_filled(&FieldType::Text, "山田 太郎 印"));
+        assert!(!is_filled(&FieldType::Date, "令和 年 月 日"));
+        assert!(is_filled(&FieldType::Date, "令和6年4月1日"));
+        assert!(!is_filled(&FieldType::Checkbox, "□ 同意する"));
+        assert!(is_filled(&FieldType::Checkbox, "☑ 同意する"));
+    }
+
+    #[test]
+    fn detects_blank_fields_in_template_regions() {
+        let blank = doc(&[("印", 0.2, 0.15), ("令", 0.15, 0.55), ("和", 0.17, 0.55), ("年", 0.21, 0.55), ("□", 0.65, 0.55)]);
+        assert_eq!(blank_names(&blank), ["氏名", "日付", "同意"]);
+
+        let filled = doc(&[
+            ("山", 0.15, 0.15),
+            ("田", 0.17, 0.15),
+            ("令", 0.15, 0.55),
+            ("和", 0.17, 0.55),
+            ("6", 0.19, 0.55),
+            ("年", 0.21, 0.55),
+            ("4", 0.23, 0.55),
+            ("月", 0.25, 0.55),
+            ("1", 0.27, 0.55),
+            ("日", 0.29, 0.55),
+            ("✓", 0.65, 0.55),
+        ]);
+        assert!(blank_names(&filled).is_empty());
+    }
+
+    #[test]
+    fn blank_fields_keep_region_and_page() {
+        let blanks = detect_blank_fields(&doc(&[]), &OcrLayout::default(), &templates());
+        assert_eq!(blanks.len(), 3);
+        assert_eq!(blanks[0].page, Some(1));
+        assert_eq!(blanks[0].position.as_ref().map(|p| (p.x, p.y)), Some((0.1, 0.1)));
+        // テンプレートのない書類種別は対象外
+        let mut other = doc(&[]);
+        other.doc_type = "01_建設業許可".to_string();
+        assert!(detect_blank_fields(&other, &OcrLayout::default(), &templates()).is_empty());
+    }
+}
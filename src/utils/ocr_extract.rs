@@ -0,0 +1,474 @@
+//! OCRトークンからの書類項目の抽出（テンプレート方式、オフライン）
+//!
+//! 書類種別ごとの抽出ルールで、AIチェックと同じキーの extracted_fields を作る。
+//! ルールは組み込み（暴対法誓約書などの定型書式。目印の右・左の文字列、日付の行）と、
+//! OCRビューで作成した帳票テンプレートの記入欄（目印からの相対領域）。
+//! 各項目には信頼度（0.0〜1.0）を付ける。同じキーはテンプレートの記入欄を優先する
+
+use std::collections::HashMap;
+
+use super::ocr_layout::{OcrLayout, OcrLine};
+use crate::models::{DocKind, FieldPosition, FormTemplate, OcrDocument, OcrToken, StandardDoc, TemplateField};
+use super::date::{find_date_spans, normalize_date};
+use super::search::normalize_chars;
+
+// ============================================
+// 組み込みルール
+// ============================================
+
+/// 組み込みの抽出ルール
+enum BuiltinRule {
+    /// 目印の右側（同じ行）。true の場合は同じブロックの続く行も含める（住所など）
+    RightOf(&'static [&'static str], bool),
+    /// 目印の左側（同じ行。宛先の「御中」「殿」）
+    LeftOf(&'static [&'static str]),
+    /// 法人格を含む行（目印のない書式の会社名。宛先の行は除く）
+    CompanyLine,
+    /// 日付の行（令和○年○月○日など）
+    DateLine,
+}
+
+struct BuiltinField {
+    key: &'static str,
+    label: &'static str,
+    /// 先に一致したルールを使う
+    rules: &'static [BuiltinRule],
+}
+
+/// 宛先の目印
+const RECIPIENT_MARKS: &[&str] = &["御中", "殿"];
+
+/// 法人格（会社名の行の判定）
+const COMPANY_MARKS: &[&str] = &["株式会社", "有限会社", "合同会社", "合資会社", "合名会社", "(株)", "(有)"];
+
+/// 暴対法誓約書（キーはAIチェックの extracted_fields と共通）
+const ANTI_GANG_PLEDGE_FIELDS: &[BuiltinField] = &[
+    BuiltinField { key: "pledge_date", label: "誓約日", rules: &[BuiltinRule::DateLine] },
+    BuiltinField { key: "recipient", label: "宛先", rules: &[BuiltinRule::LeftOf(RECIPIENT_MARKS)] },
+    BuiltinField { key: "address", label: "住所", rules: &[BuiltinRule::RightOf(&["住所", "所在地"], true)] },
+    BuiltinField {
+        key: "company_name",
+        label: "法人名",
+        rules: &[BuiltinRule::RightOf(&["法人名", "商号又は名称", "商号", "会社名"], true), BuiltinRule::CompanyLine],
+    },
+    BuiltinField {
+        key: "representative_name",
+        label: "代表者",
+        rules: &[BuiltinRule::RightOf(&["代表者氏名", "代表者名", "代表者", "氏名"], false)],
+    },
+];
+
+/// 書類種別の組み込みルール
+fn builtin_fields(doc_type: &str) -> &'static [BuiltinField] {
+    match DocKind::parse(doc_type).standard() {
+        Some(StandardDoc::AntiGangPledge) => ANTI_GANG_PLEDGE_FIELDS,
+        _ => &[],
+    }
+}
+
+/// 抽出ルールがある書類種別か（組み込み・テンプレートのいずれか）
+pub fn has_extraction_rules(doc_type: &str, templates: &[FormTemplate]) -> bool {
+    !builtin_fields(doc_type).is_empty() || FormTemplate::find(templates, doc_type).is_some_and(|t| !t.fields.is_empty())
+}
+
+// ============================================
+// 信頼度
+// ============================================
+
+/// 目印と同じ行の値
+const CONFIDENCE_SAME_LINE: f64 = 0.9;
+/// 目印の行から続く行を含む値
+const CONFIDENCE_CONTINUED: f64 = 0.8;
+/// 法人格を含む行（目印なし）
+const CONFIDENCE_COMPANY_LINE: f64 = 0.6;
+/// 日付として解釈できた値
+const CONFIDENCE_DATE: f64 = 0.95;
+/// 目印で位置を補正した記入欄
+const CONFIDENCE_ANCHORED_REGION: f64 = 0.85;
+/// 目印のない記入欄（テンプレートの位置そのまま）
+const CONFIDENCE_REGION: f64 = 0.7;
+/// 目印が見つからず、テンプレートの位置で読んだ記入欄
+const CONFIDENCE_ANCHOR_NOT_FOUND: f64 = 0.5;
+/// 1文字以下の値は誤認識の可能性が高いため下げる
+const SHORT_VALUE_FACTOR: f64 = 0.6;
+
+// ============================================
+// 抽出結果
+// ============================================
+
+/// 抽出した項目
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExtractedField {
+    /// extracted_fields のキー
+    pub key: String,
+    pub label: String,
+    pub value: String,
+    /// 信頼度（0.0〜1.0）
+    pub confidence: f64,
+    /// 値のトークンの添字
+    pub tokens: Vec<usize>,
+}
+
+/// 1書類分の抽出結果
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Extraction {
+    pub fields: Vec<ExtractedField>,
+    /// 値が見つからなかった項目（表示名）
+    pub missing: Vec<String>,
+}
+
+impl Extraction {
+    /// AIチェックと同じ形式の extracted_fields
+    pub fn extracted_fields(&self) -> HashMap<String, String> {
+        self.fields.iter().map(|f| (f.key.clone(), f.value.clone())).collect()
+    }
+
+    /// 項目ごとの信頼度
+    pub fn confidence(&self) -> HashMap<String, f64> {
+        self.fields.iter().map(|f| (f.key.clone(), f.confidence)).collect()
+    }
+
+    /// AIチェック結果と同じ形の JSON（extracted_fields と confidence）
+    pub fn to_json(&self) -> String {
+        let value = serde_json::json!({
+            "extracted_fields": self.extracted_fields(),
+            "confidence": self.confidence(),
+        });
+        serde_json::to_string_pretty(&value).unwrap_or_default()
+    }
+}
+
+// ============================================
+// 行の文字列操作
+// ============================================
+
+/// 行の文字（検索用に正規化）と、文字ごとのトークンの添字
+fn line_chars(line: &OcrLine, tokens: &[OcrToken]) -> Vec<(char, usize)> {
+    line.tokens()
+        .flat_map(|i| normalize_chars(&tokens[i].text).into_iter().map(move |c| (c, i)))
+        .collect()
+}
+
+/// 行内で目印を探す（一致した文字の範囲）
+fn find_in_chars(chars: &[(char, usize)], anchor: &str) -> Option<(usize, usize)> {
+    let needle = normalize_chars(anchor);
+    if needle.is_empty() || needle.len() > chars.len() {
+        return None;
+    }
+    (0..=chars.len() - needle.len())
+        .find(|&start| chars[start..start + needle.len()].iter().map(|(c, _)| *c).eq(needle.iter().copied()))
+        .map(|start| (start, start + needle.len()))
+}
+
+/// 行のうち条件に合うトークンの文字列（語の間は空白）とトークン
+fn line_text(line: &OcrLine, tokens: &[OcrToken], keep: impl Fn(usize) -> bool) -> (String, Vec<usize>) {
+    let mut words = Vec::new();
+    let mut kept = Vec::new();
+    for word in &line.words {
+        let text: String = word.tokens.iter().filter(|&&i| keep(i)).map(|&i| tokens[i].text.trim()).collect();
+        kept.extend(word.tokens.iter().copied().filter(|&i| keep(i)));
+        if !text.is_empty() {
+            words.push(text);
+        }
+    }
+    (words.join(" "), kept)
+}
+
+/// 値の前後の区切り記号を除く（「住所：」「(所在地)」の残り）
+fn trim_value(text: &str) -> String {
+    text.trim_matches(|c: char| c.is_whitespace() || ":：)）]】|｜・".contains(c)).to_string()
+}
+
+/// 行にいずれかの文字列を含むか
+fn line_contains(line: &OcrLine, tokens: &[OcrToken], marks: &[&str]) -> bool {
+    let chars = line_chars(line, tokens);
+    marks.iter().any(|m| find_in_chars(&chars, m).is_some())
+}
+
+fn apply_short_value_factor(value: &str, confidence: f64) -> f64 {
+    if value.chars().count() <= 1 { confidence * SHORT_VALUE_FACTOR } else { confidence }
+}
+
+// ============================================
+// 組み込みルールの適用
+// ============================================
+
+/// ルールで見つけた値（値・トークンの添字・信頼度）
+type Found = (String, Vec<usize>, f64);
+
+/// 目印の右側の値（continue_lines なら同じブロックの続く行のうち、値の列より右の文字も含める）
+fn right_of(
+    layout: &OcrLayout,
+    tokens: &[OcrToken],
+    anchors: &[&str],
+    continue_lines: bool,
+    other_anchors: &[&str],
+) -> Option<Found> {
+    for block in &layout.blocks {
+        for (li, line) in block.lines.iter().enumerate() {
+            let chars = line_chars(line, tokens);
+            let Some((_, end)) = anchors.iter().find_map(|a| find_in_chars(&chars, a)) else {
+                continue;
+            };
+            // 目印の最後の文字を含むトークンより後ろ
+            let anchor_last = chars[end - 1].1;
+            let order: Vec<usize> = line.tokens().collect();
+            let after = order.iter().position(|&i| i == anchor_last).map(|p| p + 1).unwrap_or(order.len());
+            let value_tokens: Vec<usize> = order[after..].to_vec();
+            let (text, mut used) = line_text(line, tokens, |i| value_tokens.contains(&i));
+            let mut parts = vec![trim_value(&text)];
+            let mut confidence = CONFIDENCE_SAME_LINE;
+
+            if continue_lines {
+                // 値の列（目印の右端）より右の文字を続きの行から拾う
+                let anchor_token = &tokens[anchor_last];
+                let column = anchor_token.normalized.x + anchor_token.normalized.width * 0.5;
+                for next in block.lines.iter().skip(li + 1).take(3) {
+                    if line_contains(next, tokens, other_anchors)
+                        || line_contains(next, tokens, COMPANY_MARKS)
+                        || line_contains(next, tokens, RECIPIENT_MARKS)
+                    {
+                        break;
+                    }
+                    let (next_text, next_used) = line_text(next, tokens, |i| tokens[i].normalized.x >= column);
+                    let next_text = trim_value(&next_text);
+                    if next_text.is_empty() {
+                        break;
+                    }
+                    parts.push(next_text);
+                    used.extend(next_used);
+                    confidence = CONFIDENCE_CONTINUED;
+                }
+            }
+
+            let value = parts.into_iter().filter(|p| !p.is_empty()).collect::<Vec<_>>().join(" ");
+            // 目印はあるが記入なし
+            return (!value.is_empty()).then_some((value, used, confidence));
+        }
+    }
+    None
+}
+
+/// 目印の左側の値（宛先）
+fn left_of(layout: &OcrLayout, tokens: &[OcrToken], anchors: &[&str]) -> Option<Found> {
+    let line = layout.lines().find(|l| line_contains(l, tokens, anchors))?;
+    let chars = line_chars(line, tokens);
+    let (start, _) = anchors.iter().find_map(|a| find_in_chars(&chars, a))?;
+    let before: Vec<usize> = chars[..start].iter().map(|(_, i)| *i).collect();
+    let first_anchor = chars[start].1;
+    let (text, used) = line_text(line, tokens, |i| before.contains(&i) && i != first_anchor);
+    let value = trim_value(&text);
+    (!value.is_empty()).then_some((value, used, CONFIDENCE_SAME_LINE))
+}
+
+/// 法人格を含む最初の行（宛先の行は除く）
+fn company_line(layout: &OcrLayout, tokens: &[OcrToken]) -> Option<Found> {
+    let line = layout
+        .lines()
+        .find(|l| line_contains(l, tokens, COMPANY_MARKS) && !line_contains(l, tokens, RECIPIENT_MARKS))?;
+    let (text, used) = line_text(line, tokens, |_| true);
+    let value = trim_value(&text);
+    (!value.is_empty()).then_some((value, used, CONFIDENCE_COMPANY_LINE))
+}
+
+/// 日付の行（空欄の日付は None）
+fn date_line(layout: &OcrLayout, tokens: &[OcrToken]) -> Option<Found> {
+    for line in layout.lines() {
+        let chars = line_chars(line, tokens);
+        let text: Vec<char> = chars.iter().map(|(c, _)| *c).collect();
+        let Some(&(start, end)) = find_date_spans(&text).first() else {
+            continue;
+        };
+        let text: String = chars[start..end].iter().map(|(c, _)| *c).collect();
+        let mut used: Vec<usize> = chars[start..end].iter().map(|(_, i)| *i).collect();
+        used.dedup();
+        // 日付欄はあるが空欄（「令和 年 月 日」）
+        return normalize_date(&text).map(|date| (date, used, CONFIDENCE_DATE));
+    }
+    None
+}
+
+// ============================================
+// テンプレートの記入欄
+// ============================================
+
+/// 文字列に一致するトークン群の位置（最初の一致）
+pub fn find_anchor(layout: &OcrLayout, tokens: &[OcrToken], anchor: &str, page: u32) -> Option<FieldPosition> {
+    for line in layout.lines().filter(|l| l.page == page) {
+        let chars = line_chars(line, tokens);
+        if let Some((start, end)) = find_in_chars(&chars, anchor) {
+            return union_position(chars[start..end].iter().map(|(_, i)| &tokens[*i]));
+        }
+    }
+    None
+}
+
+/// トークンを囲む矩形（ページ内の比率）
+pub fn union_position<'a>(tokens: impl Iterator<Item = &'a OcrToken>) -> Option<FieldPosition> {
+    let (left, top, right, bottom) = tokens
+        .map(|t| {
+            let n = &t.normalized;
+            (n.x, n.y, n.x + n.width, n.y + n.height)
+        })
+        .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))?;
+    Some(FieldPosition { x: left, y: top, width: right - left, height: bottom - top })
+}
+
+/// 書類上の記入欄の領域（目印があれば位置のずれを補正）と、その信頼度
+pub fn field_region(layout: &OcrLayout, tokens: &[OcrToken], field: &TemplateField) -> (FieldPosition, f64) {
+    let mut region = field.position.clone();
+    let mut confidence = CONFIDENCE_REGION;
+    if let (Some(anchor), Some(original)) = (field.anchor.as_deref().filter(|a| !a.trim().is_empty()), &field.anchor_position) {
+        match find_anchor(layout, tokens, anchor, field.page) {
+            Some(found) => {
+                region.x += found.x - original.x;
+                region.y += found.y - original.y;
+                confidence = CONFIDENCE_ANCHORED_REGION;
+            }
+            None => confidence = CONFIDENCE_ANCHOR_NOT_FOUND,
+        }
+    }
+    (region, confidence)
+}
+
+/// 領域内（中心が入るもの）のトークンの文字列（行の間は空白）とトークン
+pub fn region_text(layout: &OcrLayout, tokens: &[OcrToken], page: u32, region: &FieldPosition) -> (String, Vec<usize>) {
+    let inside = |i: usize| {
+        let t = &tokens[i];
+        let cx = t.normalized.x + t.normalized.width / 2.0;
+        let cy = t.normalized.y + t.normalized.height / 2.0;
+        t.page == page
+            && cx >= region.x && cx <= region.x + region.width
+            && cy >= region.y && cy <= region.y + region.height
+    };
+    let mut lines = Vec::new();
+    let mut used = Vec::new();
+    for line in layout.lines().filter(|l| l.page == page) {
+        let (text, line_used) = line_text(line, tokens, inside);
+        if !text.is_empty() {
+            lines.push(text);
+            used.extend(line_used);
+        }
+    }
+    (lines.join(" "), used)
+}
+
+/// 記入欄の領域内のトークンを読む
+fn read_region(layout: &OcrLayout, tokens: &[OcrToken], field: &TemplateField) -> Option<Found> {
+    let (region, confidence) = field_region(layout, tokens, field);
+    let (text, used) = region_text(layout, tokens, field.page, &region);
+    let value = trim_value(&text);
+    (!value.is_empty()).then_some((value, used, confidence))
+}
+
+// ============================================
+// 抽出
+// ============================================
+
+/// 書類種別の組み込みルールと帳票テンプレートで項目を抽出
+pub fn extract_fields(doc: &OcrDocument, layout: &OcrLayout, templates: &[FormTemplate]) -> Extraction {
+    let tokens = &doc.tokens;
+    let template_fields: &[TemplateField] = FormTemplate::find(templates, &doc.doc_type).map(|t| t.fields.as_slice()).unwrap_or(&[]);
+    let builtins = builtin_fields(&doc.doc_type);
+    let mut extraction = Extraction::default();
+    let mut push = |key: &str, label: &str, found: Option<Found>| match found {
+        Some((value, tokens, confidence)) => extraction.fields.push(ExtractedField {
+            key: key.to_string(),
+            label: label.to_string(),
+            confidence: apply_short_value_factor(&value, confidence),
+            value,
+            tokens,
+        }),
+        None => extraction.missing.push(label.to_string()),
+    };
+
+    for field in builtins {
+        // 同じキーの記入欄がテンプレートにあればそちらを使う
+        if template_fields.iter().any(|f| f.extract_key() == field.key) {
+            continue;
+        }
+        // 続く行の打ち切りに使う、他の項目の目印
+        let other_anchors: Vec<&str> = builtins
+            .iter()
+            .filter(|b| b.key != field.key)
+            .flat_map(|b| b.rules.iter())
+            .flat_map(|r| match r {
+                BuiltinRule::RightOf(anchors, _) => anchors.to_vec(),
+                _ => Vec::new(),
+            })
+            .collect();
+        let found = field.rules.iter().find_map(|rule| match rule {
+            BuiltinRule::RightOf(anchors, continue_lines) => right_of(layout, tokens, anchors, *continue_lines, &other_anchors),
+            BuiltinRule::LeftOf(anchors) => left_of(layout, tokens, anchors),
+            BuiltinRule::CompanyLine => company_line(layout, tokens),
+            BuiltinRule::DateLine => date_line(layout, tokens),
+        });
+        push(field.key, field.label, found);
+    }
+
+    for field in template_fields {
+        let found = read_region(layout, tokens, field);
+        push(field.extract_key(), &field.name, found);
+    }
+
+    extraction
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::ocr_layout::group_tokens;
+
+    /// サンプルのOCR結果（下請A・下請Bの暴対法誓約書、下請Bの作業員名簿）
+    fn sample_documents() -> Vec<OcrDocument> {
+        serde_json::from_str(include_str!("../../data/ocr_documents.json")).unwrap()
+    }
+
+    fn extract(contractor: &str, doc_type: &str) -> Extraction {
+        let doc = sample_documents().into_iter().find(|d| d.contractor == contractor && d.doc_type == doc_type).unwrap();
+        extract_fields(&doc, &group_tokens(&doc.tokens), &[])
+    }
+
+    fn fields(extraction: &Extraction) -> Vec<(&str, &str, f64)> {
+        extraction.fields.iter().map(|f| (f.key.as_str(), f.value.as_str(), f.confidence)).collect()
+    }
+
+    #[test]
+    fn extracts_labelled_pledge_fields() {
+        let extraction = extract("下請A", "09_暴対法誓約書");
+        assert_eq!(
+            fields(&extraction),
+            [
+                ("address", "〒861-2244 熊本県上益城郡益城町寺迫 1209-4", CONFIDENCE_CONTINUED),
+                ("company_name", "下請A 株式会社 熊本工事営業所", CONFIDENCE_CONTINUED),
+                ("representative_name", "所長 朝岡 恭司", CONFIDENCE_SAME_LINE),
+            ]
+        );
+        // 日付（「令和 年 月 日」のまま）と宛先（「御中」だけ）は記入されていない
+        assert_eq!(extraction.missing, ["誓約日", "宛先"]);
+    }
+
+    #[test]
+    fn company_without_label_comes_from_company_line() {
+        let extraction = extract("下請B", "09_暴対法誓約書");
+        assert_eq!(
+            fields(&extraction),
+            [
+                ("recipient", "有限会社元請建設", CONFIDENCE_SAME_LINE),
+                ("address", "熊本市桜木1丁目13番9号", CONFIDENCE_SAME_LINE),
+                // 「法人名」の目印がないため法人格を含む行から（信頼度は低め）
+                ("company_name", "株式会社 アイエスティー", CONFIDENCE_COMPANY_LINE),
+                ("representative_name", "代表取締役 高野 太", CONFIDENCE_SAME_LINE),
+            ]
+        );
+        assert_eq!(extraction.missing, ["誓約日"]);
+        assert_eq!(extraction.extracted_fields().get("recipient").map(String::as_str), Some("有限会社元請建設"));
+        assert_eq!(extraction.confidence().get("company_name"), Some(&CONFIDENCE_COMPANY_LINE));
+    }
+
+    #[test]
+    fn documents_without_rules_extract_nothing() {
+        assert!(!has_extraction_rules("08_作業員名簿", &[]));
+        assert_eq!(extract("下請B", "08_作業員名簿"), Extraction::default());
+    }
+}
@@ -0,0 +1,584 @@
+//! OCR結果の読み込み（Document AI JSON / hOCR / ALTO XML → OcrDocument）
+//!
+//! 形式ごとに単語（トークン）の矩形をピクセル単位で取り出し、ページサイズで割って normalized を求める。
+//! pixels・page_size はすべてピクセル単位に揃える。
+//! アプリ独自の形式（ocr_documents.json の書類の配列、トークンの配列）もそのまま読み込む
+//!
+//! | 形式 | 単語 | ページサイズ |
+//! |------|------|--------------|
+//! | Document AI | pages[].tokens（textAnchor で document.text を参照） | pages[].dimension |
+//! | hOCR（Tesseract など） | .ocrx_word の title の bbox | .ocr_page の bbox |
+//! | ALTO XML | String の HPOS/VPOS/WIDTH/HEIGHT | Page の WIDTH/HEIGHT |
+
+use serde_json::Value;
+use web_sys::{Document, DomParser, Element, SupportedType};
+
+use crate::models::{NormalizedCoords, OcrDocument, OcrToken, PageSize, PixelCoords};
+
+/// ALTOの長さの単位がピクセル以外（mm10 / inch1200）の場合に換算に使う解像度
+const ALTO_ASSUMED_DPI: f64 = 300.0;
+
+// ============================================
+// 形式の判定
+// ============================================
+
+/// 読み込めるOCR結果の形式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OcrFormat {
+    /// アプリ独自の書類の配列（ocr_documents.json）
+    Documents,
+    /// アプリ独自のトークンの配列（debug_tokens_*.json）
+    Tokens,
+    DocumentAi,
+    Hocr,
+    Alto,
+}
+
+impl OcrFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            OcrFormat::Documents => "OCR書類JSON",
+            OcrFormat::Tokens => "OCRトークンJSON",
+            OcrFormat::DocumentAi => "Document AI JSON",
+            OcrFormat::Hocr => "hOCR",
+            OcrFormat::Alto => "ALTO XML",
+        }
+    }
+
+    /// ファイルの内容（判定できない場合は拡張子）から形式を判定
+    pub fn detect(filename: &str, text: &str) -> Option<Self> {
+        let head = text.trim_start_matches('\u{feff}').trim_start();
+        if head.starts_with('[') {
+            let json: Value = serde_json::from_str(head).ok()?;
+            let first = json.as_array()?.first()?;
+            return Some(if first.get("tokens").is_some() { OcrFormat::Documents } else { OcrFormat::Tokens });
+        }
+        if head.starts_with('{') {
+            let json: Value = serde_json::from_str(head).ok()?;
+            let doc = json.get("document").unwrap_or(&json);
+            return doc.get("pages").is_some().then_some(OcrFormat::DocumentAi);
+        }
+        // 先頭付近のタグで判定（XML宣言・DOCTYPEの後）
+        let prefix: String = head.chars().take(4000).collect();
+        if prefix.contains("<alto") || prefix.contains(":alto") {
+            return Some(OcrFormat::Alto);
+        }
+        if prefix.contains("ocr_page") || prefix.contains("ocr-system") {
+            return Some(OcrFormat::Hocr);
+        }
+        let lower = filename.to_lowercase();
+        if lower.ends_with(".hocr") {
+            Some(OcrFormat::Hocr)
+        } else if lower.ends_with(".xml") && head.contains("<String") {
+            Some(OcrFormat::Alto)
+        } else {
+            None
+        }
+    }
+}
+
+// ============================================
+// 読み込み
+// ============================================
+
+/// ファイル名から業者名と書類種別を推定
+///
+/// 「下請A_09_暴対法誓約書.json」のように業者名の後に書類番号が続く名前は分割する。
+/// それ以外はファイル名を業者名、書類種別を「OCR読込」とする
+pub fn names_from_filename(filename: &str) -> (String, String) {
+    let stem = filename.rsplit_once('.').map(|(s, _)| s).unwrap_or(filename);
+    let stem = stem.strip_prefix("debug_tokens_").unwrap_or(stem);
+    if let Some((contractor, doc_type)) = stem.split_once('_') {
+        if !contractor.is_empty() && doc_type.starts_with(|c: char| c.is_ascii_digit()) {
+            return (contractor.to_string(), doc_type.to_string());
+        }
+    }
+    (stem.to_string(), "OCR読込".to_string())
+}
+
+/// OCR結果のファイルを OcrDocument に変換（形式は自動判定）
+pub fn import_ocr(filename: &str, text: &str) -> Result<Vec<OcrDocument>, String> {
+    let format = OcrFormat::detect(filename, text)
+        .ok_or_else(|| format!("OCR結果の形式を判定できません: {}", filename))?;
+    let (contractor, doc_type) = names_from_filename(filename);
+    let with_names = |tokens: Vec<OcrToken>, page_images: Vec<String>| OcrDocument {
+        contractor: contractor.clone(),
+        doc_type: doc_type.clone(),
+        image_url: page_images.first().cloned().unwrap_or_default(),
+        tokens,
+        page_images,
+    };
+
+    let docs = match format {
+        OcrFormat::Documents => serde_json::from_str::<Vec<OcrDocument>>(text)
+            .map_err(|e| format!("{}の解析に失敗しました: {}", format.label(), e))?,
+        OcrFormat::Tokens => {
+            let tokens = serde_json::from_str::<Vec<OcrToken>>(text)
+                .map_err(|e| format!("{}の解析に失敗しました: {}", format.label(), e))?;
+            vec![with_names(tokens, Vec::new())]
+        }
+        OcrFormat::DocumentAi => {
+            let json: Value = serde_json::from_str(text)
+                .map_err(|e| format!("{}の解析に失敗しました: {}", format.label(), e))?;
+            let (tokens, page_images) = parse_document_ai(&json)?;
+            vec![with_names(tokens, page_images)]
+        }
+        OcrFormat::Hocr => vec![with_names(parse_hocr(text)?, Vec::new())],
+        OcrFormat::Alto => vec![with_names(parse_alto(text)?, Vec::new())],
+    };
+
+    if docs.iter().all(|d| d.tokens.is_empty()) {
+        return Err(format!("{}に文字が含まれていません: {}", format.label(), filename));
+    }
+    Ok(docs)
+}
+
+/// ピクセル単位の矩形（左・上・右・下）からトークンを作る
+fn make_token(text: &str, page: u32, rect: (f64, f64, f64, f64), page_size: (f64, f64)) -> OcrToken {
+    let (left, top, right, bottom) = rect;
+    let (page_width, page_height) = page_size;
+    OcrToken {
+        text: text.to_string(),
+        page,
+        normalized: NormalizedCoords {
+            x: left / page_width,
+            y: top / page_height,
+            width: (right - left) / page_width,
+            height: (bottom - top) / page_height,
+        },
+        pixels: PixelCoords {
+            x: left.round() as i32,
+            y: top.round() as i32,
+            width: (right - left).round() as i32,
+            height: (bottom - top).round() as i32,
+        },
+        page_size: PageSize { width: page_width, height: page_height },
+    }
+}
+
+// ============================================
+// Document AI
+// ============================================
+
+/// 数値または文字列の数値（int64 は文字列で返る）
+fn json_number(value: &Value) -> Option<f64> {
+    value.as_f64().or_else(|| value.as_str()?.parse().ok())
+}
+
+/// textAnchor が指す document.text の文字列（インデックスは文字単位）
+fn anchor_text(text: &[char], anchor: &Value) -> String {
+    let Some(segments) = anchor.get("textSegments").and_then(Value::as_array) else {
+        return String::new();
+    };
+    segments
+        .iter()
+        .flat_map(|segment| {
+            // 0 の startIndex は省略される
+            let start = segment.get("startIndex").and_then(json_number).unwrap_or(0.0) as usize;
+            let end = segment.get("endIndex").and_then(json_number).unwrap_or(0.0) as usize;
+            text.get(start.min(text.len())..end.min(text.len())).unwrap_or_default().iter().copied()
+        })
+        .collect()
+}
+
+/// boundingPoly の頂点の外接矩形（ピクセル単位。normalizedVertices を優先）
+fn bounding_rect(poly: &Value, page_size: (f64, f64)) -> Option<(f64, f64, f64, f64)> {
+    let (vertices, scale) = match poly.get("normalizedVertices").and_then(Value::as_array).filter(|v| !v.is_empty()) {
+        Some(v) => (v, page_size),
+        None => (poly.get("vertices").and_then(Value::as_array)?, (1.0, 1.0)),
+    };
+    vertices
+        .iter()
+        .map(|v| {
+            // 0 の座標は省略される
+            let x = v.get("x").and_then(Value::as_f64).unwrap_or(0.0) * scale.0;
+            let y = v.get("y").and_then(Value::as_f64).unwrap_or(0.0) * scale.1;
+            (x, y, x, y)
+        })
+        .reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3)))
+}
+
+/// Document AI のレスポンス（process の戻り値、または document 単体）
+fn parse_document_ai(json: &Value) -> Result<(Vec<OcrToken>, Vec<String>), String> {
+    let doc = json.get("document").unwrap_or(json);
+    let text: Vec<char> = doc.get("text").and_then(Value::as_str).unwrap_or_default().chars().collect();
+    let pages = doc.get("pages").and_then(Value::as_array).ok_or("Document AIの結果にpagesがありません")?;
+
+    let mut tokens = Vec::new();
+    let mut page_images = Vec::new();
+    for (i, page) in pages.iter().enumerate() {
+        let number = page.get("pageNumber").and_then(json_number).map(|n| n as u32).unwrap_or(i as u32 + 1);
+        let image = page.get("image");
+        let dimension = |key: &str| {
+            page.get("dimension")
+                .and_then(|d| d.get(key))
+                .and_then(json_number)
+                .or_else(|| image.and_then(|img| img.get(key)).and_then(json_number))
+                .filter(|v| *v > 0.0)
+        };
+        let (Some(width), Some(height)) = (dimension("width"), dimension("height")) else {
+            return Err(format!("Document AIの結果に{}ページ目のサイズ（dimension）がありません", number));
+        };
+
+        // ページ画像（レスポンスに含まれる場合のみ）
+        let image_url = image
+            .and_then(|img| {
+                let content = img.get("content").and_then(Value::as_str)?;
+                let mime = img.get("mimeType").and_then(Value::as_str).unwrap_or("image/png");
+                Some(format!("data:{};base64,{}", mime, content))
+            })
+            .unwrap_or_default();
+        page_images.push(image_url);
+
+        for token in page.get("tokens").and_then(Value::as_array).into_iter().flatten() {
+            let Some(layout) = token.get("layout") else {
+                continue;
+            };
+            let token_text = layout.get("textAnchor").map(|a| anchor_text(&text, a)).unwrap_or_default();
+            let token_text = token_text.trim();
+            if token_text.is_empty() {
+                continue;
+            }
+            if let Some(rect) = layout.get("boundingPoly").and_then(|p| bounding_rect(p, (width, height))) {
+                tokens.push(make_token(token_text, number, rect, (width, height)));
+            }
+        }
+    }
+
+    if page_images.iter().all(String::is_empty) {
+        page_images.clear();
+    }
+    Ok((tokens, page_images))
+}
+
+// ============================================
+// hOCR / ALTO（ブラウザのDOMParserで解析）
+// ============================================
+
+/// マークアップから取り出したページ（座標はピクセル単位に換算済み）
+struct MarkupPage {
+    /// 記載されたページ番号（1始まり、なければ出現順）
+    number: Option<u32>,
+    /// ページサイズ（幅・高さ）
+    size: Option<(f64, f64)>,
+    /// 単語と矩形（左・上・右・下）
+    words: Vec<(String, (f64, f64, f64, f64))>,
+}
+
+/// ページごとの単語をトークンに変換（missing_size はページサイズがない場合のエラー文）
+fn markup_tokens(pages: Vec<MarkupPage>, missing_size: impl Fn(u32) -> String) -> Result<Vec<OcrToken>, String> {
+    let mut tokens = Vec::new();
+    for (i, page) in pages.into_iter().enumerate() {
+        let number = page.number.unwrap_or(i as u32 + 1);
+        let Some(size) = page.size.filter(|(w, h)| *w > 0.0 && *h > 0.0) else {
+            return Err(missing_size(number));
+        };
+        for (text, rect) in page.words {
+            let text = text.trim();
+            if !text.is_empty() {
+                tokens.push(make_token(text, number, rect, size));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn parse_markup(text: &str, kind: SupportedType) -> Result<Document, String> {
+    let parser = DomParser::new().map_err(|_| "DOMParserを作成できません".to_string())?;
+    let doc = parser
+        .parse_from_string(text, kind)
+        .map_err(|e| format!("OCR結果の解析に失敗しました: {:?}", e))?;
+    if doc.get_elements_by_tag_name("parsererror").length() > 0 {
+        return Err("OCR結果のXMLが不正です".to_string());
+    }
+    Ok(doc)
+}
+
+fn elements(collection: web_sys::HtmlCollection) -> impl Iterator<Item = Element> {
+    (0..collection.length()).filter_map(move |i| collection.item(i))
+}
+
+/// hOCR の title 属性から項目を取り出す（例: "bbox 10 20 30 40; x_wconf 95" の "bbox"）
+fn title_property<'a>(title: &'a str, name: &str) -> Option<&'a str> {
+    title.split(';').map(str::trim).find_map(|prop| {
+        let (key, value) = prop.split_once(char::is_whitespace)?;
+        (key == name).then_some(value.trim())
+    })
+}
+
+/// hOCR の title の bbox（左・上・右・下）
+fn hocr_bbox(title: &str) -> Option<(f64, f64, f64, f64)> {
+    let values: Vec<f64> = title_property(title, "bbox")?.split_whitespace().filter_map(|v| v.parse().ok()).collect();
+    match values.as_slice() {
+        [left, top, right, bottom] => Some((*left, *top, *right, *bottom)),
+        _ => None,
+    }
+}
+
+/// hOCR の .ocr_page の title からページを作る（ppageno は0始まり、bbox の大きさがページサイズ）
+fn hocr_page(title: &str, words: Vec<(String, (f64, f64, f64, f64))>) -> MarkupPage {
+    MarkupPage {
+        number: title_property(title, "ppageno").and_then(|n| n.parse::<u32>().ok()).map(|n| n + 1),
+        size: hocr_bbox(title).map(|(left, top, right, bottom)| (right - left, bottom - top)),
+        words,
+    }
+}
+
+/// hOCR（.ocr_page ごとに .ocrx_word を読む）
+fn parse_hocr(text: &str) -> Result<Vec<OcrToken>, String> {
+    let doc = parse_markup(text, SupportedType::TextHtml)?;
+    let pages = elements(doc.get_elements_by_class_name("ocr_page"))
+        .map(|page| {
+            let words = elements(page.get_elements_by_class_name("ocrx_word"))
+                .filter_map(|word| {
+                    let rect = hocr_bbox(&word.get_attribute("title")?)?;
+                    Some((word.text_content().unwrap_or_default(), rect))
+                })
+                .collect();
+            hocr_page(&page.get_attribute("title").unwrap_or_default(), words)
+        })
+        .collect();
+    markup_tokens(pages, |number| format!("hOCRの{}ページ目にbboxがありません", number))
+}
+
+/// 名前空間を問わない要素の一覧（getElementsByTagNameNS("*", …) の結果）
+fn elements_ns(collection: Result<web_sys::HtmlCollection, wasm_bindgen::JsValue>) -> Result<impl Iterator<Item = Element>, String> {
+    collection.map(elements).map_err(|e| format!("ALTOの要素を取得できません: {:?}", e))
+}
+
+fn attribute_f64(element: &Element, name: &str) -> Option<f64> {
+    element.get_attribute(name)?.trim().parse().ok()
+}
+
+/// ALTO の MeasurementUnit からピクセルへの倍率
+fn alto_scale(unit: &str) -> f64 {
+    match unit.trim() {
+        "mm10" => ALTO_ASSUMED_DPI / 254.0,
+        "inch1200" => ALTO_ASSUMED_DPI / 1200.0,
+        _ => 1.0,
+    }
+}
+
+/// ALTO の HPOS・VPOS・WIDTH・HEIGHT を矩形（左・上・右・下、ピクセル）に
+fn alto_rect([x, y, w, h]: [f64; 4], scale: f64) -> (f64, f64, f64, f64) {
+    (x * scale, y * scale, (x + w) * scale, (y + h) * scale)
+}
+
+/// ALTO XML（Page ごとに String を読む。名前空間の有無・版は問わない）
+fn parse_alto(text: &str) -> Result<Vec<OcrToken>, String> {
+    let doc = parse_markup(text, SupportedType::ApplicationXml)?;
+    let unit = elements_ns(doc.get_elements_by_tag_name_ns(Some("*"), "MeasurementUnit"))?
+        .next()
+        .and_then(|e| e.text_content())
+        .unwrap_or_default();
+    let scale = alto_scale(&unit);
+
+    let mut pages = Vec::new();
+    for page in elements_ns(doc.get_elements_by_tag_name_ns(Some("*"), "Page"))? {
+        let words = elements_ns(page.get_elements_by_tag_name_ns(Some("*"), "String"))?
+            .filter_map(|string| {
+                let [Some(x), Some(y), Some(w), Some(h)] = ["HPOS", "VPOS", "WIDTH", "HEIGHT"].map(|name| attribute_f64(&string, name)) else {
+                    return None;
+                };
+                Some((string.get_attribute("CONTENT").unwrap_or_default(), alto_rect([x, y, w, h], scale)))
+            })
+            .collect();
+        pages.push(MarkupPage {
+            number: attribute_f64(&page, "PHYSICAL_IMG_NR").map(|n| n as u32).filter(|n| *n > 0),
+            size: attribute_f64(&page, "WIDTH").zip(attribute_f64(&page, "HEIGHT")).map(|(w, h)| (w * scale, h * scale)),
+            words,
+        });
+    }
+    markup_tokens(pages, |number| format!("ALTOの{}ページ目にWIDTH・HEIGHTがありません", number))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{} != {}", actual, expected);
+    }
+
+    /// 正規化座標（x, y, 幅, 高さ）を比較
+    fn assert_normalized(token: &OcrToken, expected: (f64, f64, f64, f64)) {
+        let n = &token.normalized;
+        assert_close(n.x, expected.0);
+        assert_close(n.y, expected.1);
+        assert_close(n.width, expected.2);
+        assert_close(n.height, expected.3);
+    }
+
+    fn word(text: &str, rect: (f64, f64, f64, f64)) -> (String, (f64, f64, f64, f64)) {
+        (text.to_string(), rect)
+    }
+
+    // ---- Document AI ----
+
+    #[test]
+    fn document_ai_page_numbers_default_to_position() {
+        let json = json!({
+            "document": {
+                "text": "建設業許可\n",
+                "pages": [
+                    {
+                        "dimension": { "width": 1000, "height": 2000 },
+                        "tokens": [{ "layout": {
+                            "textAnchor": { "textSegments": [{ "endIndex": "3" }] },
+                            "boundingPoly": { "normalizedVertices": [{ "x": 0.1, "y": 0.2 }, { "x": 0.3, "y": 0.2 }, { "x": 0.3, "y": 0.25 }, { "x": 0.1, "y": 0.25 }] }
+                        }}]
+                    },
+                    {
+                        "pageNumber": 5,
+                        "dimension": { "width": 1000, "height": 2000 },
+                        "tokens": [{ "layout": {
+                            "textAnchor": { "textSegments": [{ "startIndex": "3", "endIndex": "5" }] },
+                            "boundingPoly": { "normalizedVertices": [{ "x": 0.5, "y": 0.5 }, { "x": 0.6, "y": 0.55 }] }
+                        }}]
+                    }
+                ]
+            }
+        });
+        let (tokens, page_images) = parse_document_ai(&json).unwrap();
+        assert_eq!(tokens.iter().map(|t| (t.text.as_str(), t.page)).collect::<Vec<_>>(), [("建設業", 1), ("許可", 5)]);
+        assert!(page_images.is_empty());
+    }
+
+    #[test]
+    fn document_ai_normalizes_vertices_by_dimension() {
+        // normalizedVertices はそのまま、vertices はページサイズで割る（0 の座標は省略される）
+        let json = json!({
+            "text": "AB",
+            "pages": [{
+                "dimension": { "width": 800, "height": 400 },
+                "tokens": [
+                    { "layout": {
+                        "textAnchor": { "textSegments": [{ "endIndex": "1" }] },
+                        "boundingPoly": { "normalizedVertices": [{ "y": 0.5 }, { "x": 0.25, "y": 0.75 }] }
+                    }},
+                    { "layout": {
+                        "textAnchor": { "textSegments": [{ "startIndex": "1", "endIndex": "2" }] },
+                        "boundingPoly": { "vertices": [{ "x": 200, "y": 100 }, { "x": 600, "y": 100 }, { "x": 600, "y": 300 }, { "x": 200, "y": 300 }] }
+                    }}
+                ]
+            }]
+        });
+        let (tokens, _) = parse_document_ai(&json).unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert_normalized(&tokens[0], (0.0, 0.5, 0.25, 0.25));
+        assert_eq!((tokens[0].pixels.x, tokens[0].pixels.y, tokens[0].pixels.width, tokens[0].pixels.height), (0, 200, 200, 100));
+        assert_normalized(&tokens[1], (0.25, 0.25, 0.5, 0.5));
+        assert_close(tokens[1].page_size.width, 800.0);
+        assert_close(tokens[1].page_size.height, 400.0);
+    }
+
+    #[test]
+    fn document_ai_requires_page_dimension() {
+        let json = json!({ "pages": [{ "pageNumber": 2, "tokens": [] }] });
+        let err = parse_document_ai(&json).unwrap_err();
+        assert!(err.contains("2ページ目"), "{}", err);
+    }
+
+    #[test]
+    fn import_detects_document_ai_and_names_from_filename() {
+        let text = json!({
+            "text": "印",
+            "pages": [{
+                "image": { "width": 100, "height": 100, "content": "AAAA", "mimeType": "image/jpeg" },
+                "tokens": [{ "layout": {
+                    "textAnchor": { "textSegments": [{ "endIndex": "1" }] },
+                    "boundingPoly": { "normalizedVertices": [{ "x": 0.1, "y": 0.1 }, { "x": 0.2, "y": 0.2 }] }
+                }}]
+            }]
+        })
+        .to_string();
+        assert_eq!(OcrFormat::detect("result.json", &text), Some(OcrFormat::DocumentAi));
+        let docs = import_ocr("result.json", &text).unwrap();
+        assert_eq!(docs.len(), 1);
+        assert_eq!(docs[0].tokens[0].page, 1);
+        assert_eq!(docs[0].image_url, "data:image/jpeg;base64,AAAA");
+    }
+
+    // ---- hOCR ----
+
+    #[test]
+    fn hocr_title_properties() {
+        let title = "image \"scan.png\"; bbox 0 0 2480 3508; ppageno 2";
+        assert_eq!(title_property(title, "ppageno"), Some("2"));
+        assert_eq!(title_property(title, "bbox"), Some("0 0 2480 3508"));
+        assert_eq!(title_property(title, "x_wconf"), None);
+        assert_eq!(hocr_bbox("bbox 10 20 110 70; x_wconf 95"), Some((10.0, 20.0, 110.0, 70.0)));
+        assert_eq!(hocr_bbox("bbox 10 20 110"), None);
+    }
+
+    #[test]
+    fn hocr_page_numbers_from_ppageno_or_position() {
+        // ppageno は0始まり、ない場合は出現順
+        let pages = vec![
+            hocr_page("bbox 0 0 1000 500; ppageno 3", vec![word("四", (0.0, 0.0, 10.0, 10.0))]),
+            hocr_page("bbox 0 0 1000 500", vec![word("二", (0.0, 0.0, 10.0, 10.0))]),
+        ];
+        let tokens = markup_tokens(pages, |n| n.to_string()).unwrap();
+        assert_eq!(tokens.iter().map(|t| (t.text.as_str(), t.page)).collect::<Vec<_>>(), [("四", 4), ("二", 2)]);
+    }
+
+    #[test]
+    fn hocr_bbox_normalized_by_page_bbox() {
+        let pages = vec![hocr_page(
+            "bbox 0 0 2000 1000",
+            vec![word(" 施工 ", (500.0, 250.0, 1000.0, 350.0)), word("  ", (0.0, 0.0, 1.0, 1.0))],
+        )];
+        let tokens = markup_tokens(pages, |n| n.to_string()).unwrap();
+        assert_eq!(tokens.len(), 1);
+        assert_eq!(tokens[0].text, "施工");
+        assert_normalized(&tokens[0], (0.25, 0.25, 0.25, 0.1));
+        assert_eq!((tokens[0].pixels.x, tokens[0].pixels.width), (500, 500));
+    }
+
+    #[test]
+    fn hocr_page_without_bbox_is_error() {
+        let pages = vec![hocr_page("ppageno 0", vec![])];
+        let err = markup_tokens(pages, |n| format!("hOCRの{}ページ目にbboxがありません", n)).unwrap_err();
+        assert_eq!(err, "hOCRの1ページ目にbboxがありません");
+    }
+
+    // ---- ALTO ----
+
+    #[test]
+    fn alto_units_scale_to_pixels() {
+        assert_close(alto_scale("pixel"), 1.0);
+        assert_close(alto_scale(" mm10 "), 300.0 / 254.0);
+        assert_close(alto_scale("inch1200"), 0.25);
+        assert_eq!(alto_rect([100.0, 200.0, 400.0, 40.0], 1.0), (100.0, 200.0, 500.0, 240.0));
+        assert_eq!(alto_rect([1200.0, 2400.0, 400.0, 40.0], alto_scale("inch1200")), (300.0, 600.0, 400.0, 610.0));
+    }
+
+    #[test]
+    fn alto_bbox_normalized_by_scaled_page_size() {
+        // inch1200 の A4 相当（9920×14032）→ 300dpi で 2480×3508
+        let scale = alto_scale("inch1200");
+        let pages = vec![
+            MarkupPage {
+                number: None,
+                size: Some((9920.0 * scale, 14032.0 * scale)),
+                words: vec![word("元請", alto_rect([992.0, 1403.2, 2480.0, 701.6], scale))],
+            },
+            MarkupPage { number: Some(7), size: Some((100.0, 100.0)), words: vec![word("下請", (10.0, 10.0, 20.0, 20.0))] },
+        ];
+        let tokens = markup_tokens(pages, |n| n.to_string()).unwrap();
+        assert_eq!(tokens.iter().map(|t| (t.text.as_str(), t.page)).collect::<Vec<_>>(), [("元請", 1), ("下請", 7)]);
+        assert_normalized(&tokens[0], (0.1, 0.1, 0.25, 0.05));
+        assert_close(tokens[0].page_size.width, 2480.0);
+    }
+
+    #[test]
+    fn alto_page_without_size_is_error() {
+        let pages = vec![
+            MarkupPage { number: None, size: Some((100.0, 100.0)), words: vec![] },
+            MarkupPage { number: None, size: Some((0.0, 100.0)), words: vec![] },
+        ];
+        let err = markup_tokens(pages, |n| format!("ALTOの{}ページ目にWIDTH・HEIGHTがありません", n)).unwrap_err();
+        assert_eq!(err, "ALTOの2ページ目にWIDTH・HEIGHTがありません");
+    }
+}
@@ -0,0 +1,329 @@
+//! OCRトークンの語・行・ブロックへの組み立てと全文検索
+//!
+//! Document AIのトークンは1文字単位で返ることが多いため、正規化座標から
+//! 行（縦方向の重なり）・語（行内の文字間の空き）・ブロック（行間と左右の重なり）にまとめる。
+//! 座標はページ内の比率（normalized）にページサイズを掛けて縦横の縮尺を揃えてから比較する
+
+use super::search::normalize_chars;
+use crate::models::OcrToken;
+
+/// 同じ行とみなす縦方向の重なり（低い方の高さに対する比）
+const LINE_OVERLAP_RATIO: f64 = 0.5;
+/// 語の区切りとみなす文字間の空き（行の平均文字高さに対する比）
+const WORD_GAP_RATIO: f64 = 0.8;
+/// 同じブロックとみなす行間（行の高さに対する比）
+const BLOCK_GAP_RATIO: f64 = 1.2;
+
+// ============================================
+// 矩形（ページ座標）
+// ============================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Rect {
+    x: f64,
+    y: f64,
+    w: f64,
+    h: f64,
+}
+
+impl Rect {
+    fn of(token: &OcrToken) -> Self {
+        Rect {
+            x: token.normalized.x * token.page_size.width,
+            y: token.normalized.y * token.page_size.height,
+            w: token.normalized.width * token.page_size.width,
+            h: token.normalized.height * token.page_size.height,
+        }
+    }
+
+    fn right(&self) -> f64 {
+        self.x + self.w
+    }
+
+    fn bottom(&self) -> f64 {
+        self.y + self.h
+    }
+
+    fn center_y(&self) -> f64 {
+        self.y + self.h / 2.0
+    }
+
+    fn union(&self, other: &Rect) -> Rect {
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Rect { x, y, w: self.right().max(other.right()) - x, h: self.bottom().max(other.bottom()) - y }
+    }
+
+    /// 縦方向の重なり（低い方の高さに対する比）
+    fn vertical_overlap(&self, other: &Rect) -> f64 {
+        let overlap = self.bottom().min(other.bottom()) - self.y.max(other.y);
+        let base = self.h.min(other.h);
+        if base <= 0.0 { 0.0 } else { overlap / base }
+    }
+
+    /// 左右に重なりがあるか
+    fn overlaps_horizontally(&self, other: &Rect) -> bool {
+        self.x < other.right() && other.x < self.right()
+    }
+}
+
+// ============================================
+// 組み立て結果
+// ============================================
+
+/// 語（行内で文字間の空きが小さいトークンの並び）
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrWord {
+    /// トークンの添字（左から順）
+    pub tokens: Vec<usize>,
+    pub text: String,
+}
+
+/// 行
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrLine {
+    pub page: u32,
+    pub words: Vec<OcrWord>,
+    rect: Rect,
+}
+
+impl OcrLine {
+    /// 行のテキスト（語の間は空白）
+    pub fn text(&self) -> String {
+        self.words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>().join(" ")
+    }
+
+    /// 行に含まれるトークンの添字
+    pub fn tokens(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().flat_map(|w| w.tokens.iter().copied())
+    }
+}
+
+/// ブロック（近接して並ぶ行のまとまり）
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrBlock {
+    pub page: u32,
+    pub lines: Vec<OcrLine>,
+    rect: Rect,
+}
+
+impl OcrBlock {
+    pub fn text(&self) -> String {
+        self.lines.iter().map(|l| l.text()).collect::<Vec<_>>().join("\n")
+    }
+}
+
+/// 1ドキュメント分の組み立て結果（ページ順・上から順）
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct OcrLayout {
+    pub blocks: Vec<OcrBlock>,
+}
+
+impl OcrLayout {
+    /// 全文（ブロックの間は空行）
+    pub fn text(&self) -> String {
+        self.blocks.iter().map(|b| b.text()).collect::<Vec<_>>().join("\n\n")
+    }
+
+    pub fn lines(&self) -> impl Iterator<Item = &OcrLine> {
+        self.blocks.iter().flat_map(|b| b.lines.iter())
+    }
+}
+
+// ============================================
+// 組み立て
+// ============================================
+
+/// トークンを行・語・ブロックにまとめる
+pub fn group_tokens(tokens: &[OcrToken]) -> OcrLayout {
+    let rects: Vec<Rect> = tokens.iter().map(Rect::of).collect();
+
+    // 上から順に、縦方向に重なる行へ追加
+    let mut order: Vec<usize> = (0..tokens.len()).filter(|&i| !tokens[i].text.trim().is_empty()).collect();
+    order.sort_by(|&a, &b| {
+        tokens[a].page.cmp(&tokens[b].page).then(rects[a].center_y().total_cmp(&rects[b].center_y()))
+    });
+    let mut rows: Vec<(u32, Rect, Vec<usize>)> = Vec::new();
+    for i in order {
+        let best = rows
+            .iter()
+            .enumerate()
+            .filter(|(_, (page, _, _))| *page == tokens[i].page)
+            .map(|(r, (_, rect, _))| (r, rect.vertical_overlap(&rects[i])))
+            .filter(|(_, overlap)| *overlap >= LINE_OVERLAP_RATIO)
+            .max_by(|a, b| a.1.total_cmp(&b.1));
+        match best {
+            Some((r, _)) => {
+                let row = &mut rows[r];
+                row.1 = row.1.union(&rects[i]);
+                row.2.push(i);
+            }
+            None => rows.push((tokens[i].page, rects[i], vec![i])),
+        }
+    }
+
+    // 行内を左から並べ、文字間の空きで語に区切る
+    let mut lines: Vec<OcrLine> = rows
+        .into_iter()
+        .map(|(page, rect, mut members)| {
+            members.sort_by(|&a, &b| rects[a].x.total_cmp(&rects[b].x));
+            let avg_height = members.iter().map(|&i| rects[i].h).sum::<f64>() / members.len() as f64;
+            let mut words: Vec<OcrWord> = Vec::new();
+            let mut prev: Option<usize> = None;
+            for i in members {
+                let split = prev.is_none_or(|p| rects[i].x - rects[p].right() > avg_height * WORD_GAP_RATIO);
+                if split {
+                    words.push(OcrWord { tokens: Vec::new(), text: String::new() });
+                }
+                if let Some(word) = words.last_mut() {
+                    word.tokens.push(i);
+                    word.text.push_str(tokens[i].text.trim());
+                }
+                prev = Some(i);
+            }
+            OcrLine { page, words, rect }
+        })
+        .collect();
+    lines.sort_by(|a, b| a.page.cmp(&b.page).then(a.rect.y.total_cmp(&b.rect.y)).then(a.rect.x.total_cmp(&b.rect.x)));
+
+    // 行間が狭く左右に重なる行をブロックにまとめる
+    let mut blocks: Vec<OcrBlock> = Vec::new();
+    for line in lines {
+        let target = blocks.iter_mut().find(|block| {
+            let Some(last) = block.lines.last() else {
+                return false;
+            };
+            let gap = line.rect.y - last.rect.bottom();
+            block.page == line.page
+                && gap <= last.rect.h.min(line.rect.h) * BLOCK_GAP_RATIO
+                && line.rect.bottom() > last.rect.bottom()
+                && block.rect.overlaps_horizontally(&line.rect)
+        });
+        match target {
+            Some(block) => {
+                block.rect = block.rect.union(&line.rect);
+                block.lines.push(line);
+            }
+            None => blocks.push(OcrBlock { page: line.page, rect: line.rect, lines: vec![line] }),
+        }
+    }
+    blocks.sort_by(|a, b| a.page.cmp(&b.page).then(a.rect.y.total_cmp(&b.rect.y)).then(a.rect.x.total_cmp(&b.rect.x)));
+
+    OcrLayout { blocks }
+}
+
+// ============================================
+// 検索
+// ============================================
+
+/// 検索で一致した範囲
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcrMatch {
+    /// 一致した文字を含むトークンの添字（左から順）
+    pub tokens: Vec<usize>,
+}
+
+/// 行ごとにテキストを検索し、一致した範囲を返す（全体検索と同じ正規化、行をまたぐ一致は対象外）
+pub fn search(layout: &OcrLayout, tokens: &[OcrToken], query: &str) -> Vec<OcrMatch> {
+    let needle = normalize_chars(query);
+    if needle.is_empty() {
+        return Vec::new();
+    }
+    let mut matches = Vec::new();
+    for line in layout.lines() {
+        let haystack: Vec<(char, usize)> = line
+            .tokens()
+            .flat_map(|i| normalize_chars(&tokens[i].text).into_iter().map(move |c| (c, i)))
+            .collect();
+        let mut start = 0;
+        while start + needle.len() <= haystack.len() {
+            let window = &haystack[start..start + needle.len()];
+            if window.iter().map(|(c, _)| *c).eq(needle.iter().copied()) {
+                let mut hit: Vec<usize> = window.iter().map(|(_, i)| *i).collect();
+                hit.dedup();
+                matches.push(OcrMatch { tokens: hit });
+                start += needle.len();
+            } else {
+                start += 1;
+            }
+        }
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{NormalizedCoords, PageSize, PixelCoords};
+
+    /// 1000×1000 のページ上のトークン（座標はページ内の比率）
+    fn token(text: &str, page: u32, x: f64, y: f64) -> OcrToken {
+        OcrToken {
+            text: text.to_string(),
+            page,
+            normalized: NormalizedCoords { x, y, width: 0.02, height: 0.02 },
+            pixels: PixelCoords { x: (x * 1000.0) as i32, y: (y * 1000.0) as i32, width: 20, height: 20 },
+            page_size: PageSize { width: 1000.0, height: 1000.0 },
+        }
+    }
+
+    /// 1文字ずつのトークンで1行（文字間の空きなし）
+    fn chars(text: &str, page: u32, x: f64, y: f64) -> Vec<OcrToken> {
+        text.chars().enumerate().map(|(i, c)| token(&c.to_string(), page, x + i as f64 * 0.02, y)).collect()
+    }
+
+    #[test]
+    fn splits_words_on_character_gaps() {
+        // 「建設」と「業者」の間に文字3つ分の空き、縦位置の小さなずれは同じ行
+        let mut tokens = chars("建設", 1, 0.1, 0.1);
+        tokens.extend(chars("業者", 1, 0.2, 0.105));
+        let layout = group_tokens(&tokens);
+        let lines: Vec<_> = layout.lines().collect();
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].words.iter().map(|w| w.text.as_str()).collect::<Vec<_>>(), ["建設", "業者"]);
+        assert_eq!(lines[0].words[1].tokens, [2, 3]);
+        assert_eq!(lines[0].text(), "建設 業者");
+    }
+
+    #[test]
+    fn orders_tokens_left_to_right_and_skips_blanks() {
+        let tokens = vec![token("中", 1, 0.14, 0.1), token(" ", 1, 0.12, 0.1), token("御", 1, 0.12, 0.1)];
+        let layout = group_tokens(&tokens);
+        assert_eq!(layout.text(), "御中");
+        assert_eq!(layout.lines().next().unwrap().tokens().collect::<Vec<_>>(), [2, 0]);
+    }
+
+    #[test]
+    fn groups_close_lines_into_blocks() {
+        // 1行目と2行目は行間が狭くブロックにまとめ、3行目は離れているので別ブロック
+        let mut tokens = chars("誓約書", 1, 0.1, 0.1);
+        tokens.extend(chars("御中", 1, 0.1, 0.13));
+        tokens.extend(chars("令和", 1, 0.1, 0.5));
+        let layout = group_tokens(&tokens);
+        assert_eq!(layout.blocks.len(), 2);
+        assert_eq!(layout.blocks[0].lines.len(), 2);
+        assert_eq!(layout.text(), "誓約書\n御中\n\n令和");
+    }
+
+    #[test]
+    fn keeps_pages_apart_and_in_order() {
+        // 入力順に関係なくページ順。同じ位置でもページが違えば別の行
+        let mut tokens = chars("二", 2, 0.1, 0.1);
+        tokens.extend(chars("一", 1, 0.1, 0.1));
+        let layout = group_tokens(&tokens);
+        assert_eq!(layout.blocks.iter().map(|b| (b.page, b.text())).collect::<Vec<_>>(), [(1, "一".to_string()), (2, "二".to_string())]);
+    }
+
+    #[test]
+    fn search_matches_within_lines() {
+        let mut tokens = chars("建設業者", 1, 0.1, 0.1);
+        tokens.extend(chars("ＡＢＣ", 1, 0.1, 0.13));
+        let layout = group_tokens(&tokens);
+        assert_eq!(search(&layout, &tokens, "設業"), [OcrMatch { tokens: vec![1, 2] }]);
+        // 全角英数は半角・小文字で照合
+        assert_eq!(search(&layout, &tokens, "ab").len(), 1);
+        // 行をまたぐ一致は対象外
+        assert!(search(&layout, &tokens, "者A").is_empty());
+        assert!(search(&layout, &tokens, " ").is_empty());
+    }
+}
@@ -0,0 +1,39 @@
+//! 技術者資格登録簿のLocalStorage管理
+//!
+//! 登録簿はプロジェクトをまたいで共有するため、プロジェクトデータとは別キーで保存する
+
+use crate::models::Person;
+
+const REGISTRY_KEY: &str = "sekou_taisei_person_registry";
+
+/// 登録簿を保存
+pub fn save_person_registry(persons: &[Person]) {
+    if let Some(window) = web_sys::window() {
+        if let Ok(Some(storage)) = window.local_storage() {
+            if let Ok(json) = serde_json::to_string(persons) {
+                let _ = storage.set_item(REGISTRY_KEY, &json);
+            }
+        }
+    }
+}
+
+/// 登録簿を読み込み（未保存・破損時は空）
+pub fn load_person_registry() -> Vec<Person> {
+    let load = || -> Option<Vec<Person>> {
+        let window = web_sys::window()?;
+        let storage = window.local_storage().ok()??;
+        let json = storage.get_item(REGISTRY_KEY).ok()??;
+        serde_json::from_str(&json).ok()
+    };
+    load().unwrap_or_default()
+}
+
+/// 新規登録用のIDを採番
+pub fn new_person_id() -> String {
+    format!("person_{}", js_sys::Date::now() as u64)
+}
+
+/// IDで登録簿から検索
+pub fn find_person<'a>(persons: &'a [Person], id: &str) -> Option<&'a Person> {
+    persons.iter().find(|p| p.id == id)
+}
@@ -0,0 +1,380 @@
+//! URLルーティング（ViewModeとハッシュの対応）
+//!
+//! 画面ごとにハッシュルート（#/viewer/業者ID/書類キー など）を割り当て、
+//! 画面遷移をブラウザ履歴に積み、戻る・進む・再読み込み・共有リンクで同じ画面を開けるようにする。
+//! `#data=`（共有データ）と `?gas=`（GAS URL）はルートではないためそのまま残す
+//!
+//! | 画面 | ルート |
+//! |------|--------|
+//! | ダッシュボード | （ハッシュなし） |
+//! | OCRビューワ | #/ocr |
+//! | APIキー設定 | #/apikey |
+//! | 書類提出依頼 | #/letters |
+//! | 技術者資格登録簿 | #/persons |
+//! | 一括チェック | #/batch |
+//! | PDFビューワ | #/viewer/業者ID/書類キー |
+//! | スプレッドシート | #/sheet/業者ID/書類キー |
+//! | AIチェック | #/check/業者ID/書類キー |
+//! | PDF編集 | #/edit/業者ID/書類キー |
+
+use leptos::*;
+use wasm_bindgen::prelude::*;
+use wasm_bindgen::JsCast;
+
+use crate::models::{DocKind, ProjectData, ViewMode};
+use crate::utils::log_trace::log_error;
+
+// ============================================
+// ルート定義
+// ============================================
+
+/// 書類を開く画面の種類
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DocPage {
+    Viewer,
+    Sheet,
+    Check,
+    Edit,
+}
+
+impl DocPage {
+    fn segment(&self) -> &'static str {
+        match self {
+            DocPage::Viewer => "viewer",
+            DocPage::Sheet => "sheet",
+            DocPage::Check => "check",
+            DocPage::Edit => "edit",
+        }
+    }
+
+    fn from_segment(segment: &str) -> Option<Self> {
+        match segment {
+            "viewer" => Some(DocPage::Viewer),
+            "sheet" => Some(DocPage::Sheet),
+            "check" => Some(DocPage::Check),
+            "edit" => Some(DocPage::Edit),
+            _ => None,
+        }
+    }
+}
+
+/// URLで表せる画面（書類はIDとキーのみ持ち、URL・表示名は読み込み済みデータから引く）
+#[derive(Debug, Clone, PartialEq)]
+pub enum Route {
+    Dashboard,
+    OcrViewer,
+    ApiKeySetup,
+    RequestLetters,
+    PersonRegistry,
+    BatchCheck,
+    Doc {
+        page: DocPage,
+        contractor_id: String,
+        doc_key: String,
+    },
+}
+
+/// encodeURIComponent と同じ規則でエンコード（英数字と `-_.!~*'()` 以外をUTF-8の%XXに）
+fn encode(value: &str) -> String {
+    let mut out = String::new();
+    for byte in value.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-_.!~*'()".contains(&byte) {
+            out.push(byte as char);
+        } else {
+            out.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    out
+}
+
+/// decodeURIComponent 相当（不正な%エスケープ・UTF-8はNone）
+fn decode(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = std::str::from_utf8(bytes.get(i + 1..i + 3)?).ok()?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+impl Route {
+    /// 画面に対応するルート
+    pub fn from_view(mode: &ViewMode) -> Self {
+        let doc = |page, contractor_id: &str, doc_key: &str| Route::Doc {
+            page,
+            contractor_id: contractor_id.to_string(),
+            doc_key: doc_key.to_string(),
+        };
+        match mode {
+            ViewMode::Dashboard => Route::Dashboard,
+            ViewMode::OcrViewer => Route::OcrViewer,
+            ViewMode::ApiKeySetup => Route::ApiKeySetup,
+            ViewMode::RequestLetters => Route::RequestLetters,
+            ViewMode::PersonRegistry => Route::PersonRegistry,
+            ViewMode::BatchCheck => Route::BatchCheck,
+            ViewMode::PdfViewer { contractor_id, doc_key, .. } => doc(DocPage::Viewer, contractor_id, doc_key),
+            ViewMode::SpreadsheetViewer { contractor_id, doc_key, .. } => doc(DocPage::Sheet, contractor_id, doc_key),
+            ViewMode::AiChecker { contractor_id, doc_key, .. } => doc(DocPage::Check, contractor_id, doc_key),
+            ViewMode::PdfEditor { contractor_id, doc_key, .. } => doc(DocPage::Edit, contractor_id, doc_key),
+        }
+    }
+
+    /// ハッシュを解釈（`#/` で始まらないもの・不明なルートは None）
+    pub fn parse(hash: &str) -> Option<Self> {
+        let path = hash.strip_prefix("#/")?;
+        let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+        match segments.as_slice() {
+            [] => Some(Route::Dashboard),
+            ["ocr"] => Some(Route::OcrViewer),
+            ["apikey"] => Some(Route::ApiKeySetup),
+            ["letters"] => Some(Route::RequestLetters),
+            ["persons"] => Some(Route::PersonRegistry),
+            ["batch"] => Some(Route::BatchCheck),
+            [page, contractor_id, doc_key] => Some(Route::Doc {
+                page: DocPage::from_segment(page)?,
+                contractor_id: decode(contractor_id)?,
+                doc_key: decode(doc_key)?,
+            }),
+            _ => None,
+        }
+    }
+
+    /// ハッシュ文字列（ダッシュボードは空）
+    pub fn to_hash(&self) -> String {
+        match self {
+            Route::Dashboard => String::new(),
+            Route::OcrViewer => "#/ocr".to_string(),
+            Route::ApiKeySetup => "#/apikey".to_string(),
+            Route::RequestLetters => "#/letters".to_string(),
+            Route::PersonRegistry => "#/persons".to_string(),
+            Route::BatchCheck => "#/batch".to_string(),
+            Route::Doc { page, contractor_id, doc_key } => {
+                format!("#/{}/{}/{}", page.segment(), encode(contractor_id), encode(doc_key))
+            }
+        }
+    }
+
+    /// 書類のルートはプロジェクトデータが必要
+    fn needs_project(&self) -> bool {
+        matches!(self, Route::Doc { .. })
+    }
+
+    /// 画面に変換（書類は読み込み済みデータから業者名・書類名・URLを引く）
+    pub fn resolve(&self, project: Option<&ProjectData>) -> Result<ViewMode, String> {
+        let (page, contractor_id, doc_key) = match self {
+            Route::Dashboard => return Ok(ViewMode::Dashboard),
+            Route::OcrViewer => return Ok(ViewMode::OcrViewer),
+            Route::ApiKeySetup => return Ok(ViewMode::ApiKeySetup),
+            Route::RequestLetters => return Ok(ViewMode::RequestLetters),
+            Route::PersonRegistry => return Ok(ViewMode::PersonRegistry),
+            Route::BatchCheck => return Ok(ViewMode::BatchCheck),
+            Route::Doc { page, contractor_id, doc_key } => (*page, contractor_id.clone(), doc_key.clone()),
+        };
+        let project = project.ok_or("プロジェクトデータが読み込まれていません")?;
+        let contractor = project
+            .contractors
+            .iter()
+            .find(|c| c.id == contractor_id)
+            .ok_or_else(|| format!("リンク先の業者が見つかりません: {}", contractor_id))?;
+        let doc_type = DocKind::parse(&doc_key).label();
+        let url = contractor
+            .docs
+            .get(&doc_key)
+            .and_then(|d| d.url.clone())
+            .filter(|u| !u.is_empty())
+            .ok_or_else(|| format!("リンク先の書類が見つかりません: {} / {}", contractor.name, doc_type))?;
+        let contractor_name = contractor.name.clone();
+
+        Ok(match page {
+            DocPage::Viewer => ViewMode::PdfViewer { contractor: contractor_name, doc_type, url, doc_key, contractor_id, locate: None },
+            DocPage::Sheet => ViewMode::SpreadsheetViewer {
+                contractor: contractor_name,
+                doc_type,
+                url,
+                doc_key,
+                contractor_id,
+                auto_fix: false,
+            },
+            DocPage::Check => {
+                let file_id = crate::extract_file_id(&url)
+                    .ok_or_else(|| format!("Google DriveのファイルIDを取得できません: {} / {}", contractor_name, doc_type))?;
+                ViewMode::AiChecker { contractor: contractor_name, doc_type, file_id, doc_key, contractor_id }
+            }
+            DocPage::Edit => ViewMode::PdfEditor {
+                contractor: contractor_name,
+                doc_type,
+                original_url: url,
+                doc_key,
+                contractor_id,
+            },
+        })
+    }
+}
+
+// ============================================
+// ブラウザ履歴との連携
+// ============================================
+
+/// 現在のハッシュ
+fn current_hash() -> String {
+    web_sys::window()
+        .and_then(|w| w.location().hash().ok())
+        .unwrap_or_default()
+}
+
+/// 現在のURLのルート（`#data=` などルート以外のハッシュはダッシュボード扱い）
+fn current_route() -> Route {
+    Route::parse(&current_hash()).unwrap_or(Route::Dashboard)
+}
+
+/// 履歴にルートを積む（`?gas=` などのクエリは残す）
+fn push_route(route: &Route) {
+    let Some(window) = web_sys::window() else {
+        return;
+    };
+    let location = window.location();
+    let pathname = location.pathname().unwrap_or_default();
+    let search = location.search().unwrap_or_default();
+    if let Ok(history) = window.history() {
+        let _ = history.push_state_with_url(&JsValue::NULL, "", Some(&format!("{}{}{}", pathname, search, route.to_hash())));
+    }
+}
+
+/// ViewModeとURLを同期する（App で1回だけ呼ぶ）
+///
+/// - 起動時のルートは、書類の場合はプロジェクト読み込み後に開く
+/// - 画面遷移のたびに履歴へ積み、戻る・進む（popstate）で画面を切り替える
+/// - 書類が見つからないリンクはダッシュボードに戻してエラーを表示する
+pub fn install_router(
+    project: ReadSignal<Option<ProjectData>>,
+    view_mode: ReadSignal<ViewMode>,
+    set_view_mode: WriteSignal<ViewMode>,
+    set_error_msg: WriteSignal<Option<String>>,
+) {
+    // データ読み込み待ちのルート
+    let pending = store_value(None::<Route>);
+
+    let open = move |route: Route, project: Option<&ProjectData>| -> bool {
+        if route.needs_project() && project.is_none() {
+            pending.set_value(Some(route));
+            return false;
+        }
+        pending.set_value(None);
+        match route.resolve(project) {
+            Ok(mode) => set_view_mode.set(mode),
+            Err(e) => {
+                log_error("router", &e);
+                set_error_msg.set(Some(e));
+                set_view_mode.set(ViewMode::Dashboard);
+            }
+        }
+        true
+    };
+
+    // 起動時のルート
+    let initial = current_route();
+    if initial != Route::Dashboard {
+        open(initial, None);
+    }
+
+    // プロジェクト読み込み後に保留中のルートを開く
+    create_effect(move |_| {
+        project.with(|p| {
+            if p.is_some() {
+                if let Some(route) = pending.get_value() {
+                    open(route, p.as_ref());
+                }
+            }
+        });
+    });
+
+    // 画面遷移を履歴に積む
+    create_effect(move |_| {
+        let route = Route::from_view(&view_mode.get());
+        if route == Route::Dashboard && pending.with_value(|p| p.is_some()) {
+            // 起動時のリンクを開く前（データ読み込み待ち）
+            return;
+        }
+        pending.set_value(None);
+        if route != current_route() {
+            push_route(&route);
+        }
+    });
+
+    // 戻る・進む
+    let on_popstate = Closure::<dyn FnMut(web_sys::Event)>::new(move |_: web_sys::Event| {
+        let route = current_route();
+        if route == Route::from_view(&view_mode.get_untracked()) {
+            return;
+        }
+        project.with_untracked(|p| {
+            if !open(route, p.as_ref()) {
+                set_view_mode.set(ViewMode::Dashboard);
+            }
+        });
+    });
+    if let Some(window) = web_sys::window() {
+        let _ = window.add_event_listener_with_callback("popstate", on_popstate.as_ref().unchecked_ref());
+    }
+    // App と同じ寿命のため保持し続ける
+    on_popstate.forget();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(page: DocPage, contractor_id: &str, doc_key: &str) -> Route {
+        Route::Doc { page, contractor_id: contractor_id.to_string(), doc_key: doc_key.to_string() }
+    }
+
+    #[test]
+    fn route_round_trips_through_hash() {
+        let routes = [
+            Route::OcrViewer,
+            Route::ApiKeySetup,
+            Route::RequestLetters,
+            Route::PersonRegistry,
+            Route::BatchCheck,
+            doc(DocPage::Viewer, "c1", "01_建設業許可"),
+            doc(DocPage::Sheet, "c 2", "08_作業員名簿"),
+            doc(DocPage::Check, "c/3", "041_現場代理人資格"),
+            doc(DocPage::Edit, "c%4", "独自 書類?#&"),
+        ];
+        for route in routes {
+            let hash = route.to_hash();
+            assert_eq!(Route::parse(&hash), Some(route.clone()), "{}", hash);
+        }
+    }
+
+    #[test]
+    fn dashboard_has_empty_hash() {
+        assert_eq!(Route::Dashboard.to_hash(), "");
+        assert_eq!(Route::parse("#/"), Some(Route::Dashboard));
+    }
+
+    #[test]
+    fn doc_segments_are_percent_encoded() {
+        assert_eq!(doc(DocPage::Viewer, "c/1", "01_許可").to_hash(), "#/viewer/c%2F1/01_%E8%A8%B1%E5%8F%AF");
+        assert_eq!(Route::parse("#/viewer/c1/01_%E8%A8%B1%E5%8F%AF"), Some(doc(DocPage::Viewer, "c1", "01_許可")));
+    }
+
+    #[test]
+    fn parse_rejects_non_routes() {
+        assert_eq!(Route::parse(""), None);
+        assert_eq!(Route::parse("#data=abc"), None);
+        assert_eq!(Route::parse("#/unknown"), None);
+        assert_eq!(Route::parse("#/print/c1/01_許可"), None);
+        // 不正な%エスケープ
+        assert_eq!(Route::parse("#/viewer/c1/%E8%A8"), None);
+        assert_eq!(Route::parse("#/viewer/c1/%ZZ"), None);
+    }
+}
@@ -0,0 +1,381 @@
+//! 全文検索インデックス
+//!
+//! 業者名・書類名・備考・AIチェック結果（指摘・抽出項目）・OCRテキストを横断して検索する。
+//! 全角/半角・ひらがな/カタカナ・大文字/小文字の違いを正規化して照合する
+
+use crate::models::{DocKind, ProjectData};
+use crate::rules::field_specs_for_doc;
+use crate::models::OcrDocument;
+
+// ============================================
+// 文字の正規化
+// ============================================
+
+/// 半角カナ（U+FF61〜U+FF9D）に対応する全角文字
+const HALF_WIDTH_KANA: &str = "。「」、・ヲァィゥェォャュョッーアイウエオカキクケコサシスセソタチツテトナニヌネノハヒフヘホマミムメモヤユヨラリルレロワン";
+
+/// 濁点を付けられるカタカナ
+const VOICEABLE: &str = "カキクケコサシスセソタチツテトハヒフヘホ";
+/// 半濁点を付けられるカタカナ
+const SEMI_VOICEABLE: &str = "ハヒフヘホ";
+
+/// 検索用に1文字を正規化
+fn normalize_char(c: char) -> char {
+    let c = match c {
+        // 全角英数・記号 → 半角
+        '\u{FF01}'..='\u{FF5E}' => char::from_u32(c as u32 - 0xFEE0).unwrap_or(c),
+        // 半角カナ → 全角カタカナ
+        '\u{FF61}'..='\u{FF9D}' => HALF_WIDTH_KANA.chars().nth((c as u32 - 0xFF61) as usize).unwrap_or(c),
+        // ひらがな → カタカナ
+        'ぁ'..='ゖ' => char::from_u32(c as u32 + 0x60).unwrap_or(c),
+        _ => c,
+    };
+    c.to_ascii_lowercase()
+}
+
+/// 濁点・半濁点（結合文字・全角・半角）か。戻り値は直前の文字に加算する値
+fn voicing_mark(c: char) -> Option<u32> {
+    match c {
+        '\u{3099}' | '゛' | 'ﾞ' => Some(1),
+        '\u{309A}' | '゜' | 'ﾟ' => Some(2),
+        _ => None,
+    }
+}
+
+/// 検索用に文字列を正規化（空白は除去、濁点・半濁点は直前の文字に結合）
+pub fn normalize_chars(text: &str) -> Vec<char> {
+    let mut chars = Vec::new();
+    for c in text.chars() {
+        if c.is_whitespace() {
+            continue;
+        }
+        if let Some(mark) = voicing_mark(c) {
+            // 直前のカナと結合（ウ+゛→ヴ、カ+゛→ガ、ハ+゜→パ）
+            if let Some(prev) = chars.last_mut() {
+                let combined = match (*prev, mark) {
+                    ('ウ', 1) => Some('ヴ'),
+                    (p, 1) if VOICEABLE.contains(p) => char::from_u32(p as u32 + 1),
+                    (p, 2) if SEMI_VOICEABLE.contains(p) => char::from_u32(p as u32 + 2),
+                    _ => None,
+                };
+                if let Some(combined) = combined {
+                    *prev = combined;
+                    continue;
+                }
+            }
+        }
+        chars.push(normalize_char(c));
+    }
+    chars
+}
+
+/// 検索用に文字列を正規化
+pub fn normalize_for_search(text: &str) -> String {
+    normalize_chars(text).into_iter().collect()
+}
+
+// ============================================
+// 検索インデックス
+// ============================================
+
+/// 検索結果の移動先
+#[derive(Debug, Clone, PartialEq)]
+pub enum SearchTarget {
+    /// 業者カード
+    Contractor { contractor_id: String },
+    /// 業者カード内の書類
+    Doc { contractor_id: String, doc_key: String },
+    /// OCRビューのトークン
+    OcrToken { doc_index: usize, token_index: usize },
+}
+
+/// 検索対象の1項目
+#[derive(Debug, Clone, PartialEq)]
+struct SearchEntry {
+    target: SearchTarget,
+    /// 表示名（例: 山田建設 / 建設業許可）
+    title: String,
+    /// 項目名（例: 備考）
+    field: String,
+    text: String,
+    normalized: String,
+}
+
+/// OCRドキュメント1件分の連結テキスト
+#[derive(Debug, Clone, PartialEq)]
+struct OcrText {
+    doc_index: usize,
+    title: String,
+    normalized: Vec<char>,
+    /// 正規化後の各文字が属するトークン番号
+    token_at: Vec<usize>,
+    tokens: Vec<String>,
+}
+
+/// 検索結果
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub target: SearchTarget,
+    pub title: String,
+    pub field: String,
+    /// 一致箇所を含む抜粋
+    pub snippet: String,
+}
+
+/// 全文検索インデックス
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SearchIndex {
+    entries: Vec<SearchEntry>,
+    ocr: Vec<OcrText>,
+}
+
+/// 抜粋の最大文字数
+const SNIPPET_LEN: usize = 60;
+/// OCRの抜粋に含める前後のトークン数
+const SNIPPET_TOKENS: usize = 4;
+
+impl SearchIndex {
+    /// プロジェクトとOCRドキュメントからインデックスを作成
+    pub fn build(project: Option<&ProjectData>, ocr_documents: &[OcrDocument]) -> Self {
+        let mut entries = Vec::new();
+        let mut push = |target: &SearchTarget, title: &str, field: &str, text: &str| {
+            if text.trim().is_empty() {
+                return;
+            }
+            entries.push(SearchEntry {
+                target: target.clone(),
+                title: title.to_string(),
+                field: field.to_string(),
+                text: text.to_string(),
+                normalized: normalize_for_search(text),
+            });
+        };
+
+        for contractor in project.iter().flat_map(|p| &p.contractors) {
+            let target = SearchTarget::Contractor { contractor_id: contractor.id.clone() };
+            push(&target, &contractor.name, "業者名", &contractor.name);
+            push(&target, &contractor.name, "役割", &contractor.role);
+
+            for (key, doc) in contractor.sorted_docs() {
+                let label = DocKind::parse(key).label();
+                let title = format!("{} / {}", contractor.name, label);
+                let target = SearchTarget::Doc { contractor_id: contractor.id.clone(), doc_key: key.clone() };
+                push(&target, &title, "書類名", &label);
+                push(&target, &title, "備考", doc.note.as_deref().unwrap_or_default());
+                // 書類項目は入力欄の項目名で表示
+                let specs = field_specs_for_doc(key);
+                let field_label = |k: &str| specs.iter().find(|s| s.key == k).map(|s| s.label.to_string()).unwrap_or_else(|| k.to_string());
+                let mut fields: Vec<_> = doc.fields.iter().collect();
+                fields.sort();
+                for (k, value) in fields {
+                    push(&target, &title, &field_label(k), value);
+                }
+                if let Some(result) = &doc.check_result {
+                    push(&target, &title, "AIチェック", &result.summary);
+                    for item in &result.items {
+                        push(&target, &title, "AIチェック", &item.message);
+                    }
+                    let mut extracted: Vec<_> = result.extracted_fields.iter().collect();
+                    extracted.sort();
+                    for (k, value) in extracted {
+                        push(&target, &title, &field_label(k), value);
+                    }
+                }
+            }
+        }
+
+        let ocr = ocr_documents.iter().enumerate().map(|(doc_index, doc)| {
+            let mut normalized = Vec::new();
+            let mut token_at = Vec::new();
+            for (i, token) in doc.tokens.iter().enumerate() {
+                let chars = normalize_chars(&token.text);
+                token_at.extend(std::iter::repeat_n(i, chars.len()));
+                normalized.extend(chars);
+            }
+            OcrText {
+                doc_index,
+                title: format!("{} / {}", doc.contractor, doc.doc_type),
+                normalized,
+                token_at,
+                tokens: doc.tokens.iter().map(|t| t.text.clone()).collect(),
+            }
+        }).collect();
+
+        SearchIndex { entries, ocr }
+    }
+
+    /// 検索（空白区切りの語はすべて含むものを返す）
+    pub fn search(&self, query: &str, limit: usize) -> Vec<SearchHit> {
+        let terms: Vec<String> = query.split_whitespace()
+            .map(normalize_for_search)
+            .filter(|t| !t.is_empty())
+            .collect();
+        if terms.is_empty() {
+            return Vec::new();
+        }
+
+        let mut hits: Vec<SearchHit> = self.entries.iter()
+            .filter(|e| terms.iter().all(|t| e.normalized.contains(t.as_str())))
+            .map(|e| SearchHit {
+                target: e.target.clone(),
+                title: e.title.clone(),
+                field: e.field.clone(),
+                snippet: truncate(&e.text, SNIPPET_LEN),
+            })
+            .collect();
+
+        // OCRは語ごとにトークンをまたいで照合し、先頭の語の一致箇所ごとに1件
+        let first: Vec<char> = terms[0].chars().collect();
+        for text in &self.ocr {
+            let haystack: String = text.normalized.iter().collect();
+            if !terms.iter().all(|t| haystack.contains(t.as_str())) {
+                continue;
+            }
+            for start in find_all(&text.normalized, &first) {
+                let first_token = text.token_at[start];
+                let last_token = text.token_at[start + first.len() - 1];
+                let from = first_token.saturating_sub(SNIPPET_TOKENS);
+                let to = (last_token + SNIPPET_TOKENS + 1).min(text.tokens.len());
+                hits.push(SearchHit {
+                    target: SearchTarget::OcrToken { doc_index: text.doc_index, token_index: first_token },
+                    title: text.title.clone(),
+                    field: "OCR".to_string(),
+                    snippet: truncate(&text.tokens[from..to].concat(), SNIPPET_LEN),
+                });
+            }
+        }
+
+        hits.truncate(limit);
+        hits
+    }
+}
+
+/// 一致開始位置をすべて返す
+fn find_all(haystack: &[char], needle: &[char]) -> Vec<usize> {
+    if needle.is_empty() || haystack.len() < needle.len() {
+        return Vec::new();
+    }
+    (0..=haystack.len() - needle.len())
+        .filter(|&i| haystack[i..i + needle.len()] == *needle)
+        .collect()
+}
+
+/// 指定文字数で切り詰め
+fn truncate(text: &str, max: usize) -> String {
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        format!("{}…", text.chars().take(max).collect::<String>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn project() -> ProjectData {
+        serde_json::from_value(json!({
+            "project_name": "テスト工事",
+            "contractors": [{
+                "id": "c1",
+                "name": "山田建設株式会社",
+                "role": "一次下請",
+                "docs": {
+                    "01_建設業許可": { "status": true, "note": "ｱｽﾍﾞｽﾄ含有あり" },
+                    "09_暴対法誓約書": { "status": false }
+                }
+            }]
+        }))
+        .unwrap()
+    }
+
+    /// 1トークンずつ横に並べたOCRドキュメント
+    fn ocr_doc(texts: &[&str]) -> OcrDocument {
+        let tokens: Vec<_> = texts
+            .iter()
+            .enumerate()
+            .map(|(i, text)| {
+                json!({
+                    "text": text,
+                    "page": 1,
+                    "normalized": { "x": 0.1 + i as f64 * 0.05, "y": 0.1, "width": 0.05, "height": 0.02 },
+                    "pixels": { "x": 100 + i as i32 * 50, "y": 100, "width": 50, "height": 20 },
+                    "page_size": { "width": 1000.0, "height": 1000.0 }
+                })
+            })
+            .collect();
+        serde_json::from_value(json!({
+            "contractor": "山田建設",
+            "doc_type": "09_暴対法誓約書",
+            "image_url": "",
+            "tokens": tokens
+        }))
+        .unwrap()
+    }
+
+    // ---- 正規化 ----
+
+    #[test]
+    fn normalizes_width_case_and_kana() {
+        assert_eq!(normalize_for_search("ＡＢＣ　１２３"), "abc123");
+        assert_eq!(normalize_for_search("やまだ"), "ヤマダ");
+        assert_eq!(normalize_for_search("ｱｽﾍﾞｽﾄ"), "アスベスト");
+        assert_eq!(normalize_for_search("｢ｶﾞｲﾄﾞ｣､ｰ"), "「ガイド」、ー");
+        assert_eq!(normalize_for_search("ﾊﾟﾋﾟ"), "パピ");
+        assert_eq!(normalize_for_search("ｳﾞｨ"), "ヴィ");
+    }
+
+    #[test]
+    fn combines_voicing_marks_with_previous_kana() {
+        // 結合文字・全角の濁点・半濁点
+        assert_eq!(normalize_for_search("か\u{3099}は\u{309A}"), "ガパ");
+        assert_eq!(normalize_for_search("カ゛ハ゜"), "ガパ");
+        // 濁点を付けられない文字はそのまま残す
+        assert_eq!(normalize_for_search("ア゛"), "ア゛");
+        assert_eq!(normalize_for_search("゛"), "゛");
+        // 半濁点はハ行のみ
+        assert_eq!(normalize_for_search("カﾟ"), "カﾟ");
+    }
+
+    // ---- 検索 ----
+
+    #[test]
+    fn searches_project_fields_with_normalization() {
+        let index = SearchIndex::build(Some(&project()), &[]);
+        let hits = index.search("あすべすと", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].title, "山田建設株式会社 / 建設業許可");
+        assert_eq!(hits[0].field, "備考");
+        assert_eq!(hits[0].target, SearchTarget::Doc { contractor_id: "c1".to_string(), doc_key: "01_建設業許可".to_string() });
+
+        // 空白区切りの語はすべて含むものだけ
+        assert_eq!(index.search("山田 一次", 10).len(), 0);
+        assert_eq!(index.search("誓約書", 10)[0].field, "書類名");
+        assert!(index.search("  ", 10).is_empty());
+    }
+
+    #[test]
+    fn ocr_search_matches_across_tokens() {
+        let index = SearchIndex::build(None, &[ocr_doc(&["建設", "業許", "可", "番号"])]);
+        let hits = index.search("業許可", 10);
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].target, SearchTarget::OcrToken { doc_index: 0, token_index: 1 });
+        assert_eq!(hits[0].title, "山田建設 / 09_暴対法誓約書");
+        assert_eq!(hits[0].snippet, "建設業許可番号");
+
+        // 先頭の語の一致箇所ごとに1件、件数は limit まで
+        let index = SearchIndex::build(None, &[ocr_doc(&["年", "月", "日", "年", "月"])]);
+        assert_eq!(index.search("年", 10).len(), 2);
+        assert_eq!(index.search("年", 1).len(), 1);
+        assert!(index.search("年 日付", 10).is_empty());
+    }
+
+    #[test]
+    fn truncates_snippets() {
+        assert_eq!(truncate("あいう", 3), "あいう");
+        assert_eq!(truncate("あいうえ", 3), "あいう…");
+        assert_eq!(find_all(&['a', 'a', 'a'], &['a', 'a']), [0, 1]);
+        assert!(find_all(&['a'], &[]).is_empty());
+    }
+}
@@ -0,0 +1,808 @@
+//! AI一括チェックビュー
+//!
+//! 業者単位またはプロジェクト全体のPDF書類をキューに積み、非表示iframe
+//! （editor/index.html?mode=batch-check）で1件ずつAIチェックする。
+//! 同時実行数と開始間隔でレート制限し、一時停止・再開・キャンセルに対応する。
+//! 結果は受信ごとにProjectDataへ反映し（キャッシュ保存）、GASへはキュー完了時に1回だけ保存する
+
+use std::cell::Cell;
+use std::rc::Rc;
+
+use gloo::timers::callback::Interval;
+use leptos::*;
+
+use crate::models::{detect_file_type, CheckResultData, DocChangeSource, DocFileType, DocKind, DocStatus, ProjectData, ViewMode};
+use crate::utils::audit::{get_actor_name, now_iso, record_doc_change};
+use crate::utils::cache::save_to_cache;
+use crate::utils::date::{add_days, parse_ymd};
+use crate::utils::editor_protocol::{on_editor_message, EditorMessage, EditorMode, EditorUrl};
+use crate::utils::gas::get_gas_url;
+use crate::utils::log_trace::{log_error, log_info};
+use crate::ProjectContext;
+
+// ============================================
+// 設定値
+// ============================================
+
+/// 同時実行数の初期値
+const DEFAULT_CONCURRENCY: usize = 2;
+/// 開始間隔の初期値（秒）。Gemini無料枠の15リクエスト/分に収まる間隔
+const DEFAULT_INTERVAL_SECS: u32 = 4;
+/// 1件あたりの上限時間（PDF取得＋AIチェック）
+const JOB_TIMEOUT_MS: f64 = 180_000.0;
+/// レート制限（429）を受けたときに全体の開始を待たせる時間
+const RATE_LIMIT_COOLDOWN_MS: f64 = 30_000.0;
+/// レート制限による再試行の上限
+const MAX_RATE_LIMIT_RETRIES: u32 = 2;
+/// 前回チェックからこの日数を過ぎた書類は再チェック対象
+pub const STALE_DAYS: i64 = 30;
+/// スケジューラの周期
+const TICK_MS: u32 = 500;
+
+// ============================================
+// 対象の選定
+// ============================================
+
+/// キューに積む範囲
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BatchScope {
+    /// URLのある書類すべて
+    All,
+    /// AIチェック未実施のみ
+    Unchecked,
+    /// 未実施＋要再チェック（ファイル更新・期間経過）
+    Stale,
+}
+
+impl BatchScope {
+    pub const ALL: [BatchScope; 3] = [BatchScope::Stale, BatchScope::Unchecked, BatchScope::All];
+
+    pub fn key(&self) -> &'static str {
+        match self {
+            BatchScope::All => "all",
+            BatchScope::Unchecked => "unchecked",
+            BatchScope::Stale => "stale",
+        }
+    }
+
+    pub fn parse(key: &str) -> Self {
+        match key {
+            "all" => BatchScope::All,
+            "unchecked" => BatchScope::Unchecked,
+            _ => BatchScope::Stale,
+        }
+    }
+
+    pub fn label(&self) -> String {
+        match self {
+            BatchScope::All => "URLのある書類すべて".to_string(),
+            BatchScope::Unchecked => "未チェックのみ".to_string(),
+            BatchScope::Stale => format!("未チェック・要再チェック（ファイル更新・{}日経過）", STALE_DAYS),
+        }
+    }
+
+    /// 書類が範囲に含まれるか
+    fn includes(&self, doc: &DocStatus, file_id: &str, today: &str) -> bool {
+        match self {
+            BatchScope::All => true,
+            BatchScope::Unchecked => doc.check_result.is_none(),
+            BatchScope::Stale => is_stale(doc, file_id, today),
+        }
+    }
+}
+
+/// 再チェックが必要か（未チェック・前回と別ファイル・前回から一定期間経過）
+pub fn is_stale(doc: &DocStatus, file_id: &str, today: &str) -> bool {
+    if doc.check_result.is_none() {
+        return true;
+    }
+    // 履歴導入前の結果はファイルIDを持たないので期間だけで判定
+    let file_changed = doc.check_history.last()
+        .and_then(|run| run.file_id.as_deref())
+        .is_some_and(|checked| checked != file_id);
+    let expired = match (doc.last_checked.as_deref().and_then(parse_ymd), add_days(today, -STALE_DAYS).as_deref().and_then(parse_ymd)) {
+        (Some(checked), Some(threshold)) => checked < threshold,
+        (None, _) => true,
+        _ => false,
+    };
+    file_changed || expired
+}
+
+/// ジョブの状態
+#[derive(Debug, Clone, PartialEq)]
+pub enum JobState {
+    Pending,
+    /// 実行中（開始時刻 ms）
+    Running(f64),
+    /// 完了（判定 ok / warning / error）
+    Done(String),
+    Failed(String),
+    Cancelled,
+}
+
+impl JobState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            JobState::Pending => "待機",
+            JobState::Running(_) => "実行中",
+            JobState::Done(_) => "完了",
+            JobState::Failed(_) => "失敗",
+            JobState::Cancelled => "キャンセル",
+        }
+    }
+
+    pub fn class(&self) -> &'static str {
+        match self {
+            JobState::Pending => "pending",
+            JobState::Running(_) => "running",
+            JobState::Done(_) => "done",
+            JobState::Failed(_) => "failed",
+            JobState::Cancelled => "cancelled",
+        }
+    }
+}
+
+/// 一括チェックの1件
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchJob {
+    pub id: String,
+    pub contractor_id: String,
+    pub contractor_name: String,
+    pub doc_key: String,
+    pub doc_type: String,
+    pub file_id: String,
+    /// 開始した回数（再試行でiframeを作り直すため）
+    pub attempts: u32,
+    pub state: JobState,
+}
+
+/// キューの作成結果
+#[derive(Debug, Clone, Default)]
+pub struct BatchPlan {
+    pub jobs: Vec<BatchJob>,
+    /// URLはあるがPDFではない（AIチェック対象外）件数
+    pub skipped: usize,
+}
+
+/// 対象書類からキューを作成（contractor_idがNoneなら全業者）
+pub fn plan_batch(project: &ProjectData, contractor_id: Option<&str>, scope: BatchScope, today: &str, id_prefix: &str) -> BatchPlan {
+    let mut plan = BatchPlan::default();
+    let contractors = project.contractors.iter()
+        .filter(|c| contractor_id.is_none_or(|id| c.id == id));
+    for contractor in contractors {
+        for (doc_key, doc) in contractor.sorted_docs() {
+            let Some(url) = doc.url.as_deref().filter(|u| !u.trim().is_empty()) else {
+                continue;
+            };
+            let file_id = match (detect_file_type(url), crate::extract_file_id(url)) {
+                (DocFileType::Pdf, Some(file_id)) => file_id,
+                _ => {
+                    plan.skipped += 1;
+                    continue;
+                }
+            };
+            if !scope.includes(doc, &file_id, today) {
+                continue;
+            }
+            plan.jobs.push(BatchJob {
+                id: format!("{}-{}", id_prefix, plan.jobs.len()),
+                contractor_id: contractor.id.clone(),
+                contractor_name: contractor.name.clone(),
+                doc_key: doc_key.clone(),
+                doc_type: DocKind::parse(doc_key).label(),
+                file_id,
+                attempts: 0,
+                state: JobState::Pending,
+            });
+        }
+    }
+    plan
+}
+
+/// 結果を書類に反映（反映できたらtrue）
+///
+/// ワーカーが最新版のファイルに切り替えてチェックした場合（file_idがジョブと異なる）は、
+/// 書類のURLもそのファイルに更新する。GASへの保存で古いURLに戻したり、
+/// 次回の一括チェックで同じ書類を再び対象にしたりしないため
+fn apply_result(project: &mut ProjectData, job: &BatchJob, result: CheckResultData, file_id: Option<String>, now: &str, actor: &str) -> bool {
+    let Some(doc) = project.contractors.iter_mut()
+        .find(|c| c.id == job.contractor_id)
+        .and_then(|c| c.docs.get_mut(&job.doc_key)) else {
+        return false;
+    };
+    let file_id = file_id.filter(|id| !id.trim().is_empty()).unwrap_or_else(|| job.file_id.clone());
+    if file_id != job.file_id {
+        let before = doc.clone();
+        doc.url = Some(match doc.url.as_deref().filter(|u| u.contains(&job.file_id)) {
+            Some(url) => url.replace(&job.file_id, &file_id),
+            None => format!("https://drive.google.com/file/d/{}/view", file_id),
+        });
+        record_doc_change(Some(&before), doc, DocChangeSource::LatestFile, actor, now);
+    }
+    let before = doc.clone();
+    doc.push_check_result(result, now, Some(file_id));
+    record_doc_change(Some(&before), doc, DocChangeSource::AiCheck, actor, now);
+    true
+}
+
+/// レート制限によるエラーか（Gemini API の 429 / RESOURCE_EXHAUSTED）
+fn is_rate_limited(error: &str) -> bool {
+    error.contains("429") || error.contains("RESOURCE_EXHAUSTED") || error.to_lowercase().contains("quota")
+}
+
+// ============================================
+// 実行状態
+// ============================================
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum RunState {
+    Idle,
+    Running,
+    Paused,
+    Finished,
+}
+
+/// 件数の集計
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+struct Progress {
+    total: usize,
+    pending: usize,
+    running: usize,
+    done: usize,
+    failed: usize,
+    cancelled: usize,
+}
+
+impl Progress {
+    fn of(jobs: &[BatchJob]) -> Self {
+        let mut p = Progress { total: jobs.len(), ..Default::default() };
+        for job in jobs {
+            match job.state {
+                JobState::Pending => p.pending += 1,
+                JobState::Running(_) => p.running += 1,
+                JobState::Done(_) => p.done += 1,
+                JobState::Failed(_) => p.failed += 1,
+                JobState::Cancelled => p.cancelled += 1,
+            }
+        }
+        p
+    }
+
+    /// 終了した件数（完了・失敗・キャンセル）
+    fn finished(&self) -> usize {
+        self.done + self.failed + self.cancelled
+    }
+
+    fn percent(&self) -> usize {
+        (self.finished() * 100).checked_div(self.total).unwrap_or(0)
+    }
+}
+
+// ============================================
+// 一括チェックビュー
+// ============================================
+
+#[component]
+pub fn BatchCheckView() -> impl IntoView {
+    let ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
+    let set_view_mode = ctx.set_view_mode;
+    let project = ctx.project;
+    let set_project = ctx.set_project;
+
+    let (contractor_filter, set_contractor_filter) = create_signal(String::new());
+    let (scope, set_scope) = create_signal(BatchScope::Stale);
+    let (concurrency, set_concurrency) = create_signal(DEFAULT_CONCURRENCY);
+    let (interval_secs, set_interval_secs) = create_signal(DEFAULT_INTERVAL_SECS);
+
+    let (jobs, set_jobs) = create_signal(Vec::<BatchJob>::new());
+    let (run_state, set_run_state) = create_signal(RunState::Idle);
+    let (message, set_message) = create_signal(None::<String>);
+    // 次に開始してよい時刻（ms）。開始間隔とレート制限の待ち時間
+    let next_start_at = store_value(0.0_f64);
+    let batch_seq = store_value(0_u32);
+    // GAS未保存の反映件数（破棄時の保存判定にも使うためCellで持つ）
+    let unsaved = Rc::new(Cell::new(0_usize));
+
+    let progress = create_memo(move |_| jobs.with(|j| Progress::of(j)));
+    let busy = move || matches!(run_state.get(), RunState::Running | RunState::Paused);
+
+    let preview = create_memo(move |_| {
+        project.with(|p| p.as_ref().map(|p| {
+            let filter = contractor_filter.get();
+            let plan = plan_batch(p, (!filter.is_empty()).then_some(filter.as_str()), scope.get(), &crate::get_today(), "preview");
+            (plan.jobs.len(), plan.skipped)
+        }))
+    });
+
+    // GASへ1回だけ保存
+    let save_once = {
+        let unsaved = unsaved.clone();
+        move || {
+            let count = unsaved.replace(0);
+            if count == 0 {
+                return;
+            }
+            let Some(proj) = project.get_untracked() else {
+                return;
+            };
+            set_message.set(Some(format!("{}件の結果をGASに保存中...", count)));
+            spawn_local(async move {
+                match crate::sync_to_gas(&proj).await {
+                    Ok(msg) => {
+                        log_info("batch-check", &format!("GAS保存成功: {}", msg));
+                        set_message.try_set(Some(format!("{}件の結果をGASに保存しました", count)));
+                    }
+                    Err(e) => {
+                        log_error("batch-check", &format!("GAS保存エラー: {}", e));
+                        set_message.try_set(Some(format!("GASへの保存に失敗しました（結果はキャッシュに保存済み）: {}", e)));
+                    }
+                }
+            });
+        }
+    };
+
+    // ワーカーからの結果
+    {
+        let unsaved = unsaved.clone();
+        on_editor_message(move |msg| {
+            let EditorMessage::BatchCheckResult { job_id, file_id, result, error, .. } = msg else {
+                return;
+            };
+            // キャンセル・タイムアウト済みのジョブの結果は捨てる
+            let Some(job) = jobs.with_untracked(|js| {
+                js.iter().find(|j| &j.id == job_id && matches!(j.state, JobState::Running(_))).cloned()
+            }) else {
+                return;
+            };
+
+            let state = match (result, error) {
+                (Some(result), _) => {
+                    let status = result.status.clone();
+                    let mut applied = false;
+                    set_project.update(|p| {
+                        if let Some(p) = p {
+                            applied = apply_result(p, &job, result.clone(), file_id.clone(), &now_iso(), &get_actor_name());
+                        }
+                    });
+                    if applied {
+                        project.with_untracked(|p| {
+                            if let Some(p) = p {
+                                save_to_cache(p)
+                            }
+                        });
+                        unsaved.set(unsaved.get() + 1);
+                        JobState::Done(status)
+                    } else {
+                        JobState::Failed("書類が見つかりません（一括チェック中に削除された可能性があります）".to_string())
+                    }
+                }
+                (None, Some(error)) if is_rate_limited(error) && job.attempts <= MAX_RATE_LIMIT_RETRIES => {
+                    next_start_at.set_value(js_sys::Date::now() + RATE_LIMIT_COOLDOWN_MS);
+                    log_info("batch-check", &format!("レート制限のため再試行します: {}", job.id));
+                    JobState::Pending
+                }
+                (None, Some(error)) if error.contains("APIキー") || error.contains("シート連携") => {
+                    // 設定不足は全件失敗するので止めて知らせる
+                    set_run_state.set(RunState::Paused);
+                    set_message.set(Some(format!("{}。設定後に「再開」してください", error)));
+                    JobState::Pending
+                }
+                (None, error) => JobState::Failed(error.clone().unwrap_or_else(|| "結果がありません".to_string())),
+            };
+            set_jobs.update(|js| {
+                if let Some(j) = js.iter_mut().find(|j| j.id == job.id) {
+                    j.state = state;
+                }
+            });
+        });
+    }
+
+    // スケジューラ（タイムアウト・開始・完了判定）
+    let tick = {
+        let save_once = save_once.clone();
+        move || {
+            if run_state.get_untracked() != RunState::Running {
+                return;
+            }
+            let now = js_sys::Date::now();
+            let limit = concurrency.get_untracked();
+            let mut started = false;
+            set_jobs.update(|js| {
+                for job in js.iter_mut() {
+                    if matches!(job.state, JobState::Running(at) if now - at > JOB_TIMEOUT_MS) {
+                        job.state = JobState::Failed("タイムアウト".to_string());
+                    }
+                }
+                let running = js.iter().filter(|j| matches!(j.state, JobState::Running(_))).count();
+                if running < limit && now >= next_start_at.get_value() {
+                    if let Some(job) = js.iter_mut().find(|j| j.state == JobState::Pending) {
+                        job.state = JobState::Running(now);
+                        job.attempts += 1;
+                        started = true;
+                    }
+                }
+            });
+            if started {
+                next_start_at.set_value(now + f64::from(interval_secs.get_untracked()) * 1000.0);
+            }
+            let p = progress.get_untracked();
+            if p.pending == 0 && p.running == 0 {
+                set_run_state.set(RunState::Finished);
+                log_info("batch-check", &format!("一括チェック完了: 完了{} 失敗{} キャンセル{}", p.done, p.failed, p.cancelled));
+                save_once();
+            }
+        }
+    };
+    let interval = Interval::new(TICK_MS, tick);
+
+    {
+        let save_once = save_once.clone();
+        on_cleanup(move || {
+            drop(interval);
+            // 画面を離れた場合も反映済みの結果は保存する
+            save_once();
+        });
+    }
+
+    let on_start = move |_| {
+        let Some(p) = project.get_untracked() else {
+            return;
+        };
+        batch_seq.update_value(|s| *s += 1);
+        let filter = contractor_filter.get_untracked();
+        let plan = plan_batch(
+            &p,
+            (!filter.is_empty()).then_some(filter.as_str()),
+            scope.get_untracked(),
+            &crate::get_today(),
+            &format!("batch{}", batch_seq.get_value()),
+        );
+        if plan.jobs.is_empty() {
+            set_message.set(Some("対象の書類がありません".to_string()));
+            return;
+        }
+        log_info("batch-check", &format!("一括チェック開始: {}件", plan.jobs.len()));
+        set_jobs.set(plan.jobs);
+        set_message.set(None);
+        next_start_at.set_value(0.0);
+        set_run_state.set(RunState::Running);
+    };
+
+    let on_cancel = move |_| {
+        set_jobs.update(|js| {
+            for job in js.iter_mut().filter(|j| matches!(j.state, JobState::Pending | JobState::Running(_))) {
+                job.state = JobState::Cancelled;
+            }
+        });
+        // 次のtickで完了扱いになり、反映済みの結果を保存する
+        set_run_state.set(RunState::Running);
+    };
+
+    let on_retry_failed = move |_| {
+        set_jobs.update(|js| {
+            for job in js.iter_mut().filter(|j| matches!(j.state, JobState::Failed(_) | JobState::Cancelled)) {
+                job.state = JobState::Pending;
+                job.attempts = 0;
+            }
+        });
+        set_message.set(None);
+        set_run_state.set(RunState::Running);
+    };
+
+    let running_jobs = move || jobs.with(|js| {
+        js.iter().filter(|j| matches!(j.state, JobState::Running(_))).cloned().collect::<Vec<_>>()
+    });
+
+    let gas_url = get_gas_url();
+    let gas_missing = gas_url.is_none();
+
+    view! {
+        <div class="batch-check-view">
+            <div class="request-letter-header">
+                <button class="back-btn" on:click=move |_| set_view_mode.set(ViewMode::Dashboard)>
+                    "← 戻る"
+                </button>
+                <h2>"AI一括チェック"</h2>
+                <span class="hint">"PDFの1ページ目をAIチェックします。GASへの保存は完了時に1回です"</span>
+            </div>
+
+            {gas_missing.then(|| view! {
+                <p class="batch-check-warning">"シート連携が未設定です。メニュー → シート連携設定 からGAS URLを設定してください。"</p>
+            })}
+
+            <div class="batch-check-settings">
+                <label>
+                    "対象業者"
+                    <select prop:disabled=busy on:change=move |ev| set_contractor_filter.set(event_target_value(&ev))>
+                        <option value="" selected=move || contractor_filter.get().is_empty()>"全体"</option>
+                        {move || project.with(|p| p.as_ref().map(|p| p.contractors.iter().map(|c| {
+                            let id = c.id.clone();
+                            let selected_id = id.clone();
+                            view! {
+                                <option value=id selected=move || contractor_filter.get() == selected_id>{c.name.clone()}</option>
+                            }
+                        }).collect_view()))}
+                    </select>
+                </label>
+                <label>
+                    "範囲"
+                    <select prop:disabled=busy on:change=move |ev| set_scope.set(BatchScope::parse(&event_target_value(&ev)))>
+                        {BatchScope::ALL.into_iter().map(|s| view! {
+                            <option value=s.key() selected=move || scope.get() == s>{s.label()}</option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <label>
+                    "同時実行数"
+                    <select on:change=move |ev| {
+                        if let Ok(n) = event_target_value(&ev).parse::<usize>() {
+                            set_concurrency.set(n);
+                        }
+                    }>
+                        {[1_usize, 2, 3].into_iter().map(|n| view! {
+                            <option value=n.to_string() selected=move || concurrency.get() == n>{n}</option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <label>
+                    "開始間隔"
+                    <select on:change=move |ev| {
+                        if let Ok(n) = event_target_value(&ev).parse::<u32>() {
+                            set_interval_secs.set(n);
+                        }
+                    }>
+                        {[2_u32, 4, 6, 10].into_iter().map(|n| view! {
+                            <option value=n.to_string() selected=move || interval_secs.get() == n>{format!("{}秒", n)}</option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <span class="batch-check-preview">
+                    {move || preview.get().map(|(count, skipped)| {
+                        if skipped > 0 {
+                            format!("対象 {}件（PDF以外の{}件は対象外）", count, skipped)
+                        } else {
+                            format!("対象 {}件", count)
+                        }
+                    })}
+                </span>
+            </div>
+
+            <div class="batch-check-controls">
+                <button class="gas-btn primary" on:click=on_start disabled=move || busy() || gas_missing>
+                    "開始"
+                </button>
+                <button class="gas-btn" on:click=move |_| set_run_state.set(RunState::Paused) disabled=move || run_state.get() != RunState::Running>
+                    "一時停止"
+                </button>
+                <button class="gas-btn" on:click=move |_| set_run_state.set(RunState::Running) disabled=move || run_state.get() != RunState::Paused>
+                    "再開"
+                </button>
+                <button class="gas-btn" on:click=on_cancel disabled=move || !busy()>
+                    "キャンセル"
+                </button>
+                <button class="gas-btn" on:click=on_retry_failed
+                    disabled=move || busy() || progress.with(|p| p.failed + p.cancelled == 0)>
+                    "失敗・キャンセル分を再実行"
+                </button>
+            </div>
+
+            {move || (progress.get().total > 0).then(|| {
+                let p = progress.get();
+                let state_label = match run_state.get() {
+                    RunState::Running => "実行中",
+                    RunState::Paused => "一時停止中（実行中の書類は完了まで続きます）",
+                    RunState::Finished => "完了",
+                    RunState::Idle => "",
+                };
+                view! {
+                    <div class="batch-check-progress">
+                        <div class="batch-check-progress-bar">
+                            <div class="batch-check-progress-fill" style=format!("width: {}%", p.percent())></div>
+                        </div>
+                        <div class="batch-check-progress-text">
+                            <span>{format!("{} / {}件", p.finished(), p.total)}</span>
+                            <span>{state_label}</span>
+                            <span class="stat stat-ok">"完了: " {p.done}</span>
+                            <span class="stat stat-error">"失敗: " {p.failed}</span>
+                            <span>"実行中: " {p.running}</span>
+                            <span>"待機: " {p.pending}</span>
+                            {(p.cancelled > 0).then(|| view! { <span>"キャンセル: " {p.cancelled}</span> })}
+                        </div>
+                    </div>
+                }
+            })}
+
+            {move || message.get().map(|m| view! { <p class="batch-check-message">{m}</p> })}
+
+            {move || {
+                let failed: Vec<BatchJob> = jobs.with(|js| js.iter().filter(|j| matches!(j.state, JobState::Failed(_))).cloned().collect());
+                (!failed.is_empty()).then(|| view! {
+                    <div class="batch-check-failures">
+                        <h3>"失敗した書類"</h3>
+                        <ul>
+                            {failed.into_iter().map(|job| {
+                                let reason = match &job.state {
+                                    JobState::Failed(reason) => reason.clone(),
+                                    _ => String::new(),
+                                };
+                                view! {
+                                    <li>
+                                        <span class="batch-job-contractor">{job.contractor_name}</span>
+                                        <span class="batch-job-doc">{job.doc_type}</span>
+                                        <span class="batch-job-error">{reason}</span>
+                                    </li>
+                                }
+                            }).collect_view()}
+                        </ul>
+                    </div>
+                })
+            }}
+
+            {move || (progress.get().total > 0).then(|| view! {
+                <details class="batch-check-jobs">
+                    <summary>"書類ごとの状態"</summary>
+                    <table>
+                        <thead>
+                            <tr><th>"業者"</th><th>"書類"</th><th>"状態"</th></tr>
+                        </thead>
+                        <tbody>
+                            {jobs.get().into_iter().map(|job| {
+                                let detail = match &job.state {
+                                    JobState::Done(status) => format!("{}（{}）", job.state.label(), status),
+                                    _ => job.state.label().to_string(),
+                                };
+                                view! {
+                                    <tr class=format!("batch-job batch-job-{}", job.state.class())>
+                                        <td>{job.contractor_name}</td>
+                                        <td>{job.doc_type}</td>
+                                        <td>{detail}</td>
+                                    </tr>
+                                }
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                </details>
+            })}
+
+            // 実行中のジョブごとに非表示のワーカーを置く（状態が変わると破棄される）
+            <div class="batch-check-workers" aria-hidden="true">
+                <For
+                    each=running_jobs
+                    key=|job: &BatchJob| format!("{}#{}", job.id, job.attempts)
+                    children=move |job: BatchJob| {
+                        let src = EditorUrl::new(EditorMode::BatchCheck)
+                            .file_id(&job.file_id)
+                            .doc(&job.contractor_name, &job.contractor_id, &job.doc_type, &job.doc_key)
+                            .gas_url(gas_url.as_deref())
+                            .job_id(&job.id)
+                            .build();
+                        view! { <iframe class="batch-check-frame" src=src tabindex="-1"></iframe> }
+                    }
+                />
+            </div>
+        </div>
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::DocChangeField;
+    use serde_json::{json, Value};
+
+    const TODAY: &str = "2026-10-18";
+
+    fn pdf_url(file_id: &str) -> String {
+        format!("https://drive.google.com/file/d/{}/view", file_id)
+    }
+
+    fn checked(last_checked: &str, file_id: Option<&str>) -> Value {
+        let result = json!({ "status": "ok", "summary": "", "items": [], "missing_fields": [] });
+        json!({
+            "status": true,
+            "url": pdf_url("pdf-1"),
+            "check_result": result,
+            "last_checked": last_checked,
+            "check_history": [{ "checked_at": last_checked, "file_id": file_id, "result": result }],
+        })
+    }
+
+    fn doc(value: Value) -> DocStatus {
+        serde_json::from_value(value).unwrap()
+    }
+
+    /// 業者A（PDF未チェック・PDFチェック済み・スプレッドシート・URLなし）と業者B（PDF未チェック）
+    fn project() -> ProjectData {
+        serde_json::from_value(json!({
+            "project_name": "テスト工事",
+            "contractors": [
+                {
+                    "id": "a",
+                    "name": "業者A",
+                    "role": "1次",
+                    "docs": {
+                        "09_暴対法誓約書": { "status": true, "url": pdf_url("pdf-9") },
+                        "01_建設業許可": checked("2026-10-01", Some("pdf-1")),
+                        "08_作業員名簿": { "status": true, "url": "https://docs.google.com/spreadsheets/d/sheet-8/edit" },
+                        "07_建退共": { "status": false, "url": " " },
+                    },
+                },
+                {
+                    "id": "b",
+                    "name": "業者B",
+                    "role": "2次",
+                    "docs": { "01_建設業許可": { "status": true, "url": pdf_url("pdf-b1") } },
+                },
+            ],
+        }))
+        .unwrap()
+    }
+
+    fn job_keys(plan: &BatchPlan) -> Vec<(&str, &str, &str)> {
+        plan.jobs.iter().map(|j| (j.contractor_id.as_str(), j.doc_key.as_str(), j.file_id.as_str())).collect()
+    }
+
+    #[test]
+    fn unchecked_and_changed_or_expired_docs_are_stale() {
+        assert!(is_stale(&doc(json!({ "status": true })), "pdf-1", TODAY));
+        assert!(!is_stale(&doc(checked("2026-10-01", Some("pdf-1"))), "pdf-1", TODAY));
+        // 前回と別のファイル
+        assert!(is_stale(&doc(checked("2026-10-01", Some("pdf-1"))), "pdf-2", TODAY));
+        // ちょうどSTALE_DAYS日前までは対象外、それより前は対象
+        assert!(!is_stale(&doc(checked("2026-09-18", Some("pdf-1"))), "pdf-1", TODAY));
+        assert!(is_stale(&doc(checked("2026-09-17", Some("pdf-1"))), "pdf-1", TODAY));
+        // 履歴導入前の結果（ファイルIDなし）は期間だけで判定
+        assert!(!is_stale(&doc(checked("2026-10-01", None)), "pdf-2", TODAY));
+        // チェック日時が読めない結果は再チェック
+        assert!(is_stale(&doc(checked("不明", Some("pdf-1"))), "pdf-1", TODAY));
+    }
+
+    #[test]
+    fn plan_batch_queues_pdfs_in_scope() {
+        let project = project();
+        let plan = plan_batch(&project, None, BatchScope::All, TODAY, "job");
+        assert_eq!(job_keys(&plan), [("a", "01_建設業許可", "pdf-1"), ("a", "09_暴対法誓約書", "pdf-9"), ("b", "01_建設業許可", "pdf-b1")]);
+        // スプレッドシートは対象外として数える（URLが空の書類は数えない）
+        assert_eq!(plan.skipped, 1);
+        assert_eq!(plan.jobs.iter().map(|j| j.id.as_str()).collect::<Vec<_>>(), ["job-0", "job-1", "job-2"]);
+        assert_eq!((plan.jobs[0].doc_type.as_str(), &plan.jobs[0].state), ("建設業許可", &JobState::Pending));
+
+        let stale = plan_batch(&project, None, BatchScope::Stale, TODAY, "job");
+        assert_eq!(job_keys(&stale), [("a", "09_暴対法誓約書", "pdf-9"), ("b", "01_建設業許可", "pdf-b1")]);
+        let unchecked = plan_batch(&project, Some("a"), BatchScope::Unchecked, TODAY, "job");
+        assert_eq!(job_keys(&unchecked), [("a", "09_暴対法誓約書", "pdf-9")]);
+    }
+
+    #[test]
+    fn result_from_latest_file_updates_url() {
+        let mut project = project();
+        let plan = plan_batch(&project, Some("a"), BatchScope::All, TODAY, "job");
+        let job = plan.jobs.iter().find(|j| j.doc_key == "09_暴対法誓約書").unwrap();
+        let result: CheckResultData = serde_json::from_value(json!({ "status": "warning", "summary": "", "items": [], "missing_fields": [] })).unwrap();
+        assert!(apply_result(&mut project, job, result, Some("pdf-9-v2".to_string()), "2026-10-18T09:00:00Z", "担当者"));
+
+        let doc = &project.contractors[0].docs["09_暴対法誓約書"];
+        assert_eq!(doc.url.as_deref(), Some(pdf_url("pdf-9-v2").as_str()));
+        assert_eq!(doc.check_history.last().and_then(|r| r.file_id.as_deref()), Some("pdf-9-v2"));
+        let changes: Vec<_> = doc.history.iter().map(|c| (c.field, c.source)).collect();
+        assert_eq!(changes, [(DocChangeField::Url, DocChangeSource::LatestFile), (DocChangeField::AiStatus, DocChangeSource::AiCheck)]);
+        // 次回の一括チェックでは再び対象にならない
+        let stale = plan_batch(&project, Some("a"), BatchScope::Stale, "2026-10-18", "job");
+        assert!(stale.jobs.iter().all(|j| j.doc_key != "09_暴対法誓約書"));
+    }
+
+    #[test]
+    fn result_for_same_file_keeps_url() {
+        let mut project = project();
+        let plan = plan_batch(&project, Some("b"), BatchScope::All, TODAY, "job");
+        let result: CheckResultData = serde_json::from_value(json!({ "status": "ok", "summary": "", "items": [], "missing_fields": [] })).unwrap();
+        assert!(apply_result(&mut project, &plan.jobs[0], result.clone(), None, "2026-10-18T09:00:00Z", ""));
+        let doc = &project.contractors[1].docs["01_建設業許可"];
+        assert_eq!(doc.url.as_deref(), Some(pdf_url("pdf-b1").as_str()));
+        assert_eq!(doc.check_history.last().and_then(|r| r.file_id.as_deref()), Some("pdf-b1"));
+        assert!(doc.history.iter().all(|c| c.field != DocChangeField::Url));
+        // 一括チェック中に削除された書類には反映しない
+        project.contractors[1].docs.clear();
+        assert!(!apply_result(&mut project, &plan.jobs[0], result, None, "2026-10-18T09:00:00Z", ""));
+    }
+}
@@ -2,11 +2,15 @@
 //!
 //! ## 変更履歴
 //! - 2026-01-02: CheckResultsPanelに閉じるボタン追加（モバイル対応）
+//! - 2026-10-18: CheckResultPanelに書類種別のチェックリスト（確認済み・不備あり・未評価）を追加
+//! - 2026-10-18: 位置付きの指摘・未記入項目のクリックで書類の該当箇所を表示（on_locate）
+//! - 2026-10-18: チェックリストを ChecklistDetails に分離（ビューワの結果パネルでも表示）
 //!
 //! PDFドキュメントのチェック結果を表示するパネルコンポーネント
 
 use leptos::*;
-use crate::models::CheckResultData;
+use crate::models::{CheckResultData, FindingLocation};
+use crate::rules::checklist::{checklist_summary, evaluate_checklist, ChecklistLine};
 use crate::{CheckMode, CheckStatus, ProjectContext};
 
 // ============================================
@@ -17,7 +21,11 @@ use crate::{CheckMode, CheckStatus, ProjectContext};
 #[component]
 pub fn CheckResultPanel(
     result: CheckResultData,
+    /// 書類キー（指定するとチェックリストを並べて表示）
+    #[prop(optional, into)] doc_key: Option<String>,
     #[prop(optional)] on_close: Option<Callback<()>>,
+    /// 位置付きの指摘・未記入項目をクリックしたとき（書類の該当箇所を表示）
+    #[prop(optional)] on_locate: Option<Callback<FindingLocation>>,
 ) -> impl IntoView {
     let status_class = match result.status.as_str() {
         "ok" => "status-ok",
@@ -45,6 +53,9 @@ pub fn CheckResultPanel(
     let warning_count = result.items.iter().filter(|i| i.item_type == "warning").count();
     let error_count = result.items.iter().filter(|i| i.item_type == "error").count();
 
+    let checklist = doc_key.and_then(|key| checklist_lines(&key, &result));
+    let columns_class = if checklist.is_some() { "result-columns with-checklist" } else { "result-columns" };
+
     view! {
         <div class=format!("check-result-panel {}", status_class)>
             // ヘッダー
@@ -71,57 +82,136 @@ pub fn CheckResultPanel(
                 <span class="stat stat-error">"エラー: " {error_count}</span>
             </div>
 
-            // チェック項目（折りたたみ可能）
-            {(!result.items.is_empty()).then(|| {
-                let items = result.items.clone();
-                view! {
-                    <details class="result-details" open>
-                        <summary>"チェック項目 (" {items.len()} "件)"</summary>
-                        <ul class="result-items-list">
-                            {items.into_iter().map(|item| {
-                                let icon = match item.item_type.as_str() {
-                                    "ok" => "✓",
-                                    "warning" => "⚠",
-                                    "error" => "✗",
-                                    "info" => "ℹ",
-                                    _ => "•",
-                                };
-                                view! {
-                                    <li class=format!("result-item item-{}", item.item_type)>
-                                        <span class="item-icon">{icon}</span>
-                                        <span class="item-message">{item.message}</span>
-                                    </li>
-                                }
-                            }).collect_view()}
-                        </ul>
-                    </details>
-                }
-            })}
+            <div class=columns_class>
+                <div class="result-main">
+                    // チェック項目（折りたたみ可能）
+                    {(!result.items.is_empty()).then(|| {
+                        let items = result.items.clone();
+                        view! {
+                            <details class="result-details" open>
+                                <summary>"チェック項目 (" {items.len()} "件)"</summary>
+                                <ul class="result-items-list">
+                                    {items.into_iter().map(|item| {
+                                        let icon = match item.item_type.as_str() {
+                                            "ok" => "✓",
+                                            "warning" => "⚠",
+                                            "error" => "✗",
+                                            "info" => "ℹ",
+                                            _ => "•",
+                                        };
+                                        let at = item.finding_location();
+                                        let class = format!("result-item item-{}{}", item.item_type, locatable_class(&at, on_locate));
+                                        view! {
+                                            <li class=class on:click=locate_handler(at.clone(), on_locate)>
+                                                <span class="item-icon">{icon}</span>
+                                                <span class="item-message">{item.message}</span>
+                                                {location_tag(&at)}
+                                            </li>
+                                        }
+                                    }).collect_view()}
+                                </ul>
+                            </details>
+                        }
+                    })}
 
-            // 未記入項目
-            {(!result.missing_fields.is_empty()).then(|| {
-                let fields = result.missing_fields.clone();
-                view! {
-                    <details class="missing-fields-details" open>
-                        <summary class="missing-header">
-                            "未記入項目 (" {fields.len()} "件)"
-                        </summary>
-                        <ul class="missing-fields-list">
-                            {fields.into_iter().map(|field| view! {
-                                <li class="missing-field-item">
-                                    <span class="field-icon">"□"</span>
-                                    <span class="field-name">{field.field}</span>
-                                    <span class="field-location">"（"{field.location}"）"</span>
-                                </li>
-                            }).collect_view()}
-                        </ul>
-                    </details>
-                }
-            })}
+                    // 未記入項目
+                    {(!result.missing_fields.is_empty()).then(|| {
+                        let fields = result.missing_fields.clone();
+                        view! {
+                            <details class="missing-fields-details" open>
+                                <summary class="missing-header">
+                                    "未記入項目 (" {fields.len()} "件)"
+                                </summary>
+                                <ul class="missing-fields-list">
+                                    {fields.into_iter().map(|field| {
+                                        let at = field.finding_location();
+                                        let class = format!("missing-field-item{}", locatable_class(&at, on_locate));
+                                        view! {
+                                            <li class=class on:click=locate_handler(at.clone(), on_locate)>
+                                                <span class="field-icon">"□"</span>
+                                                <span class="field-name">{field.field}</span>
+                                                <span class="field-location">"（"{field.location}"）"</span>
+                                                {location_tag(&at)}
+                                            </li>
+                                        }
+                                    }).collect_view()}
+                                </ul>
+                            </details>
+                        }
+                    })}
+                </div>
+
+                // チェックリスト（書類種別ごとの確認項目）
+                {checklist.map(|lines| view! { <ChecklistDetails lines=lines /> })}
+            </div>
         </div>
     }
 }
 
+// ============================================
+// チェックリスト
+// ============================================
+
+/// 書類種別のチェックリストを評価（定義のない書類は None）
+pub(crate) fn checklist_lines(doc_key: &str, result: &CheckResultData) -> Option<Vec<ChecklistLine>> {
+    Some(evaluate_checklist(doc_key, result)).filter(|lines| !lines.is_empty())
+}
+
+/// チェックリスト（行ごとの評価・書式・よくある誤り・根拠）
+#[component]
+pub fn ChecklistDetails(lines: Vec<ChecklistLine>) -> impl IntoView {
+    view! {
+        <details class="checklist-details" open>
+            <summary>{format!("チェックリスト（{}）", checklist_summary(&lines))}</summary>
+            <ul class="checklist-list">
+                {lines.into_iter().map(|line| view! {
+                    <li class=format!("checklist-item checklist-{}", line.state.class())>
+                        <span class="checklist-icon" title=line.state.label()>{line.state.icon()}</span>
+                        <div class="checklist-body">
+                            <span class="checklist-label">{line.item.label}</span>
+                            <span class="checklist-format">"書式: " {line.item.format}</span>
+                            {(!line.item.mistakes.is_empty()).then(|| view! {
+                                <span class="checklist-mistakes">"よくある誤り: " {line.item.mistakes.join("、")}</span>
+                            })}
+                            {line.evidence.map(|e| view! { <span class="checklist-evidence">{e}</span> })}
+                        </div>
+                    </li>
+                }).collect_view()}
+            </ul>
+        </details>
+    }
+}
+
+// ============================================
+// 指摘箇所へのジャンプ
+// ============================================
+
+/// クリックで位置を表示できる項目のクラス
+pub(crate) fn locatable_class(at: &Option<FindingLocation>, on_locate: Option<Callback<FindingLocation>>) -> &'static str {
+    if at.is_some() && on_locate.is_some() {
+        " locatable"
+    } else {
+        ""
+    }
+}
+
+/// 項目クリック時に位置を通知するハンドラ
+pub(crate) fn locate_handler(
+    at: Option<FindingLocation>,
+    on_locate: Option<Callback<FindingLocation>>,
+) -> impl Fn(web_sys::MouseEvent) + 'static {
+    move |_| {
+        if let (Some(at), Some(cb)) = (at.clone(), on_locate) {
+            cb.call(at);
+        }
+    }
+}
+
+/// 位置の表示（例: 📍 2ページ）
+pub(crate) fn location_tag(at: &Option<FindingLocation>) -> Option<impl IntoView> {
+    at.as_ref().map(|at| view! { <span class="finding-location">{format!("📍 {}ページ", at.page)}</span> })
+}
+
 // ============================================
 // 既存チェック結果パネル
 // ============================================
@@ -154,6 +244,14 @@ pub fn CheckResultsPanel() -> impl IntoView {
                 let title = match mode {
                     CheckMode::Existence => "書類存在チェック結果",
                     CheckMode::Date => "日付チェック結果",
+                    CheckMode::Workers => "作業員名簿チェック結果",
+                    CheckMode::Persons => "技術者資格チェック結果",
+                    CheckMode::Permit => "建設業許可チェック結果",
+                    CheckMode::Numbers => "番号形式チェック結果",
+                    CheckMode::Ccus => "CCUS登録チェック結果",
+                    CheckMode::Ledger => "施工体制台帳要否チェック結果",
+                    CheckMode::Engineer => "配置技術者チェック結果",
+                    CheckMode::AiHistory => "AIチェック前回比較",
                     CheckMode::None => "",
                 };
 
@@ -201,9 +299,13 @@ pub fn CheckResultsPanel() -> impl IntoView {
                             </div>
                         })}
 
-                        {(mode == CheckMode::Date && !oks.is_empty()).then(|| view! {
+                        {(matches!(mode, CheckMode::Date | CheckMode::Workers | CheckMode::Persons | CheckMode::Permit | CheckMode::Numbers | CheckMode::Ccus | CheckMode::Ledger | CheckMode::Engineer | CheckMode::AiHistory) && !oks.is_empty()).then(|| view! {
                             <div class="check-section ok-section">
-                                <h4>"有効期限内"</h4>
+                                <h4>{match mode {
+                                    CheckMode::Date => "有効期限内",
+                                    CheckMode::AiHistory => "解消・変化なし",
+                                    _ => "問題なし",
+                                }}</h4>
                                 {oks.into_iter().map(|r| view! {
                                     <div class="check-result-item ok">
                                         <span class="result-contractor">{r.contractor_name.clone()}</span>
@@ -6,9 +6,19 @@
 
 pub mod check_panel;
 pub mod pdf_viewer;
+pub mod ocr_blank;
+pub mod ocr_check;
+pub mod ocr_extract;
+pub mod ocr_template;
 pub mod ocr_viewer;
 pub mod spreadsheet_viewer;
+pub mod request_letter;
+pub mod person_registry;
+pub mod batch_check;
 
 pub use check_panel::CheckResultsPanel;
 pub use pdf_viewer::PdfViewer;
 pub use spreadsheet_viewer::SpreadsheetViewer;
+pub use request_letter::RequestLetterView;
+pub use person_registry::PersonRegistryView;
+pub use batch_check::BatchCheckView;
@@ -0,0 +1,64 @@
+//! 未記入の記入欄の表示（一覧とCanvasへの描画）
+//!
+//! 未記入の判定は utils::ocr_blank（OCRトークンと帳票テンプレートの比較）
+
+use leptos::*;
+use web_sys::CanvasRenderingContext2d;
+
+use super::ocr_viewer::{OcrViewContext, PageTransform};
+use crate::MissingField;
+
+// ============================================
+// 未記入欄の一覧
+// ============================================
+
+/// 未記入の記入欄の一覧（クリックでそのページを表示）
+#[component]
+pub fn OcrBlankFieldList(blanks: Memo<Vec<MissingField>>) -> impl IntoView {
+    let ctx = use_context::<OcrViewContext>().expect("OcrViewContext not found");
+
+    view! {
+        <Show when=move || blanks.with(|b| !b.is_empty())>
+            <div class="ocr-blank-list">
+                <h4>{move || format!("未記入の記入欄（{}件）", blanks.with(|b| b.len()))}</h4>
+                <ul>
+                    {move || blanks.get().into_iter().map(|blank| {
+                        let page = blank.page.unwrap_or(1);
+                        view! {
+                            <li on:click=move |_| ctx.set_current_page.set(page)>
+                                <span class="ocr-blank-name">{blank.field_name}</span>
+                                <span class="ocr-blank-type">{blank.field_type.label()}</span>
+                                <span class="ocr-blank-page">{format!("{}ページ", page)}</span>
+                            </li>
+                        }
+                    }).collect_view()}
+                </ul>
+            </div>
+        </Show>
+    }
+}
+
+// ============================================
+// 描画
+// ============================================
+
+/// 未記入の記入欄（指定ページ分）を赤の破線で囲む
+pub fn draw_blank_fields(ctx: &CanvasRenderingContext2d, transform: &PageTransform, blanks: &[MissingField], page: u32) {
+    let dash = js_sys::Array::of2(&8.0.into(), &4.0.into());
+    for blank in blanks.iter().filter(|b| b.page.unwrap_or(1) == page) {
+        let Some(p) = &blank.position else {
+            continue;
+        };
+        let (x, y, w, h) = transform.rect(p.x, p.y, p.width, p.height);
+        ctx.set_fill_style_str("rgba(229, 57, 53, 0.15)");
+        ctx.fill_rect(x, y, w, h);
+        let _ = ctx.set_line_dash(&dash);
+        ctx.set_stroke_style_str("#e53935");
+        ctx.set_line_width(2.0);
+        ctx.stroke_rect(x, y, w, h);
+        let _ = ctx.set_line_dash(&js_sys::Array::new());
+        ctx.set_fill_style_str("#e53935");
+        ctx.set_font("bold 12px sans-serif");
+        let _ = ctx.fill_text(&format!("未記入: {}", blank.field_name), x, y + h + 14.0);
+    }
+}
@@ -0,0 +1,177 @@
+//! OCRビューからの書類チェック（APIキー不要のプロバイダー）
+//!
+//! 表示中の書類のOCRトークン（または貼り付けたテキスト）を checker のプロバイダーで確認し、
+//! AIチェックと同じ形の結果を表示する。プロジェクトに同じ業者・書類があれば結果を保存できる
+//! （モックの結果は保存しない）。
+//! 位置付きの指摘をクリックすると、Canvas上の該当箇所を強調する
+
+use leptos::*;
+
+use super::ocr_viewer::OcrViewContext;
+use super::check_panel::CheckResultPanel;
+use crate::checker::{CheckInput, CheckSource, CheckerKind};
+use crate::models::{CheckResultData, DocChangeSource, FindingLocation};
+use crate::utils::audit::{get_actor_name, now_iso, record_doc_change};
+use crate::ProjectContext;
+
+/// 書類チェックパネル
+#[component]
+pub fn OcrCheckPanel() -> impl IntoView {
+    let ctx = use_context::<OcrViewContext>().expect("OcrViewContext not found");
+    let project_ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
+
+    let (checker_kind, set_checker_kind) = create_signal(CheckerKind::Local);
+    let (result, set_result) = create_signal(None::<Result<CheckResultData, String>>);
+    let (pasted_text, set_pasted_text) = create_signal(String::new());
+    let (message, set_message) = create_signal(None::<String>);
+
+    // 書類を切り替えたら結果と指摘箇所の強調を消す
+    create_effect(move |_| {
+        ctx.current_doc_index.track();
+        set_result.set(None);
+        set_message.set(None);
+        ctx.set_highlight.set(None);
+    });
+
+    // 指摘箇所のページを開いて強調し、Canvasまでスクロール
+    let on_locate = Callback::new(move |at: FindingLocation| {
+        ctx.set_template_mode.set(false);
+        ctx.set_current_page.set(at.page);
+        ctx.set_highlight.set(Some(at));
+        let canvas = web_sys::window().and_then(|w| w.document()).and_then(|d| d.query_selector(".ocr-canvas").ok().flatten());
+        if let Some(canvas) = canvas {
+            canvas.scroll_into_view();
+        }
+    });
+
+    // 表示中の書類の業者名・書類キー
+    let current_doc = move || {
+        let idx = ctx.current_doc_index.get();
+        ctx.documents.with(|docs| docs.get(idx).map(|d| (d.contractor.clone(), d.doc_type.clone())))
+    };
+
+    // プロジェクトに同じ業者・書類があるか（結果の保存先）
+    let save_target_exists = create_memo(move |_| {
+        let Some((contractor, doc_key)) = current_doc() else {
+            return false;
+        };
+        project_ctx.project.with(|p| {
+            p.as_ref().is_some_and(|p| {
+                p.contractors.iter().any(|c| c.name.trim() == contractor.trim() && c.docs.contains_key(&doc_key))
+            })
+        })
+    });
+
+    let run_check = move |use_pasted_text: bool| {
+        let idx = ctx.current_doc_index.get_untracked();
+        let today = crate::get_today();
+        let text = pasted_text.get_untracked();
+        let templates = ctx.templates.get_untracked();
+        let checker = checker_kind.get_untracked().checker(&templates);
+        let outcome = ctx.documents.with_untracked(|docs| {
+            let doc = docs.get(idx).ok_or("書類が選択されていません")?;
+            let source = if use_pasted_text { CheckSource::Text(&text) } else { CheckSource::Tokens(&doc.tokens) };
+            project_ctx.project.with_untracked(|p| {
+                let input = CheckInput::new(&doc.doc_type, source, &today).with_project(p.as_ref(), &doc.contractor);
+                checker.check(&input)
+            })
+        });
+        set_message.set(None);
+        ctx.set_highlight.set(None);
+        set_result.set(Some(outcome));
+    };
+
+    let on_save = move |_| {
+        if !checker_kind.get_untracked().can_save() {
+            return;
+        }
+        let Some(Ok(check_result)) = result.get_untracked() else {
+            return;
+        };
+        let Some((contractor_name, doc_key)) = current_doc() else {
+            return;
+        };
+        let now = now_iso();
+        let mut saved = false;
+        project_ctx.set_project.update(|p| {
+            let Some(project) = p else {
+                return;
+            };
+            let doc = project
+                .contractors
+                .iter_mut()
+                .find(|c| c.name.trim() == contractor_name.trim())
+                .and_then(|c| c.docs.get_mut(&doc_key));
+            if let Some(doc) = doc {
+                let before = doc.clone();
+                doc.push_check_result(check_result.clone(), &now, None);
+                record_doc_change(Some(&before), doc, DocChangeSource::RuleCheck, &get_actor_name(), &now);
+                saved = true;
+            }
+        });
+        set_message.set(Some(if saved {
+            format!("{} / {} にチェック結果を保存しました", contractor_name, doc_key)
+        } else {
+            format!("プロジェクトに {} / {} が見つかりません", contractor_name, doc_key)
+        }));
+    };
+
+    view! {
+        <div class="ocr-check-panel">
+            <div class="ocr-check-header">
+                <h4>"書類チェック（APIキー不要）"</h4>
+                <select on:change=move |ev| {
+                    let idx: usize = event_target_value(&ev).parse().unwrap_or(0);
+                    if let Some(kind) = CheckerKind::ALL.get(idx) {
+                        // 表示中の結果は切り替え前のプロバイダーのもの
+                        set_checker_kind.set(*kind);
+                        set_result.set(None);
+                        set_message.set(None);
+                        ctx.set_highlight.set(None);
+                    }
+                }>
+                    {CheckerKind::ALL.iter().enumerate().map(|(i, kind)| {
+                        let kind = *kind;
+                        view! {
+                            <option value=i.to_string() selected=move || checker_kind.get() == kind>{kind.label()}</option>
+                        }
+                    }).collect_view()}
+                </select>
+                <button class="ocr-check-btn" on:click=move |_| run_check(false)>"OCR結果をチェック"</button>
+                <button class="ocr-check-btn"
+                    disabled=move || !checker_kind.get().can_save() || !matches!(result.get(), Some(Ok(_))) || !save_target_exists.get()
+                    title=move || if !checker_kind.get().can_save() {
+                        "モックの結果は保存できません"
+                    } else if save_target_exists.get() {
+                        ""
+                    } else {
+                        "プロジェクトに同じ業者名・書類キーがありません"
+                    }
+                    on:click=on_save
+                >"結果を書類に保存"</button>
+            </div>
+
+            <details class="ocr-check-text">
+                <summary>"テキストを貼り付けてチェック"</summary>
+                <textarea rows="6" placeholder="PDFなどからコピーした書類の文字"
+                    prop:value=move || pasted_text.get()
+                    on:input=move |ev| set_pasted_text.set(event_target_value(&ev))
+                />
+                <button class="ocr-check-btn"
+                    disabled=move || pasted_text.with(|t| t.trim().is_empty())
+                    on:click=move |_| run_check(true)
+                >"テキストをチェック"</button>
+            </details>
+
+            {move || message.get().map(|msg| view! { <p class="ocr-check-message">{msg}</p> })}
+            {move || match result.get() {
+                None => ().into_view(),
+                Some(Ok(check_result)) => {
+                    let doc_key = current_doc().map(|(_, key)| key).unwrap_or_default();
+                    view! { <CheckResultPanel result=check_result doc_key=doc_key on_locate=on_locate /> }.into_view()
+                }
+                Some(Err(e)) => view! { <p class="ocr-check-error">{e}</p> }.into_view(),
+            }}
+        </div>
+    }
+}
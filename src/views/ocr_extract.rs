@@ -0,0 +1,89 @@
+//! OCRトークンからの書類項目の抽出結果パネル
+//!
+//! 抽出ルール（組み込み・帳票テンプレート）は utils::ocr_extract。
+//! 抽出した項目・値・信頼度を表で示し、AIチェックと同じ形のJSONをコピーできる
+
+use leptos::*;
+
+use super::ocr_viewer::OcrViewContext;
+use crate::utils::ocr_extract::{extract_fields, has_extraction_rules};
+use crate::utils::ocr_layout::OcrLayout;
+
+// ============================================
+// 抽出結果パネル
+// ============================================
+
+/// 表示中の書類の抽出結果（項目・値・信頼度）。行のクリックで値の位置を選択する
+#[component]
+pub fn OcrExtractPanel(layout: Memo<OcrLayout>) -> impl IntoView {
+    let ctx = use_context::<OcrViewContext>().expect("OcrViewContext not found");
+
+    let extraction = create_memo(move |_| {
+        let idx = ctx.current_doc_index.get();
+        ctx.documents.with(|docs| {
+            let doc = docs.get(idx)?;
+            ctx.templates.with(|ts| {
+                has_extraction_rules(&doc.doc_type, ts).then(|| layout.with(|l| extract_fields(doc, l, ts)))
+            })
+        })
+    });
+
+    let confidence_class = |confidence: f64| {
+        if confidence >= 0.8 {
+            "ocr-extract-confidence high"
+        } else if confidence >= 0.6 {
+            "ocr-extract-confidence medium"
+        } else {
+            "ocr-extract-confidence low"
+        }
+    };
+
+    view! {
+        <div class="ocr-extract-panel">
+            <div class="ocr-extract-header">
+                <h4>"項目抽出（テンプレート）"</h4>
+                <button class="ocr-copy-btn"
+                    disabled=move || extraction.with(|e| e.is_none())
+                    on:click=move |_| {
+                        let Some(json) = extraction.with(|e| e.as_ref().map(|e| e.to_json())) else {
+                            return;
+                        };
+                        if let Some(window) = web_sys::window() {
+                            let _ = window.navigator().clipboard().write_text(&json);
+                        }
+                    }
+                >"JSONをコピー"</button>
+            </div>
+            {move || match extraction.get() {
+                None => view! {
+                    <p class="empty-state">"この書類種別の抽出ルールはありません（記入欄テンプレートを作成すると抽出できます）"</p>
+                }.into_view(),
+                Some(extraction) => view! {
+                    <table class="ocr-extract-table">
+                        <thead>
+                            <tr><th>"項目"</th><th>"キー"</th><th>"値"</th><th>"信頼度"</th></tr>
+                        </thead>
+                        <tbody>
+                            {extraction.fields.into_iter().map(|field| {
+                                let first = field.tokens.first().copied();
+                                view! {
+                                    <tr on:click=move |_| ctx.set_selected_token.set(first)>
+                                        <td>{field.label}</td>
+                                        <td class="ocr-extract-key">{field.key}</td>
+                                        <td>{field.value}</td>
+                                        <td class=confidence_class(field.confidence)>
+                                            {format!("{:.0}%", field.confidence * 100.0)}
+                                        </td>
+                                    </tr>
+                                }
+                            }).collect_view()}
+                        </tbody>
+                    </table>
+                    {(!extraction.missing.is_empty()).then(|| view! {
+                        <p class="ocr-extract-missing">{format!("未検出: {}", extraction.missing.join("、"))}</p>
+                    })}
+                }.into_view(),
+            }}
+        </div>
+    }
+}
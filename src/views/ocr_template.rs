@@ -0,0 +1,273 @@
+//! 帳票テンプレートの作成（OCRビューで記入欄の領域を描く）
+//!
+//! Canvas上のドラッグで記入欄を追加し、名前とフィールドタイプを設定する。
+//! 位置はページ内の比率で保存し、同じ書類種別（doc_type）の書類に共通で使う
+
+use leptos::*;
+use web_sys::CanvasRenderingContext2d;
+
+use super::ocr_viewer::{OcrViewContext, PageTransform};
+use crate::models::{FieldPosition, FieldType, FormTemplate, TemplateField};
+use crate::utils::audit::now_iso;
+use crate::utils::form_template::{export_form_templates, import_form_templates, save_form_templates};
+use crate::utils::ocr_extract::find_anchor;
+use crate::utils::ocr_layout::group_tokens;
+use crate::utils::{download_file, read_file_as_text};
+
+/// 記入欄とみなす最小サイズ（ページ内の比率。クリックだけの誤操作を除く）
+pub const MIN_FIELD_SIZE: f64 = 0.005;
+
+// ============================================
+// テンプレートの編集
+// ============================================
+
+/// 表示中のOCRドキュメントの書類種別
+fn current_doc_type(ctx: &OcrViewContext) -> Option<String> {
+    let idx = ctx.current_doc_index.get_untracked();
+    ctx.documents.with_untracked(|docs| docs.get(idx).map(|d| d.doc_type.clone()))
+}
+
+/// 表示中の書類種別のテンプレートの記入欄
+pub fn current_template_fields(ctx: &OcrViewContext) -> Vec<TemplateField> {
+    let idx = ctx.current_doc_index.get();
+    let doc_type = ctx.documents.with(|docs| docs.get(idx).map(|d| d.doc_type.clone()));
+    let Some(doc_type) = doc_type else {
+        return Vec::new();
+    };
+    ctx.templates.with(|ts| FormTemplate::find(ts, &doc_type).map(|t| t.fields.clone()).unwrap_or_default())
+}
+
+/// 表示中の書類種別のテンプレートを編集して保存（なければ作成）
+fn edit_template(ctx: &OcrViewContext, f: impl FnOnce(&mut FormTemplate)) {
+    let Some(doc_type) = current_doc_type(ctx) else {
+        return;
+    };
+    ctx.set_templates.update(|templates| {
+        let idx = match templates.iter().position(|t| t.doc_type == doc_type) {
+            Some(idx) => idx,
+            None => {
+                templates.push(FormTemplate { doc_type: doc_type.clone(), ..FormTemplate::default() });
+                templates.len() - 1
+            }
+        };
+        let template = &mut templates[idx];
+        f(template);
+        template.updated_at = Some(now_iso());
+    });
+    ctx.templates.with_untracked(|ts| save_form_templates(ts));
+}
+
+/// 描いた領域を記入欄として追加（表示中のページ）
+pub fn add_template_field(ctx: &OcrViewContext, position: FieldPosition) {
+    let page = ctx.current_page.get_untracked();
+    let mut added = None;
+    edit_template(ctx, |template| {
+        template.fields.push(TemplateField {
+            name: format!("項目{}", template.fields.len() + 1),
+            field_type: FieldType::Text,
+            page,
+            position,
+            key: None,
+            anchor: None,
+            anchor_position: None,
+        });
+        added = Some(template.fields.len() - 1);
+    });
+    ctx.set_selected_field.set(added);
+}
+
+/// 目印の文字列を設定し、表示中の書類での位置を記録する（抽出時は位置のずれを補正する）
+fn set_field_anchor(ctx: &OcrViewContext, i: usize, anchor: String) {
+    let idx = ctx.current_doc_index.get_untracked();
+    let located = ctx.documents.with_untracked(|docs| {
+        let doc = docs.get(idx)?;
+        let page = ctx.templates.with_untracked(|ts| FormTemplate::find(ts, &doc.doc_type)?.fields.get(i).map(|f| f.page))?;
+        find_anchor(&group_tokens(&doc.tokens), &doc.tokens, &anchor, page)
+    });
+    edit_template(ctx, |template| {
+        if let Some(field) = template.fields.get_mut(i) {
+            field.anchor = (!anchor.trim().is_empty()).then(|| anchor.clone());
+            field.anchor_position = located.clone();
+        }
+    });
+}
+
+// ============================================
+// テンプレート編集パネル
+// ============================================
+
+/// 記入欄の一覧と名前・タイプの編集、JSONの書き出し・読み込み
+#[component]
+pub fn OcrTemplatePanel() -> impl IntoView {
+    let ctx = use_context::<OcrViewContext>().expect("OcrViewContext not found");
+    let (message, set_message) = create_signal(None::<String>);
+
+    let fields = create_memo(move |_| current_template_fields(&ctx));
+    let field_count = create_memo(move |_| fields.with(|f| f.len()));
+    let read = move |i: usize, f: fn(&TemplateField) -> String| fields.with(|fs| fs.get(i).map(f).unwrap_or_default());
+    let edit_field = move |i: usize, f: &dyn Fn(&mut TemplateField)| {
+        edit_template(&ctx, |template| {
+            if let Some(field) = template.fields.get_mut(i) {
+                f(field);
+            }
+        });
+    };
+
+    let on_export = move |_| {
+        match ctx.templates.with_untracked(|ts| export_form_templates(ts)) {
+            Ok(json) => download_file("form_templates.json", "application/json", &json),
+            Err(e) => set_message.set(Some(e)),
+        }
+    };
+
+    let on_import = move |ev: web_sys::Event| {
+        let input: web_sys::HtmlInputElement = event_target(&ev);
+        if let Some(file) = input.files().and_then(|files| files.get(0)) {
+            read_file_as_text(&file, move |text| {
+                let mut result = Ok(0);
+                ctx.set_templates.update(|ts| result = import_form_templates(ts, &text));
+                match result {
+                    Ok(count) => {
+                        ctx.templates.with_untracked(|ts| save_form_templates(ts));
+                        ctx.set_selected_field.set(None);
+                        set_message.set(Some(format!("テンプレートを{}件読み込みました", count)));
+                    }
+                    Err(e) => set_message.set(Some(e)),
+                }
+            });
+        }
+        input.set_value("");
+    };
+
+    view! {
+        <div class="ocr-template-panel">
+            <div class="ocr-template-header">
+                <h4>{move || {
+                    let idx = ctx.current_doc_index.get();
+                    let doc_type = ctx.documents.with(|docs| docs.get(idx).map(|d| d.doc_type.clone()).unwrap_or_default());
+                    format!("帳票テンプレート: {}", doc_type)
+                }}</h4>
+                <button class="ocr-template-btn" on:click=on_export>"JSON書き出し"</button>
+                <label class="ocr-template-btn">
+                    "JSON読込"
+                    <input type="file" accept=".json" style="display:none" on:change=on_import />
+                </label>
+            </div>
+            <p class="hint">
+                "画像上をドラッグして記入欄を追加します。位置はページ内の比率で保存され、同じ書類種別の書類に共通で使われます。"
+                "目印の文字を設定すると、書類ごとの位置のずれを目印の位置で補正して抽出します"
+            </p>
+            {move || message.get().map(|msg| view! { <p class="ocr-template-message">{msg}</p> })}
+
+            {move || (field_count.get() == 0).then(|| view! {
+                <p class="empty-state">"記入欄はまだありません"</p>
+            })}
+
+            {move || (0..field_count.get()).map(|i| {
+                let row_class = move || {
+                    if ctx.selected_field.get() == Some(i) { "ocr-template-field selected" } else { "ocr-template-field" }
+                };
+                let select_field = move |_| {
+                    ctx.set_selected_field.set(Some(i));
+                    if let Some(page) = fields.with_untracked(|fs| fs.get(i).map(|f| f.page)) {
+                        ctx.set_current_page.set(page);
+                    }
+                };
+                view! {
+                    <div class=row_class on:click=select_field>
+                        <input type="text" placeholder="項目名"
+                            prop:value=move || read(i, |f| f.name.clone())
+                            on:input=move |ev| { let v = event_target_value(&ev); edit_field(i, &|f| f.name = v.clone()); }
+                        />
+                        <input type="text" class="ocr-template-key" placeholder="抽出キー（省略時は項目名）"
+                            prop:value=move || read(i, |f| f.key.clone().unwrap_or_default())
+                            on:change=move |ev| {
+                                let v = event_target_value(&ev);
+                                edit_field(i, &|f| f.key = (!v.trim().is_empty()).then(|| v.trim().to_string()));
+                            }
+                        />
+                        <input type="text" class="ocr-template-anchor" placeholder="目印の文字（例: 代表者）"
+                            prop:value=move || read(i, |f| f.anchor.clone().unwrap_or_default())
+                            on:change=move |ev| set_field_anchor(&ctx, i, event_target_value(&ev))
+                        />
+                        <span class="ocr-template-anchor-state">{move || {
+                            fields.with(|fs| match fs.get(i) {
+                                Some(f) if f.anchor.is_some() && f.anchor_position.is_none() => "目印が見つかりません",
+                                _ => "",
+                            })
+                        }}</span>
+                        <select on:change=move |ev| {
+                            let idx: usize = event_target_value(&ev).parse().unwrap_or(0);
+                            if let Some(field_type) = FieldType::ALL.get(idx) {
+                                edit_field(i, &|f| f.field_type = field_type.clone());
+                            }
+                        }>
+                            {FieldType::ALL.iter().enumerate().map(|(ti, field_type)| {
+                                let field_type = field_type.clone();
+                                let label = field_type.label();
+                                view! {
+                                    <option value=ti.to_string()
+                                        selected=move || fields.with(|fs| fs.get(i).is_some_and(|f| f.field_type == field_type))
+                                    >{label}</option>
+                                }
+                            }).collect_view()}
+                        </select>
+                        <span class="ocr-template-page">{move || format!("{}ページ", read(i, |f| f.page.to_string()))}</span>
+                        <button class="delete-btn small" on:click=move |ev: web_sys::MouseEvent| {
+                            ev.stop_propagation();
+                            edit_template(&ctx, |template| {
+                                if i < template.fields.len() {
+                                    template.fields.remove(i);
+                                }
+                            });
+                            ctx.set_selected_field.set(None);
+                        }>"削除"</button>
+                    </div>
+                }
+            }).collect_view()}
+        </div>
+    }
+}
+
+// ============================================
+// 描画
+// ============================================
+
+/// 記入欄（指定ページ分）と描画中の領域を描く
+pub fn draw_template_fields(
+    ctx: &CanvasRenderingContext2d,
+    transform: &PageTransform,
+    fields: &[TemplateField],
+    page: u32,
+    selected: Option<usize>,
+    draft: Option<&FieldPosition>,
+) {
+    for (i, field) in fields.iter().enumerate().filter(|(_, f)| f.page == page) {
+        let p = &field.position;
+        let (x, y, w, h) = transform.rect(p.x, p.y, p.width, p.height);
+        let is_selected = selected == Some(i);
+        let (fill_color, line_width) = if is_selected {
+            ("rgba(123, 31, 162, 0.25)", 3.0) // 選択中
+        } else {
+            ("rgba(123, 31, 162, 0.08)", 1.5)
+        };
+        ctx.set_fill_style_str(fill_color);
+        ctx.fill_rect(x, y, w, h);
+        ctx.set_stroke_style_str("#7b1fa2");
+        ctx.set_line_width(line_width);
+        ctx.stroke_rect(x, y, w, h);
+        ctx.set_fill_style_str("#7b1fa2");
+        ctx.set_font("12px sans-serif");
+        let _ = ctx.fill_text(&format!("{}（{}）", field.name, field.field_type.label()), x, y - 3.0);
+    }
+
+    if let Some(p) = draft {
+        let (x, y, w, h) = transform.rect(p.x, p.y, p.width, p.height);
+        let dash = js_sys::Array::of2(&6.0.into(), &4.0.into());
+        let _ = ctx.set_line_dash(&dash);
+        ctx.set_stroke_style_str("#7b1fa2");
+        ctx.set_line_width(2.0);
+        ctx.stroke_rect(x, y, w, h);
+        let _ = ctx.set_line_dash(&js_sys::Array::new());
+    }
+}
@@ -1,70 +1,39 @@
 //! OCR座標マッピングビュー
 //!
 //! Document AI OCRで検出したテキストの位置を可視化するビュー
+//!
+//! トークンを行・ブロックにまとめた認識テキストを表示し、検索語に一致した範囲を
+//! Canvas上で強調して順に移動できる。複数ページの書類はページごとに画像とトークンを切り替える。
+//! テンプレート編集中はCanvas上のドラッグで記入欄を描き、帳票テンプレートとして保存する。
+//! 組み込みルールとテンプレートで書類の項目（日付・宛先・代表者など）を抽出して表示し、
+//! テンプレートの記入欄のうち未記入の欄を赤で示す
+
+use std::collections::HashSet;
 
 use leptos::*;
-use serde::{Deserialize, Serialize};
 use wasm_bindgen::prelude::*;
 use wasm_bindgen::JsCast;
 use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlImageElement};
 
-// ============================================
-// OCRトークン可視化の型定義
-// ============================================
-
-/// OCRで検出されたテキストトークン
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct OcrToken {
-    pub text: String,
-    pub page: u32,
-    pub normalized: NormalizedCoords,
-    pub pixels: PixelCoords,
-    pub page_size: PageSize,
-}
-
-/// 正規化された座標 (0.0〜1.0)
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct NormalizedCoords {
-    pub x: f64,
-    pub y: f64,
-    pub width: f64,
-    pub height: f64,
-}
-
-/// ピクセル座標
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PixelCoords {
-    pub x: i32,
-    pub y: i32,
-    pub width: i32,
-    pub height: i32,
-}
-
-/// ページサイズ
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct PageSize {
-    pub width: f64,
-    pub height: f64,
-}
-
-/// OCRドキュメント
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct OcrDocument {
-    pub contractor: String,
-    pub doc_type: String,
-    pub image_url: String,
-    pub tokens: Vec<OcrToken>,
-}
+use super::ocr_blank::{draw_blank_fields, OcrBlankFieldList};
+use super::ocr_check::OcrCheckPanel;
+use super::ocr_extract::OcrExtractPanel;
+use super::ocr_template::{add_template_field, current_template_fields, draw_template_fields, OcrTemplatePanel, MIN_FIELD_SIZE};
+use crate::models::{FieldPosition, FindingLocation, FormTemplate, OcrDocument};
+use crate::utils::ocr_blank::detect_blank_fields;
+use crate::utils::ocr_import::import_ocr;
+use crate::utils::ocr_layout::{group_tokens, search, OcrLayout, OcrMatch};
+use crate::utils::read_file_as_text;
+use crate::MissingField;
 
 // ============================================
 // OCR可視化ビューのコンテキスト
 // ============================================
 
 /// OCRビューの状態管理コンテキスト
-#[derive(Clone)]
+#[derive(Clone, Copy)]
 pub struct OcrViewContext {
     pub documents: ReadSignal<Vec<OcrDocument>>,
-    #[allow(dead_code)]
     pub set_documents: WriteSignal<Vec<OcrDocument>>,
     pub current_doc_index: ReadSignal<usize>,
     pub set_current_doc_index: WriteSignal<usize>,
@@ -72,6 +41,27 @@ pub struct OcrViewContext {
     pub set_selected_token: WriteSignal<Option<usize>>,
     pub show_all_boxes: ReadSignal<bool>,
     pub set_show_all_boxes: WriteSignal<bool>,
+    /// テキスト検索の入力
+    pub search_query: ReadSignal<String>,
+    pub set_search_query: WriteSignal<String>,
+    /// 選択中の検索一致（検索結果の添字）
+    pub current_match: ReadSignal<usize>,
+    pub set_current_match: WriteSignal<usize>,
+    /// 表示中のページ（1始まり）
+    pub current_page: ReadSignal<u32>,
+    pub set_current_page: WriteSignal<u32>,
+    /// 帳票テンプレート（LocalStorageに保存）
+    pub templates: ReadSignal<Vec<FormTemplate>>,
+    pub set_templates: WriteSignal<Vec<FormTemplate>>,
+    /// テンプレート編集中（Canvasのドラッグで記入欄を追加）
+    pub template_mode: ReadSignal<bool>,
+    pub set_template_mode: WriteSignal<bool>,
+    /// 選択中の記入欄（テンプレートのfieldsの添字）
+    pub selected_field: ReadSignal<Option<usize>>,
+    pub set_selected_field: WriteSignal<Option<usize>>,
+    /// 強調表示中のチェック指摘の位置（チェック結果のクリックで設定）
+    pub highlight: ReadSignal<Option<FindingLocation>>,
+    pub set_highlight: WriteSignal<Option<FindingLocation>>,
 }
 
 // ============================================
@@ -83,6 +73,108 @@ pub struct OcrViewContext {
 pub fn OcrViewer() -> impl IntoView {
     let ctx = use_context::<OcrViewContext>().expect("OcrViewContext not found");
 
+    // 表示中ドキュメントの行・ブロック
+    let layout = create_memo(move |_| {
+        let idx = ctx.current_doc_index.get();
+        ctx.documents.with(|docs| docs.get(idx).map(|d| group_tokens(&d.tokens)).unwrap_or_default())
+    });
+
+    // 検索一致
+    let matches = create_memo(move |_| {
+        let idx = ctx.current_doc_index.get();
+        let query = ctx.search_query.get();
+        ctx.documents.with(|docs| {
+            docs.get(idx)
+                .map(|d| layout.with(|l| search(l, &d.tokens, &query)))
+                .unwrap_or_default()
+        })
+    });
+
+    // テンプレートの記入欄のうち未記入の欄
+    let blanks = create_memo(move |_| {
+        let idx = ctx.current_doc_index.get();
+        ctx.documents.with(|docs| {
+            docs.get(idx)
+                .map(|d| ctx.templates.with(|ts| layout.with(|l| detect_blank_fields(d, l, ts))))
+                .unwrap_or_default()
+        })
+    });
+
+    let (copied, set_copied) = create_signal(false);
+    let (import_message, set_import_message) = create_signal(None::<String>);
+
+    // OCR結果のファイルを読み込み、最初に読み込んだ書類を表示
+    let on_import = move |ev: web_sys::Event| {
+        let input: web_sys::HtmlInputElement = event_target(&ev);
+        let Some(files) = input.files() else {
+            return;
+        };
+        for i in 0..files.length() {
+            let Some(file) = files.get(i) else {
+                continue;
+            };
+            let filename = file.name();
+            read_file_as_text(&file, move |text| match import_ocr(&filename, &text) {
+                Ok(docs) => {
+                    let first = ctx.documents.with_untracked(|d| d.len());
+                    let count = docs.len();
+                    ctx.set_documents.update(|d| d.extend(docs));
+                    ctx.set_current_doc_index.set(first);
+                    ctx.set_selected_token.set(None);
+                    ctx.set_current_match.set(0);
+                    ctx.set_current_page.set(1);
+                    ctx.set_selected_field.set(None);
+                    set_import_message.set(Some(format!("{}から{}件の書類を読み込みました", filename, count)));
+                }
+                Err(e) => set_import_message.set(Some(e)),
+            });
+        }
+        input.set_value("");
+    };
+
+    let page_count = create_memo(move |_| {
+        let idx = ctx.current_doc_index.get();
+        ctx.documents.with(|docs| docs.get(idx).map(|d| d.page_count()).unwrap_or(1))
+    });
+
+    // トークンのあるページへ移動
+    let show_token_page = move |token_idx: usize| {
+        let idx = ctx.current_doc_index.get_untracked();
+        let page = ctx.documents.with_untracked(|docs| docs.get(idx).and_then(|d| d.tokens.get(token_idx)).map(|t| t.page));
+        if let Some(page) = page {
+            if page != ctx.current_page.get_untracked() {
+                ctx.set_current_page.set(page);
+            }
+        }
+    };
+
+    // 選択中のトークン（一覧・認識テキスト・全体検索から）のページを表示
+    create_effect(move |_| {
+        if let Some(token_idx) = ctx.selected_token.get() {
+            show_token_page(token_idx);
+        }
+    });
+
+    // 選択中の検索一致のページを表示
+    create_effect(move |_| {
+        let current = ctx.current_match.get();
+        let first = matches.with(|m| m.get(current.min(m.len().saturating_sub(1))).and_then(|m| m.tokens.first().copied()));
+        if let Some(token_idx) = first {
+            show_token_page(token_idx);
+        }
+    });
+
+    // 一致間の移動（末尾の次は先頭へ）
+    let step_match = move |forward: bool| {
+        let count = matches.with(|m| m.len());
+        if count == 0 {
+            return;
+        }
+        ctx.set_current_match.update(|i| {
+            *i = if forward { (*i + 1) % count } else { (*i + count - 1) % count };
+        });
+    };
+
     view! {
         <div class="ocr-viewer">
             <div class="ocr-header">
@@ -100,6 +192,9 @@ pub fn OcrViewer() -> impl IntoView {
                     let idx: usize = event_target_value(&ev).parse().unwrap_or(0);
                     ctx.set_current_doc_index.set(idx);
                     ctx.set_selected_token.set(None);
+                    ctx.set_current_match.set(0);
+                    ctx.set_current_page.set(1);
+                    ctx.set_selected_field.set(None);
                 }>
                     {move || ctx.documents.get().iter().enumerate().map(|(i, doc)| {
                         view! {
@@ -117,16 +212,125 @@ pub fn OcrViewer() -> impl IntoView {
                     />
                     "全ボックス表示"
                 </label>
+
+                <label class="checkbox-label">
+                    <input type="checkbox"
+                        prop:checked=move || ctx.template_mode.get()
+                        on:change=move |ev| {
+                            ctx.set_template_mode.set(event_target_checked(&ev));
+                            ctx.set_selected_field.set(None);
+                        }
+                    />
+                    "記入欄テンプレート編集"
+                </label>
+
+                <label class="ocr-import-btn" title="Document AI JSON / hOCR / ALTO XML / OCR書類JSON">
+                    "OCR結果を読込"
+                    <input type="file" multiple accept=".json,.hocr,.html,.htm,.xml" style="display:none" on:change=on_import />
+                </label>
+
+                // テキスト検索
+                <div class="ocr-search">
+                    <input type="search"
+                        placeholder="テキスト検索"
+                        prop:value=move || ctx.search_query.get()
+                        on:input=move |ev| {
+                            ctx.set_search_query.set(event_target_value(&ev));
+                            ctx.set_current_match.set(0);
+                        }
+                        on:keydown=move |ev: web_sys::KeyboardEvent| {
+                            if ev.key() == "Enter" {
+                                ev.prevent_default();
+                                step_match(!ev.shift_key());
+                            }
+                        }
+                    />
+                    <button class="ocr-search-step" title="前の一致（Shift+Enter）"
+                        disabled=move || matches.with(|m| m.is_empty())
+                        on:click=move |_| step_match(false)
+                    >"▲"</button>
+                    <button class="ocr-search-step" title="次の一致（Enter）"
+                        disabled=move || matches.with(|m| m.is_empty())
+                        on:click=move |_| step_match(true)
+                    >"▼"</button>
+                    <span class="ocr-search-count">
+                        {move || {
+                            let count = matches.with(|m| m.len());
+                            if ctx.search_query.with(|q| q.trim().is_empty()) {
+                                String::new()
+                            } else if count == 0 {
+                                "該当なし".to_string()
+                            } else {
+                                format!("{} / {}件", ctx.current_match.get().min(count - 1) + 1, count)
+                            }
+                        }}
+                    </span>
+                </div>
             </div>
 
+            {move || import_message.get().map(|msg| view! { <p class="ocr-import-message">{msg}</p> })}
+
+            // ページ切替（複数ページの書類のみ）
+            <Show when=move || { page_count.get() > 1 }>
+                <OcrPageNav matches=matches />
+            </Show>
+
             // Canvas表示エリア
             <div class="ocr-canvas-container">
-                <OcrCanvas />
+                <OcrCanvas matches=matches blanks=blanks />
+            </div>
+
+            // 未記入の記入欄（帳票テンプレートとの比較）
+            <OcrBlankFieldList blanks=blanks />
+
+            // 帳票テンプレート（編集中のみ）
+            <Show when=move || ctx.template_mode.get()>
+                <OcrTemplatePanel />
+            </Show>
+
+            // 項目抽出（組み込みルール・帳票テンプレート）
+            <OcrExtractPanel layout=layout />
+
+            // 書類チェック（ローカルルール・モック）
+            <OcrCheckPanel />
+
+            // 認識テキスト（行・ブロック）
+            <div class="ocr-text-view">
+                <div class="ocr-text-header">
+                    <h4>"認識テキスト"</h4>
+                    <button class="ocr-copy-btn"
+                        disabled=move || layout.with(|l| l.blocks.is_empty())
+                        on:click=move |_| {
+                            if let Some(window) = web_sys::window() {
+                                let promise = window.navigator().clipboard().write_text(&layout.with(|l| l.text()));
+                                spawn_local(async move {
+                                    if wasm_bindgen_futures::JsFuture::from(promise).await.is_ok() {
+                                        set_copied.set(true);
+                                        gloo::timers::future::TimeoutFuture::new(2000).await;
+                                        set_copied.set(false);
+                                    }
+                                });
+                            }
+                        }
+                    >
+                        {move || if copied.get() { "コピーしました" } else { "テキストをコピー" }}
+                    </button>
+                </div>
+                {move || layout.with(|l| {
+                    if l.blocks.is_empty() {
+                        return view! { <p class="ocr-text-empty">"テキストがありません"</p> }.into_view();
+                    }
+                    render_layout(&ctx, l, &matches.get(), page_count.get() > 1)
+                })}
             </div>
 
             // トークン一覧
-            <div class="ocr-token-list">
-                <h4>"検出テキスト一覧"</h4>
+            <details class="ocr-token-list">
+                <summary>{move || {
+                    let idx = ctx.current_doc_index.get();
+                    let count = ctx.documents.with(|docs| docs.get(idx).map(|d| d.tokens.len()).unwrap_or(0));
+                    format!("検出トークン一覧（{}件）", count)
+                }}</summary>
                 <div class="token-grid">
                     {move || {
                         let docs = ctx.documents.get();
@@ -152,7 +356,7 @@ pub fn OcrViewer() -> impl IntoView {
                         }
                     }}
                 </div>
-            </div>
+            </details>
 
             // 選択中トークンの詳細
             {move || {
@@ -161,11 +365,11 @@ pub fn OcrViewer() -> impl IntoView {
                 let token_idx = ctx.selected_token.get();
 
                 if let (Some(doc), Some(t_idx)) = (docs.get(doc_idx), token_idx) {
-                    if let Some(token) = doc.tokens.get(t_idx) {
-                        Some(view! {
+                    doc.tokens.get(t_idx).map(|token| view! {
                             <div class="token-detail">
                                 <h4>"選択中: \"" {token.text.clone()} "\""</h4>
                                 <table>
+                                    <tr><td>"ページ"</td><td>{token.page}</td></tr>
                                     <tr><td>"正規化座標"</td><td>{format!("x: {:.4}, y: {:.4}", token.normalized.x, token.normalized.y)}</td></tr>
                                     <tr><td>"サイズ"</td><td>{format!("w: {:.4}, h: {:.4}", token.normalized.width, token.normalized.height)}</td></tr>
                                     <tr><td>"ピクセル座標"</td><td>{format!("x: {}, y: {}", token.pixels.x, token.pixels.y)}</td></tr>
@@ -173,20 +377,135 @@ pub fn OcrViewer() -> impl IntoView {
                                 </table>
                             </div>
                         })
-                    } else { None }
                 } else { None }
             }}
         </div>
     }
 }
 
+/// 認識テキストを表示（検索に一致した行・選択中のトークンを含む語を強調、複数ページはページ見出し付き）
+fn render_layout(ctx: &OcrViewContext, layout: &OcrLayout, matches: &[OcrMatch], multi_page: bool) -> View {
+    let current = ctx.current_match.get().min(matches.len().saturating_sub(1));
+    let matched: HashSet<usize> = matches.iter().flat_map(|m| m.tokens.iter().copied()).collect();
+    let current_tokens: &[usize] = matches.get(current).map(|m| m.tokens.as_slice()).unwrap_or(&[]);
+    let selected = ctx.selected_token.get();
+    let set_selected_token = ctx.set_selected_token;
+    let set_current_page = ctx.set_current_page;
+
+    layout.blocks.iter().enumerate().map(|(b, block)| view! {
+        {(multi_page && (b == 0 || layout.blocks[b - 1].page != block.page)).then(|| {
+            let page = block.page;
+            view! {
+                <div class="ocr-page-label" on:click=move |_| set_current_page.set(page)>
+                    {format!("{}ページ", page)}
+                </div>
+            }
+        })}
+        <div class="ocr-block">
+            {block.lines.iter().map(|line| {
+                let mut class = "ocr-line".to_string();
+                if line.tokens().any(|i| matched.contains(&i)) {
+                    class.push_str(" match");
+                }
+                if line.tokens().any(|i| current_tokens.contains(&i)) {
+                    class.push_str(" current");
+                }
+                view! {
+                    <div class=class>
+                        {line.words.iter().map(|word| {
+                            let first = word.tokens[0];
+                            let is_selected = selected.is_some_and(|s| word.tokens.contains(&s));
+                            view! {
+                                <span
+                                    class=if is_selected { "ocr-word selected" } else { "ocr-word" }
+                                    on:click=move |_| set_selected_token.set(Some(first))
+                                >
+                                    {word.text.clone()}
+                                </span>
+                            }
+                        }).collect_view()}
+                    </div>
+                }
+            }).collect_view()}
+        </div>
+    }).collect_view()
+}
+
+// ============================================
+// ページ切替
+// ============================================
+
+/// ページ送りとサムネイル（検索一致のあるページは件数を表示）
+#[component]
+fn OcrPageNav(matches: Memo<Vec<OcrMatch>>) -> impl IntoView {
+    let ctx = use_context::<OcrViewContext>().expect("OcrViewContext not found");
+
+    // ページごとの画像と検索一致の件数
+    let pages = move || {
+        let idx = ctx.current_doc_index.get();
+        ctx.documents.with(|docs| {
+            let Some(doc) = docs.get(idx) else {
+                return Vec::new();
+            };
+            matches.with(|m| {
+                (1..=doc.page_count())
+                    .map(|page| {
+                        let hits = m
+                            .iter()
+                            .filter(|hit| hit.tokens.first().and_then(|&t| doc.tokens.get(t)).is_some_and(|t| t.page == page))
+                            .count();
+                        (page, doc.page_image(page).map(str::to_string), hits)
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+    };
+    let page_count = move || pages().len() as u32;
+
+    view! {
+        <div class="ocr-pages">
+            <div class="ocr-page-nav">
+                <button
+                    disabled=move || ctx.current_page.get() <= 1
+                    on:click=move |_| ctx.set_current_page.update(|p| *p = p.saturating_sub(1).max(1))
+                >"‹ 前"</button>
+                <span class="ocr-page-indicator">
+                    {move || format!("{} / {}ページ", ctx.current_page.get(), page_count())}
+                </span>
+                <button
+                    disabled=move || ctx.current_page.get() >= page_count()
+                    on:click=move |_| {
+                        let last = page_count();
+                        ctx.set_current_page.update(|p| *p = (*p + 1).min(last));
+                    }
+                >"次 ›"</button>
+            </div>
+            <div class="ocr-thumbs">
+                {move || pages().into_iter().map(|(page, image, hits)| {
+                    let class = move || if ctx.current_page.get() == page { "ocr-thumb active" } else { "ocr-thumb" };
+                    view! {
+                        <button class=class title=format!("{}ページ", page) on:click=move |_| ctx.set_current_page.set(page)>
+                            {match image {
+                                Some(url) => view! { <img src=url loading="lazy" alt="" /> }.into_view(),
+                                None => view! { <span class="ocr-thumb-blank">"画像なし"</span> }.into_view(),
+                            }}
+                            <span class="ocr-thumb-label">{page}</span>
+                            {(hits > 0).then(|| view! { <span class="ocr-thumb-hits">{hits}</span> })}
+                        </button>
+                    }
+                }).collect_view()}
+            </div>
+        </div>
+    }
+}
+
 // ============================================
 // OCR Canvas コンポーネント
 // ============================================
 
-/// OCRトークンを描画するCanvas
+/// OCRトークンを描画するCanvas（検索一致・未記入の記入欄を強調）
 #[component]
-pub fn OcrCanvas() -> impl IntoView {
+pub fn OcrCanvas(matches: Memo<Vec<OcrMatch>>, blanks: Memo<Vec<MissingField>>) -> impl IntoView {
     let ctx = use_context::<OcrViewContext>().expect("OcrViewContext not found");
     let canvas_ref = create_node_ref::<leptos::html::Canvas>();
 
@@ -195,38 +514,101 @@ pub fn OcrCanvas() -> impl IntoView {
     // 現在読み込み中の画像URL
     let (loading_url, set_loading_url) = create_signal::<String>(String::new());
 
-    // 画像読み込みエフェクト
+    // 画像読み込みエフェクト（表示中のページ）
     create_effect(move |_| {
-        let docs = ctx.documents.get();
         let doc_idx = ctx.current_doc_index.get();
-
-        if let Some(doc) = docs.get(doc_idx) {
-            let image_url = doc.image_url.clone();
-
+        let page = ctx.current_page.get();
+        let image_url = ctx.documents.with(|docs| {
+            docs.get(doc_idx).and_then(|d| d.page_image(page)).map(str::to_string).unwrap_or_default()
+        });
+
+        if image_url.is_empty() {
+            // 画像のないページ
+            set_loading_url.set(String::new());
+            set_loaded_image.set(None);
+        } else if image_url != loading_url.get_untracked() {
             // 新しい画像URLなら読み込み開始
-            if !image_url.is_empty() && image_url != loading_url.get_untracked() {
-                set_loading_url.set(image_url.clone());
-                set_loaded_image.set(None);
+            set_loading_url.set(image_url.clone());
+            set_loaded_image.set(None);
 
-                // 画像エレメントを作成
-                if let Ok(img) = HtmlImageElement::new() {
-                    let _set_img = set_loaded_image.clone();
+            // 画像エレメントを作成
+            if let Ok(img) = HtmlImageElement::new() {
+                let img_loaded = img.clone();
+                let expected_url = image_url.clone();
 
-                    // onloadコールバック
-                    let onload = Closure::wrap(Box::new(move |_: web_sys::Event| {
-                        // 画像読み込み完了 - 再描画トリガー
-                    }) as Box<dyn FnMut(_)>);
+                // onloadコールバック（読み込み完了で再描画。ページを切り替え済みなら破棄）
+                let onload = Closure::wrap(Box::new(move |_: web_sys::Event| {
+                    if loading_url.get_untracked() == expected_url {
+                        set_loaded_image.set(Some(img_loaded.clone()));
+                    }
+                }) as Box<dyn FnMut(_)>);
 
-                    img.set_onload(Some(onload.as_ref().unchecked_ref()));
-                    onload.forget();
+                img.set_onload(Some(onload.as_ref().unchecked_ref()));
+                onload.forget();
 
-                    img.set_src(&image_url);
-                    set_loaded_image.set(Some(img));
-                }
+                img.set_src(&image_url);
             }
         }
     });
 
+    // 記入欄として描画中の領域（ページ内の比率。始点と現在位置）
+    let (draft, set_draft) = create_signal(None::<(f64, f64, f64, f64)>);
+
+    // マウス位置をページ内の比率に変換（表示サイズとCanvasの解像度の違いを補正）
+    let to_page_point = move |ev: &web_sys::MouseEvent| -> Option<(f64, f64)> {
+        let canvas = canvas_ref.get_untracked()?;
+        let idx = ctx.current_doc_index.get_untracked();
+        let page = ctx.current_page.get_untracked();
+        let page_size = ctx.documents.with_untracked(|docs| docs.get(idx).map(|d| d.page_size(page)))?;
+        let bounds = canvas.get_bounding_client_rect();
+        if bounds.width() <= 0.0 || bounds.height() <= 0.0 {
+            return None;
+        }
+        let x = (ev.client_x() as f64 - bounds.left()) * canvas.width() as f64 / bounds.width();
+        let y = (ev.client_y() as f64 - bounds.top()) * canvas.height() as f64 / bounds.height();
+        Some(PageTransform::new(&canvas, page_size).to_normalized(x, y))
+    };
+
+    let on_mouse_down = move |ev: web_sys::MouseEvent| {
+        if !ctx.template_mode.get_untracked() {
+            return;
+        }
+        if let Some((x, y)) = to_page_point(&ev) {
+            ev.prevent_default();
+            set_draft.set(Some((x, y, x, y)));
+        }
+    };
+
+    let on_mouse_move = move |ev: web_sys::MouseEvent| {
+        if draft.with_untracked(|d| d.is_none()) {
+            return;
+        }
+        if let Some((x, y)) = to_page_point(&ev) {
+            set_draft.update(|d| {
+                if let Some(d) = d {
+                    d.2 = x;
+                    d.3 = y;
+                }
+            });
+        }
+    };
+
+    let on_mouse_up = move |_: web_sys::MouseEvent| {
+        let Some((x0, y0, x1, y1)) = draft.get_untracked() else {
+            return;
+        };
+        set_draft.set(None);
+        let position = FieldPosition {
+            x: x0.min(x1),
+            y: y0.min(y1),
+            width: (x1 - x0).abs(),
+            height: (y1 - y0).abs(),
+        };
+        if position.width >= MIN_FIELD_SIZE && position.height >= MIN_FIELD_SIZE {
+            add_template_field(&ctx, position);
+        }
+    };
+
     // Canvas描画エフェクト
     create_effect(move |_| {
         let docs = ctx.documents.get();
@@ -234,11 +616,35 @@ pub fn OcrCanvas() -> impl IntoView {
         let show_all = ctx.show_all_boxes.get();
         let selected = ctx.selected_token.get();
         let img = loaded_image.get();
+        let matches = matches.get();
+        let current = ctx.current_match.get();
+        let page = ctx.current_page.get();
+        let highlight = ctx.highlight.get();
 
         if let Some(doc) = docs.get(doc_idx) {
             if let Some(canvas) = canvas_ref.get() {
                 let canvas_el: &HtmlCanvasElement = &canvas;
-                draw_ocr_canvas(canvas_el, doc, show_all, selected, img.as_ref());
+                // トークン・検索一致・記入欄・指摘箇所はすべて同じ変換で描く
+                let transform = PageTransform::new(canvas_el, doc.page_size(page));
+                draw_ocr_canvas(canvas_el, &transform, doc, page, show_all, selected, img.as_ref());
+                draw_search_matches(canvas_el, &transform, doc, page, &matches, current);
+                if ctx.template_mode.get() {
+                    let fields = current_template_fields(&ctx);
+                    let draft = draft.get().map(|(x0, y0, x1, y1)| FieldPosition {
+                        x: x0.min(x1),
+                        y: y0.min(y1),
+                        width: (x1 - x0).abs(),
+                        height: (y1 - y0).abs(),
+                    });
+                    if let Some(ctx_2d) = context_2d(canvas_el) {
+                        draw_template_fields(&ctx_2d, &transform, &fields, page, ctx.selected_field.get(), draft.as_ref());
+                    }
+                } else if let Some(ctx_2d) = context_2d(canvas_el) {
+                    blanks.with(|b| draw_blank_fields(&ctx_2d, &transform, b, page));
+                    if let Some(at) = highlight.as_ref().filter(|at| at.page == page) {
+                        draw_finding_highlight(&ctx_2d, &transform, at);
+                    }
+                }
             }
         }
     });
@@ -246,9 +652,13 @@ pub fn OcrCanvas() -> impl IntoView {
     view! {
         <canvas
             node_ref=canvas_ref
-            class="ocr-canvas"
+            class=move || if ctx.template_mode.get() { "ocr-canvas template-mode" } else { "ocr-canvas" }
             width="800"
             height="1130"
+            on:mousedown=on_mouse_down
+            on:mousemove=on_mouse_move
+            on:mouseup=on_mouse_up
+            on:mouseleave=move |_| set_draft.set(None)
         />
     }
 }
@@ -257,10 +667,67 @@ pub fn OcrCanvas() -> impl IntoView {
 // 描画関数
 // ============================================
 
-/// CanvasにOCRトークンを描画
+/// ページ内の比率とCanvas座標の変換（ページをCanvas中央に収める）
+pub struct PageTransform {
+    scale: f64,
+    offset_x: f64,
+    offset_y: f64,
+    page_width: f64,
+    page_height: f64,
+}
+
+impl PageTransform {
+    pub fn new(canvas: &HtmlCanvasElement, page_size: (f64, f64)) -> Self {
+        let canvas_width = canvas.width() as f64;
+        let canvas_height = canvas.height() as f64;
+        let scale = (canvas_width / page_size.0).min(canvas_height / page_size.1);
+        PageTransform {
+            scale,
+            offset_x: (canvas_width - page_size.0 * scale) / 2.0,
+            offset_y: (canvas_height - page_size.1 * scale) / 2.0,
+            page_width: page_size.0,
+            page_height: page_size.1,
+        }
+    }
+
+    /// ページ内の比率の矩形をCanvas座標に
+    pub fn rect(&self, x: f64, y: f64, width: f64, height: f64) -> (f64, f64, f64, f64) {
+        (
+            self.offset_x + x * self.page_width * self.scale,
+            self.offset_y + y * self.page_height * self.scale,
+            width * self.page_width * self.scale,
+            height * self.page_height * self.scale,
+        )
+    }
+
+    /// ページ全体のCanvas上の矩形
+    pub fn page_rect(&self) -> (f64, f64, f64, f64) {
+        self.rect(0.0, 0.0, 1.0, 1.0)
+    }
+
+    /// Canvas座標をページ内の比率に（ページ外はページの端に寄せる）
+    pub fn to_normalized(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            ((x - self.offset_x) / (self.page_width * self.scale)).clamp(0.0, 1.0),
+            ((y - self.offset_y) / (self.page_height * self.scale)).clamp(0.0, 1.0),
+        )
+    }
+}
+
+fn context_2d(canvas: &HtmlCanvasElement) -> Option<CanvasRenderingContext2d> {
+    canvas
+        .get_context("2d")
+        .ok()
+        .flatten()
+        .and_then(|c| c.dyn_into::<CanvasRenderingContext2d>().ok())
+}
+
+/// CanvasにOCRトークンを描画（指定ページのトークンのみ）
 fn draw_ocr_canvas(
     canvas: &HtmlCanvasElement,
+    transform: &PageTransform,
     doc: &OcrDocument,
+    page: u32,
     show_all: bool,
     selected: Option<usize>,
     background_img: Option<&HtmlImageElement>,
@@ -279,48 +746,29 @@ fn draw_ocr_canvas(
         ctx.set_fill_style_str("#f5f5f5");
         ctx.fill_rect(0.0, 0.0, canvas_width, canvas_height);
 
-        // ページサイズを取得（最初のトークンから）
-        let page_size = doc
-            .tokens
-            .first()
-            .map(|t| (t.page_size.width, t.page_size.height))
-            .unwrap_or((1681.0, 2378.0));
-
-        // スケール計算
-        let scale_x = canvas_width / page_size.0;
-        let scale_y = canvas_height / page_size.1;
-        let scale = scale_x.min(scale_y);
-
-        // オフセット（センタリング）
-        let offset_x = (canvas_width - page_size.0 * scale) / 2.0;
-        let offset_y = (canvas_height - page_size.1 * scale) / 2.0;
+        // ページの位置（センタリング）
+        let (page_x, page_y, page_w, page_h) = transform.page_rect();
 
         // 背景画像を描画（ある場合）
         if let Some(img) = background_img {
             if img.complete() && img.natural_width() > 0 {
-                let _ = ctx.draw_image_with_html_image_element_and_dw_and_dh(
-                    img,
-                    offset_x,
-                    offset_y,
-                    page_size.0 * scale,
-                    page_size.1 * scale,
-                );
+                let _ = ctx.draw_image_with_html_image_element_and_dw_and_dh(img, page_x, page_y, page_w, page_h);
             } else {
                 ctx.set_fill_style_str("#ffffff");
-                ctx.fill_rect(offset_x, offset_y, page_size.0 * scale, page_size.1 * scale);
+                ctx.fill_rect(page_x, page_y, page_w, page_h);
             }
         } else {
             ctx.set_fill_style_str("#ffffff");
-            ctx.fill_rect(offset_x, offset_y, page_size.0 * scale, page_size.1 * scale);
+            ctx.fill_rect(page_x, page_y, page_w, page_h);
         }
 
         // ページ境界線
         ctx.set_stroke_style_str("#cccccc");
         ctx.set_line_width(1.0);
-        ctx.stroke_rect(offset_x, offset_y, page_size.0 * scale, page_size.1 * scale);
+        ctx.stroke_rect(page_x, page_y, page_w, page_h);
 
         // トークンを描画
-        for (i, token) in doc.tokens.iter().enumerate() {
+        for (i, token) in doc.tokens.iter().enumerate().filter(|(_, t)| t.page == page) {
             let is_selected = selected == Some(i);
             let is_marker = token.text == "御"
                 || token.text == "中"
@@ -337,10 +785,8 @@ fn draw_ocr_canvas(
                 continue;
             }
 
-            let x = offset_x + token.normalized.x * page_size.0 * scale;
-            let y = offset_y + token.normalized.y * page_size.1 * scale;
-            let w = token.normalized.width * page_size.0 * scale;
-            let h = token.normalized.height * page_size.1 * scale;
+            let n = &token.normalized;
+            let (x, y, w, h) = transform.rect(n.x, n.y, n.width, n.height);
 
             // 色設定
             let (stroke_color, fill_color, line_width) = if is_selected {
@@ -381,5 +827,71 @@ fn draw_ocr_canvas(
 
         ctx.set_fill_style_str("#ff0000");
         let _ = ctx.fill_text("■ 選択中", 10.0, 80.0);
+
+        ctx.set_fill_style_str("#f9a825");
+        let _ = ctx.fill_text("■ 検索一致（橙: 選択中の一致）", 10.0, 100.0);
+
+        ctx.set_fill_style_str("#e53935");
+        let _ = ctx.fill_text("□ 未記入の記入欄（赤破線）", 10.0, 120.0);
+
+        ctx.set_fill_style_str("#8e24aa");
+        let _ = ctx.fill_text("■ チェックの指摘箇所", 10.0, 140.0);
+    }
+}
+
+/// チェック結果で選択した指摘箇所を強調（領域がなければページ全体を囲む）
+fn draw_finding_highlight(ctx: &CanvasRenderingContext2d, transform: &PageTransform, at: &FindingLocation) {
+    let (x, y, w, h) = match &at.bbox {
+        Some(b) => transform.rect(b.x, b.y, b.width, b.height),
+        None => transform.rect(0.0, 0.0, 1.0, 1.0),
+    };
+    ctx.set_fill_style_str("rgba(142, 36, 170, 0.18)");
+    ctx.fill_rect(x - 4.0, y - 4.0, w + 8.0, h + 8.0);
+    ctx.set_stroke_style_str("#8e24aa");
+    ctx.set_line_width(3.0);
+    ctx.stroke_rect(x - 4.0, y - 4.0, w + 8.0, h + 8.0);
+    ctx.set_fill_style_str("#8e24aa");
+    ctx.set_font("bold 12px sans-serif");
+    let _ = ctx.fill_text("指摘箇所", x - 4.0, y - 8.0);
+}
+
+/// 検索に一致した範囲を強調（指定ページの一致ごとにトークンを囲む矩形）
+fn draw_search_matches(
+    canvas: &HtmlCanvasElement,
+    transform: &PageTransform,
+    doc: &OcrDocument,
+    page: u32,
+    matches: &[OcrMatch],
+    current: usize,
+) {
+    if matches.is_empty() {
+        return;
+    }
+    let Some(ctx) = context_2d(canvas) else {
+        return;
+    };
+
+    let current = current.min(matches.len() - 1);
+
+    for (i, m) in matches.iter().enumerate() {
+        let rects = m.tokens.iter().filter_map(|&t| doc.tokens.get(t)).filter(|t| t.page == page).map(|token| {
+            let n = &token.normalized;
+            (n.x, n.y, n.x + n.width, n.y + n.height)
+        });
+        let Some((left, top, right, bottom)) = rects.reduce(|a, b| (a.0.min(b.0), a.1.min(b.1), a.2.max(b.2), a.3.max(b.3))) else {
+            continue;
+        };
+        let (x, y, w, h) = transform.rect(left, top, right - left, bottom - top);
+
+        let (stroke_color, fill_color, line_width) = if i == current {
+            ("#e65100", "rgba(255, 152, 0, 0.45)", 3.0) // 橙: 選択中の一致
+        } else {
+            ("#f9a825", "rgba(255, 235, 59, 0.4)", 1.5) // 黄: 一致
+        };
+        ctx.set_fill_style_str(fill_color);
+        ctx.fill_rect(x - 2.0, y - 2.0, w + 4.0, h + 4.0);
+        ctx.set_stroke_style_str(stroke_color);
+        ctx.set_line_width(line_width);
+        ctx.stroke_rect(x - 2.0, y - 2.0, w + 4.0, h + 4.0);
     }
 }
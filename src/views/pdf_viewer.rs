@@ -1,10 +1,13 @@
 //! PDFビューワコンポーネント
+//!
+//! 保存済みのAIチェック結果を横に表示し、位置付きの指摘をクリックすると
+//! ビューワ（iframe）をそのページで開き直して該当箇所を強調する
 
 use leptos::*;
-use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsCast;
 
-use crate::models::{CheckResultData, ViewMode};
+use super::check_panel::{checklist_lines, locatable_class, locate_handler, location_tag, ChecklistDetails};
+use crate::models::{CheckResultData, FindingLocation, ViewMode};
+use crate::utils::editor_protocol::{on_editor_message, EditorMessage, EditorMode, EditorUrl};
 use crate::utils::gas::get_gas_url;
 use crate::ProjectContext;
 
@@ -38,6 +41,8 @@ pub fn PdfViewer(
     url: String,
     doc_key: String,
     contractor_id: String,
+    /// 開いた直後に表示する指摘箇所
+    locate: Option<FindingLocation>,
 ) -> impl IntoView {
     let ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
     let set_view_mode = ctx.set_view_mode;
@@ -63,80 +68,72 @@ pub fn PdfViewer(
         });
     }
 
-    let on_back = {
-        let set_check_result_tooltip = set_check_result_tooltip.clone();
-        move |_: web_sys::MouseEvent| {
-            // 戻る時にホバー状態をリセット
-            set_check_result_tooltip.set(crate::CheckResultTooltipState::default());
-            set_view_mode.set(ViewMode::Dashboard);
-        }
+    let on_back = move |_: web_sys::MouseEvent| {
+        // 戻る時にホバー状態をリセット
+        set_check_result_tooltip.set(crate::CheckResultTooltipState::default());
+        set_view_mode.set(ViewMode::Dashboard);
     };
 
     // ローカルパス検出（H:\, C:\, /Users/ など）
     let is_local_path = url.contains(":\\") || url.starts_with("/Users/") || url.starts_with("/home/");
 
+    // 表示中の指摘箇所（変わるとiframeをそのページで開き直す）
+    let (locate, set_locate) = create_signal(locate);
+    let on_locate = Callback::new(move |at: FindingLocation| set_locate.set(Some(at)));
+
     // React viewer用のiframe URL構築
-    let iframe_url = if is_local_path {
-        String::new()
-    } else {
-        let file_id = extract_drive_file_id(&url).unwrap_or_default();
-        let gas_url = get_gas_url().unwrap_or_default();
-        format!(
-            "editor/index.html?mode=view&fileId={}&docType={}&contractor={}&gasUrl={}&contractorId={}&docKey={}",
-            js_sys::encode_uri_component(&file_id),
-            js_sys::encode_uri_component(&doc_type),
-            js_sys::encode_uri_component(&contractor),
-            js_sys::encode_uri_component(&gas_url),
-            js_sys::encode_uri_component(&contractor_id),
-            js_sys::encode_uri_component(&doc_key)
-        )
+    let viewer_url = EditorUrl::new(EditorMode::View)
+        .file_id(&extract_drive_file_id(&url).unwrap_or_default())
+        .doc(&contractor, &contractor_id, &doc_type, &doc_key)
+        .gas_url(get_gas_url().as_deref());
+    let iframe_url = move || viewer_url.clone().locate(locate.get().as_ref()).build();
+
+    // この書類の保存済みAIチェック結果
+    let check_result = {
+        let contractor_id = contractor_id.clone();
+        let doc_key = doc_key.clone();
+        move || {
+            ctx.project.with(|p| {
+                p.as_ref()?
+                    .contractors
+                    .iter()
+                    .find(|c| c.id == contractor_id)?
+                    .docs
+                    .get(&doc_key)?
+                    .check_result
+                    .clone()
+            })
+        }
     };
 
     let url_display = url.clone();
 
-    // postMessage ハンドラ（viewer-back, viewer-edit）
+    // iframeからのメッセージ（viewer-back, viewer-edit）
     // AIチェックはReact側でインライン実行するため、viewer-checkは不要
     {
-        let set_view_mode = ctx.set_view_mode.clone();
-        let contractor_for_msg = contractor.clone();
-        let doc_type_for_msg = doc_type.clone();
-        let url_for_msg = url.clone();
-
-        create_effect(move |_| {
-            let set_view_mode = set_view_mode.clone();
-            let contractor = contractor_for_msg.clone();
-            let doc_type = doc_type_for_msg.clone();
-            let url = url_for_msg.clone();
-
-            let handler = Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
-                if let Ok(data) = event.data().dyn_into::<js_sys::Object>() {
-                    if let Some(msg_type) = js_sys::Reflect::get(&data, &"type".into())
-                        .ok()
-                        .and_then(|v| v.as_string())
-                    {
-                        match msg_type.as_str() {
-                            "viewer-back" => {
-                                // 戻る時にホバー状態をリセット
-                                set_check_result_tooltip.set(crate::CheckResultTooltipState::default());
-                                set_view_mode.set(ViewMode::Dashboard);
-                            }
-                            "viewer-edit" => {
-                                set_view_mode.set(ViewMode::PdfEditor {
-                                    contractor: contractor.clone(),
-                                    doc_type: doc_type.clone(),
-                                    original_url: url.clone(),
-                                });
-                            }
-                            // AIチェックはReact側でインライン実行するため、画面遷移は不要
-                            _ => {}
-                        }
-                    }
-                }
-            }) as Box<dyn FnMut(_)>);
+        let set_view_mode = ctx.set_view_mode;
+        let contractor = contractor.clone();
+        let doc_type = doc_type.clone();
+        let url = url.clone();
+        let doc_key = doc_key.clone();
+        let contractor_id = contractor_id.clone();
 
-            let window = web_sys::window().unwrap();
-            let _ = window.add_event_listener_with_callback("message", handler.as_ref().unchecked_ref());
-            handler.forget();
+        on_editor_message(move |message| match message {
+            EditorMessage::ViewerBack => {
+                // 戻る時にホバー状態をリセット
+                set_check_result_tooltip.set(crate::CheckResultTooltipState::default());
+                set_view_mode.set(ViewMode::Dashboard);
+            }
+            EditorMessage::ViewerEdit => {
+                set_view_mode.set(ViewMode::PdfEditor {
+                    contractor: contractor.clone(),
+                    doc_type: doc_type.clone(),
+                    original_url: url.clone(),
+                    doc_key: doc_key.clone(),
+                    contractor_id: contractor_id.clone(),
+                });
+            }
+            _ => {}
         });
     }
 
@@ -154,11 +151,18 @@ pub fn PdfViewer(
                 }.into_view()
             } else {
                 view! {
-                    <iframe
-                        src=iframe_url
-                        class="pdf-frame"
-                        style="width: 100%; height: 100vh; border: none;"
-                    ></iframe>
+                    <div class="pdf-viewer-body">
+                        <iframe
+                            src=iframe_url
+                            class="pdf-frame"
+                            style="width: 100%; height: 100vh; border: none;"
+                        ></iframe>
+                        {move || check_result().map(|result| view! {
+                            <aside class="viewer-check-result">
+                                <ViewerCheckResultPanel result=result doc_key=doc_key.clone() on_locate=on_locate />
+                            </aside>
+                        })}
+                    </div>
                 }.into_view()
             }}
         </div>
@@ -170,7 +174,13 @@ pub fn PdfViewer(
 // ============================================
 
 #[component]
-pub fn ViewerCheckResultPanel(result: CheckResultData) -> impl IntoView {
+pub fn ViewerCheckResultPanel(
+    result: CheckResultData,
+    /// 書類キー（指定するとチェックリストを表示）
+    #[prop(optional, into)] doc_key: Option<String>,
+    /// 位置付きの指摘・未記入項目をクリックしたとき（書類の該当箇所を表示）
+    #[prop(optional)] on_locate: Option<Callback<FindingLocation>>,
+) -> impl IntoView {
     let status_class = match result.status.as_str() {
         "ok" => "status-ok",
         "warning" => "status-warning",
@@ -180,6 +190,7 @@ pub fn ViewerCheckResultPanel(result: CheckResultData) -> impl IntoView {
 
     let result_items = result.items.clone();
     let missing_fields = result.missing_fields.clone();
+    let checklist = doc_key.and_then(|key| checklist_lines(&key, &result));
 
     view! {
         <div class=format!("check-result-panel {}", status_class)>
@@ -208,10 +219,14 @@ pub fn ViewerCheckResultPanel(result: CheckResultData) -> impl IntoView {
                                     "error" => "✗",
                                     _ => "•",
                                 };
+                                let at = item.finding_location();
                                 view! {
-                                    <li class=format!("item-{}", item.item_type)>
+                                    <li class=format!("item-{}{}", item.item_type, locatable_class(&at, on_locate))
+                                        on:click=locate_handler(at.clone(), on_locate)
+                                    >
                                         <span class="item-icon">{icon}</span>
                                         <span class="item-message">{item.message}</span>
+                                        {location_tag(&at)}
                                     </li>
                                 }
                             }).collect_view()}
@@ -226,16 +241,22 @@ pub fn ViewerCheckResultPanel(result: CheckResultData) -> impl IntoView {
                     <div class="missing-fields-list">
                         <h4>"未記入項目"</h4>
                         <ul>
-                            {fields.into_iter().map(|field| view! {
-                                <li>
-                                    <span class="field-name">{field.field}</span>
-                                    <span class="field-location">"（"{field.location}"）"</span>
-                                </li>
+                            {fields.into_iter().map(|field| {
+                                let at = field.finding_location();
+                                view! {
+                                    <li class=locatable_class(&at, on_locate).trim() on:click=locate_handler(at.clone(), on_locate)>
+                                        <span class="field-name">{field.field}</span>
+                                        <span class="field-location">"（"{field.location}"）"</span>
+                                        {location_tag(&at)}
+                                    </li>
+                                }
                             }).collect_view()}
                         </ul>
                     </div>
                 }
             })}
+
+            {checklist.map(|lines| view! { <ChecklistDetails lines=lines /> })}
         </div>
     }
 }
@@ -0,0 +1,123 @@
+//! 技術者資格登録簿ビュー
+//!
+//! 現場代理人・主任技術者などの資格（番号・有効期限・資格者証リンク）を
+//! プロジェクト横断で管理する。編集内容は即時LocalStorageに保存される
+
+use leptos::*;
+use crate::components::row_list::RowList;
+use crate::models::{Person, PersonQualification, ViewMode};
+use crate::utils::registry::{new_person_id, save_person_registry};
+use crate::ProjectContext;
+
+#[component]
+pub fn PersonRegistryView() -> impl IntoView {
+    let ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
+    let set_view_mode = ctx.set_view_mode;
+    // 編集のたびに登録簿を保存
+    let rows = RowList::new(ctx.person_registry, ctx.set_person_registry, |ps: &[Person]| save_person_registry(ps));
+    let add_person = move |_| rows.push(Person { id: new_person_id(), ..Person::default() });
+
+    view! {
+        <div class="person-registry-view">
+            <div class="request-letter-header">
+                <button class="back-btn" on:click=move |_| set_view_mode.set(ViewMode::Dashboard)>
+                    "← 戻る"
+                </button>
+                <h2>"技術者資格登録簿"</h2>
+                <span class="hint">"全プロジェクト共通。現場代理人・主任技術者、資格書類（041/051）から参照されます"</span>
+                <button class="add-btn" on:click=add_person>"+ 人物追加"</button>
+            </div>
+
+            {move || (rows.count() == 0).then(|| view! {
+                <p class="empty-state">"登録されている人物はいません"</p>
+            })}
+
+            {move || (0..rows.count()).map(|i| {
+                let q_count = create_memo(move |_| rows.read(i, |p| p.qualifications.len()));
+                let edit_q = move |qi: usize, f: &dyn Fn(&mut PersonQualification)| {
+                    rows.edit(i, |p| {
+                        if let Some(q) = p.qualifications.get_mut(qi) {
+                            f(q);
+                        }
+                    });
+                };
+                let read_q = move |qi: usize, f: fn(&PersonQualification) -> String| {
+                    rows.read(i, |p| p.qualifications.get(qi).map(f).unwrap_or_default())
+                };
+
+                view! {
+                    <div class="person-card">
+                        <div class="worker-editor-row">
+                            <input type="text" class="worker-name" placeholder="氏名"
+                                prop:value=move || rows.read(i, |p| p.name.clone())
+                                on:input=move |ev| { let v = event_target_value(&ev); rows.edit(i, |p| p.name = v); }
+                            />
+                            <input type="text" placeholder="所属会社"
+                                prop:value=move || rows.read(i, |p| p.company.clone())
+                                on:input=move |ev| { let v = event_target_value(&ev); rows.edit(i, |p| p.company = v); }
+                            />
+                            <button class="add-btn small" on:click=move |_| {
+                                rows.edit(i, |p| p.qualifications.push(PersonQualification::default()));
+                            }>"+ 資格追加"</button>
+                            <button class="delete-btn small" on:click=move |_| {
+                                let name = rows.read(i, |p| p.name.clone());
+                                let confirmed = web_sys::window()
+                                    .and_then(|w| w.confirm_with_message(&format!("「{}」を登録簿から削除しますか？", name)).ok())
+                                    .unwrap_or(false);
+                                if confirmed {
+                                    rows.remove(i);
+                                }
+                            }>"削除"</button>
+                        </div>
+
+                        {move || (0..q_count.get()).map(|qi| view! {
+                            <div class="worker-editor-row worker-qualification">
+                                <input type="text" placeholder="資格名（例: 1級土木施工管理技士）"
+                                    prop:value=move || read_q(qi, |q| q.name.clone())
+                                    on:input=move |ev| { let v = event_target_value(&ev); edit_q(qi, &|q| q.name = v.clone()); }
+                                />
+                                <input type="text" placeholder="番号"
+                                    prop:value=move || read_q(qi, |q| q.number.clone().unwrap_or_default())
+                                    on:input=move |ev| {
+                                        let v = event_target_value(&ev);
+                                        edit_q(qi, &|q| q.number = (!v.trim().is_empty()).then(|| v.clone()));
+                                    }
+                                />
+                                <label class="worker-field">
+                                    "有効期限"
+                                    <input type="date"
+                                        prop:value=move || read_q(qi, |q| q.expiry.clone().unwrap_or_default())
+                                        on:input=move |ev| {
+                                            let v = event_target_value(&ev);
+                                            edit_q(qi, &|q| q.expiry = (!v.is_empty()).then(|| v.clone()));
+                                        }
+                                    />
+                                </label>
+                                <input type="text" placeholder="資格者証URL"
+                                    prop:value=move || read_q(qi, |q| q.url.clone().unwrap_or_default())
+                                    on:input=move |ev| {
+                                        let v = event_target_value(&ev);
+                                        edit_q(qi, &|q| q.url = (!v.trim().is_empty()).then(|| v.clone()));
+                                    }
+                                />
+                                {move || {
+                                    let url = read_q(qi, |q| q.url.clone().unwrap_or_default());
+                                    (!url.is_empty()).then(|| view! {
+                                        <a class="doc-link" href=url target="_blank" rel="noopener">"開く"</a>
+                                    })
+                                }}
+                                <button class="delete-btn small" on:click=move |_| {
+                                    rows.edit(i, |p| {
+                                        if qi < p.qualifications.len() {
+                                            p.qualifications.remove(qi);
+                                        }
+                                    });
+                                }>"✕"</button>
+                            </div>
+                        }).collect_view()}
+                    </div>
+                }
+            }).collect_view()}
+        </div>
+    }
+}
@@ -0,0 +1,366 @@
+//! 書類提出依頼ビュー
+//!
+//! 未提出・期限切れ・AIチェックでエラーになった書類を業者ごとに集計し、
+//! 印刷用の依頼書とメール下書き（.eml / mailto）を生成する
+
+use leptos::*;
+use crate::models::{Contractor, DocKind, ProjectData, RequestLetterTemplate, ViewMode};
+use crate::utils::{download_file, encode_base64};
+use crate::ProjectContext;
+
+// ============================================
+// 依頼対象の集計
+// ============================================
+
+/// 書類を依頼する理由
+#[derive(Debug, Clone, PartialEq)]
+pub enum RequestReason {
+    /// 未提出（status = false）
+    Missing,
+    /// 備考に「要依頼」と記載
+    Requested,
+    /// 有効期限切れ（期限日）
+    Expired(String),
+    /// AIチェックでエラー判定（サマリー）
+    CheckError(String),
+}
+
+impl RequestReason {
+    /// 依頼書に記載する理由テキスト
+    pub fn label(&self) -> String {
+        match self {
+            RequestReason::Missing => "未提出".to_string(),
+            RequestReason::Requested => "要依頼".to_string(),
+            RequestReason::Expired(date) => format!("有効期限切れ（{}）", date),
+            RequestReason::CheckError(summary) if summary.is_empty() => "書類不備".to_string(),
+            RequestReason::CheckError(summary) => format!("書類不備: {}", summary),
+        }
+    }
+}
+
+/// 依頼対象の書類
+#[derive(Debug, Clone)]
+pub struct RequestedDoc {
+    pub doc_key: String,
+    pub label: String,
+    pub reasons: Vec<RequestReason>,
+    pub note: Option<String>,
+}
+
+/// 業者ごとの書類提出依頼
+#[derive(Debug, Clone)]
+pub struct RequestLetter {
+    pub contractor_id: String,
+    pub contractor_name: String,
+    pub email: Option<String>,
+    pub docs: Vec<RequestedDoc>,
+    pub subject: String,
+    pub body: String,
+}
+
+/// 業者の書類から依頼対象を抽出（書類種別順）
+pub fn collect_requested_docs(contractor: &Contractor, today: &str) -> Vec<RequestedDoc> {
+    contractor.sorted_docs().into_iter()
+        .filter_map(|(doc_key, doc)| {
+            let note = doc.note.clone().filter(|n| !n.trim().is_empty());
+            let mut reasons = Vec::new();
+
+            if !doc.status {
+                reasons.push(RequestReason::Missing);
+            } else if note.as_deref().map(|n| n.contains("要依頼")).unwrap_or(false) {
+                reasons.push(RequestReason::Requested);
+            }
+            if let Some(ref valid_until) = doc.valid_until {
+                if !valid_until.is_empty() && valid_until.as_str() < today {
+                    reasons.push(RequestReason::Expired(valid_until.clone()));
+                }
+            }
+            if let Some(ref result) = doc.check_result {
+                if result.status == "error" {
+                    reasons.push(RequestReason::CheckError(result.summary.clone()));
+                }
+            }
+
+            if reasons.is_empty() {
+                return None;
+            }
+
+            let label = DocKind::parse(doc_key).label();
+
+            Some(RequestedDoc {
+                doc_key: doc_key.clone(),
+                label,
+                reasons,
+                note,
+            })
+        })
+        .collect()
+}
+
+/// 依頼書本文の書類一覧（番号付き）
+fn format_doc_list(docs: &[RequestedDoc]) -> String {
+    docs.iter()
+        .enumerate()
+        .map(|(i, doc)| {
+            let reasons = doc.reasons.iter().map(|r| r.label()).collect::<Vec<_>>().join(" / ");
+            match doc.note.as_deref() {
+                Some(note) if note != "要依頼" => format!("{}. {}（{}）※{}", i + 1, doc.label, reasons, note),
+                _ => format!("{}. {}（{}）", i + 1, doc.label, reasons),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// yyyy-MM-dd を「yyyy年M月d日」に整形（解析できなければそのまま）
+fn format_japanese_date(date: &str) -> String {
+    let parts: Vec<_> = date.split('-').filter_map(|p| p.parse::<u32>().ok()).collect();
+    match parts.as_slice() {
+        [y, m, d] => format!("{}年{}月{}日", y, m, d),
+        _ => date.to_string(),
+    }
+}
+
+/// テンプレートの `{name}` プレースホルダーを置換
+pub fn render_template(template: &str, vars: &[(&str, String)]) -> String {
+    vars.iter().fold(template.to_string(), |text, (name, value)| {
+        text.replace(&format!("{{{}}}", name), value)
+    })
+}
+
+/// 依頼対象のある業者ごとに依頼書を生成
+pub fn build_request_letters(project: &ProjectData, today: &str) -> Vec<RequestLetter> {
+    let template = project.request_template.clone().unwrap_or_default();
+    let period = match (&project.period_start, &project.period_end) {
+        (Some(start), Some(end)) => format!("{} 〜 {}", start, end),
+        _ => project.period.clone(),
+    };
+
+    project.contractors.iter()
+        .filter_map(|contractor| {
+            let docs = collect_requested_docs(contractor, today);
+            if docs.is_empty() {
+                return None;
+            }
+
+            let vars = [
+                ("project_name", project.project_name.clone()),
+                ("client", project.client.clone()),
+                ("period", period.clone()),
+                ("site_representative", project.site_representative.clone().unwrap_or_default()),
+                ("chief_engineer", project.chief_engineer.clone().unwrap_or_default()),
+                ("contractor_name", contractor.name.clone()),
+                ("contractor_role", contractor.role.clone()),
+                ("contractor_email", contractor.email.clone().unwrap_or_default()),
+                ("doc_count", docs.len().to_string()),
+                ("doc_list", format_doc_list(&docs)),
+                ("today", format_japanese_date(today)),
+            ];
+
+            Some(RequestLetter {
+                contractor_id: contractor.id.clone(),
+                contractor_name: contractor.name.clone(),
+                email: contractor.email.clone().filter(|e| !e.trim().is_empty()),
+                subject: render_template(&template.subject, &vars),
+                body: render_template(&template.body, &vars),
+                docs,
+            })
+        })
+        .collect()
+}
+
+/// テンプレートで使用できるプレースホルダー一覧（編集画面のヒント用）
+pub const TEMPLATE_PLACEHOLDERS: &[(&str, &str)] = &[
+    ("{project_name}", "工事名"),
+    ("{client}", "発注者"),
+    ("{period}", "工期"),
+    ("{site_representative}", "現場代理人"),
+    ("{chief_engineer}", "主任技術者"),
+    ("{contractor_name}", "業者名"),
+    ("{contractor_role}", "業者の役割"),
+    ("{contractor_email}", "業者メール"),
+    ("{doc_count}", "依頼書類数"),
+    ("{doc_list}", "依頼書類一覧"),
+    ("{today}", "作成日"),
+];
+
+// ============================================
+// 出力（.eml / mailto / 印刷）
+// ============================================
+
+/// MIMEヘッダー用エンコード（RFC 2047, UTF-8 Base64）
+fn encode_mime_header(value: &str) -> String {
+    if value.is_ascii() {
+        value.to_string()
+    } else {
+        format!("=?UTF-8?B?{}?=", encode_base64(value).unwrap_or_default())
+    }
+}
+
+/// メール下書き（.eml）を生成
+///
+/// `X-Unsent: 1` を付けることでOutlook等では送信前の下書きとして開かれる
+pub fn build_eml(letter: &RequestLetter) -> String {
+    let body = letter.body.replace("\r\n", "\n").replace('\n', "\r\n");
+    let encoded = encode_base64(&body).unwrap_or_default();
+    let wrapped = encoded.as_bytes()
+        .chunks(76)
+        .map(|chunk| String::from_utf8_lossy(chunk).into_owned())
+        .collect::<Vec<_>>()
+        .join("\r\n");
+
+    let mut eml = String::new();
+    if let Some(ref email) = letter.email {
+        eml.push_str(&format!("To: {}\r\n", email));
+    }
+    eml.push_str(&format!("Subject: {}\r\n", encode_mime_header(&letter.subject)));
+    eml.push_str("X-Unsent: 1\r\n");
+    eml.push_str("MIME-Version: 1.0\r\n");
+    eml.push_str("Content-Type: text/plain; charset=UTF-8\r\n");
+    eml.push_str("Content-Transfer-Encoding: base64\r\n");
+    eml.push_str("\r\n");
+    eml.push_str(&wrapped);
+    eml.push_str("\r\n");
+    eml
+}
+
+/// mailtoリンクを生成
+pub fn build_mailto(letter: &RequestLetter) -> String {
+    let body = letter.body.replace("\r\n", "\n").replace('\n', "\r\n");
+    format!(
+        "mailto:{}?subject={}&body={}",
+        letter.email.as_deref().map(|e| js_sys::encode_uri_component(e).as_string().unwrap_or_default()).unwrap_or_default(),
+        js_sys::encode_uri_component(&letter.subject),
+        js_sys::encode_uri_component(&body)
+    )
+}
+
+/// HTML特殊文字をエスケープ
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// 依頼書を別ウィンドウで開いて印刷
+fn print_letter(letter: &RequestLetter) {
+    let Some(window) = web_sys::window() else { return };
+    let Ok(Some(print_window)) = window.open_with_url_and_target("", "_blank") else {
+        let _ = window.alert_with_message("印刷用ウィンドウを開けませんでした（ポップアップブロックを確認してください）");
+        return;
+    };
+
+    let html = format!(
+        "<head><meta charset=\"utf-8\"><title>{title}</title>\
+         <style>\
+         body {{ font-family: 'Yu Mincho', 'Hiragino Mincho ProN', serif; margin: 30mm 25mm; color: #000; }}\
+         h1 {{ text-align: center; font-size: 20pt; letter-spacing: 0.5em; margin-bottom: 2em; }}\
+         .letter-subject {{ font-weight: bold; margin-bottom: 1.5em; }}\
+         .letter-body {{ white-space: pre-wrap; line-height: 1.9; font-size: 11pt; }}\
+         </style></head>\
+         <body><h1>書類提出依頼書</h1>\
+         <div class=\"letter-subject\">{title}</div>\
+         <div class=\"letter-body\">{body}</div></body>",
+        title = escape_html(&letter.subject),
+        body = escape_html(&letter.body),
+    );
+
+    if let Some(root) = print_window.document().and_then(|d| d.document_element()) {
+        root.set_inner_html(&html);
+        let _ = print_window.print();
+    }
+}
+
+// ============================================
+// 書類提出依頼ビューコンポーネント
+// ============================================
+
+/// 書類提出依頼一覧
+#[component]
+pub fn RequestLetterView() -> impl IntoView {
+    let ctx = use_context::<ProjectContext>().expect("ProjectContext not found");
+    let set_view_mode = ctx.set_view_mode;
+
+    view! {
+        <div class="request-letter-view">
+            <div class="request-letter-header">
+                <button class="back-btn" on:click=move |_| set_view_mode.set(ViewMode::Dashboard)>
+                    "← 戻る"
+                </button>
+                <h2>"書類提出依頼"</h2>
+                <span class="hint">"文面は編集モードの「提出依頼テンプレート」で変更できます"</span>
+            </div>
+
+            {move || {
+                let Some(project) = ctx.project.get() else {
+                    return view! { <p class="empty-state">"プロジェクトデータがありません"</p> }.into_view();
+                };
+                let letters = build_request_letters(&project, &crate::get_today());
+                if letters.is_empty() {
+                    return view! { <p class="empty-state">"依頼対象の書類はありません"</p> }.into_view();
+                }
+
+                let project_name = project.project_name.clone();
+                letters.into_iter().map(|letter| {
+                    let mailto = build_mailto(&letter);
+                    let letter_for_print = letter.clone();
+                    let letter_for_eml = letter.clone();
+                    let eml_filename = format!(
+                        "{}_{}_書類提出依頼.eml",
+                        project_name.replace(" ", "_").replace("/", "-"),
+                        letter.contractor_name.replace(" ", "_").replace("/", "-")
+                    );
+
+                    view! {
+                        <div class="request-letter-card" data-contractor-id=letter.contractor_id.clone()>
+                            <div class="request-letter-card-header">
+                                <h3>{letter.contractor_name.clone()}</h3>
+                                <span class="request-letter-email">
+                                    {letter.email.clone().unwrap_or_else(|| "メール未登録".to_string())}
+                                </span>
+                                <div class="request-letter-actions">
+                                    <button class="gas-btn" on:click=move |_| print_letter(&letter_for_print)>
+                                        "印刷"
+                                    </button>
+                                    <button class="gas-btn" on:click=move |_| {
+                                        download_file(&eml_filename, "message/rfc822", &build_eml(&letter_for_eml));
+                                    }>
+                                        ".emlダウンロード"
+                                    </button>
+                                    <a class="gas-btn primary" href=mailto>"メール作成"</a>
+                                </div>
+                            </div>
+
+                            <table class="request-letter-docs">
+                                <thead>
+                                    <tr><th>"書類"</th><th>"理由"</th><th>"備考"</th></tr>
+                                </thead>
+                                <tbody>
+                                    {letter.docs.iter().map(|doc| view! {
+                                        <tr title=doc.doc_key.clone()>
+                                            <td>{doc.label.clone()}</td>
+                                            <td>{doc.reasons.iter().map(|r| r.label()).collect::<Vec<_>>().join(" / ")}</td>
+                                            <td>{doc.note.clone().unwrap_or_default()}</td>
+                                        </tr>
+                                    }).collect_view()}
+                                </tbody>
+                            </table>
+
+                            <details class="request-letter-preview">
+                                <summary>"文面プレビュー"</summary>
+                                <div class="request-letter-subject">{letter.subject.clone()}</div>
+                                <pre class="request-letter-body">{letter.body.clone()}</pre>
+                            </details>
+                        </div>
+                    }
+                }).collect_view()
+            }}
+        </div>
+    }
+}
+
+/// テンプレートが既定文面と同じかどうか（保存時に既定値を埋め込まないため）
+pub fn is_default_template(template: &RequestLetterTemplate) -> bool {
+    *template == RequestLetterTemplate::default()
+}
@@ -1,6 +1,7 @@
 //! スプレッドシートビューワモジュール
 //!
 //! ## 変更履歴
+//! - 2026-10-18: AIチェックURLを EditorUrl で構築、メッセージ受信を editor_protocol に統一
 //! - 2026-01-02: AIチェック時のツールバー重複を修正
 //! - 2026-01-02: 工事名（projectName）をAIチェックURLに追加（バリデーション用）
 //! - 2026-01-02: Excelファイル判定（isExcel, fileId）をAIチェックURLに追加
@@ -16,10 +17,9 @@
 //! 認証関連のサブフレームはGoogleのCSPによりブロックされます。
 
 use leptos::*;
-use wasm_bindgen::prelude::*;
-use wasm_bindgen::JsCast;
 use crate::models::ViewMode;
 use crate::ProjectContext;
+use crate::utils::editor_protocol::{on_editor_message, EditorMessage, EditorMode, EditorUrl};
 use crate::utils::gas::get_gas_url;
 
 // ============================================
@@ -37,7 +37,7 @@ fn extract_spreadsheet_id(url: &str) -> Option<String> {
     url.split_once("/d/")
         .map(|(_, rest)| rest)
         .and_then(|rest| {
-            let id = rest.split(|c| c == '/' || c == '?' || c == '#').next()?;
+            let id = rest.split(['/', '?', '#']).next()?;
             (!id.is_empty()).then(|| id.to_string())
         })
 }
@@ -90,14 +90,11 @@ pub fn SpreadsheetViewer(
     // auto_fix=true の場合は最初からAIチェックモードに入る
     let (ai_check_mode, set_ai_check_mode) = create_signal(auto_fix);
 
-    let on_back = {
-        let set_ai_check_mode = set_ai_check_mode.clone();
-        move |_| {
-            if ai_check_mode.get() {
-                set_ai_check_mode.set(false);
-            } else {
-                ctx.set_view_mode.set(ViewMode::Dashboard);
-            }
+    let on_back = move |_| {
+        if ai_check_mode.get() {
+            set_ai_check_mode.set(false);
+        } else {
+            ctx.set_view_mode.set(ViewMode::Dashboard);
         }
     };
 
@@ -136,58 +133,25 @@ pub fn SpreadsheetViewer(
     // 工事名を取得（事業所名バリデーション用）
     let project_name = ctx.project.get().map(|p| p.project_name.clone()).unwrap_or_default();
     let ai_check_url = spreadsheet_info.as_ref().map(|(id, gid)| {
-        let mut check_url = format!(
-            "editor/index.html?mode=spreadsheet-check&spreadsheetId={}&docType={}&contractor={}&gasUrl={}&contractorId={}&docKey={}&projectName={}",
-            js_sys::encode_uri_component(id),
-            js_sys::encode_uri_component(&doc_type),
-            js_sys::encode_uri_component(&contractor),
-            js_sys::encode_uri_component(&gas_url),
-            js_sys::encode_uri_component(&contractor_id),
-            js_sys::encode_uri_component(&doc_key),
-            js_sys::encode_uri_component(&project_name)
-        );
-        if let Some(g) = gid {
-            check_url.push_str(&format!("&gid={}", js_sys::encode_uri_component(g)));
-        }
-        // Excelファイルの場合はfileIdとフラグを追加
-        if is_excel_compat {
-            check_url.push_str(&format!("&isExcel=true&fileId={}", js_sys::encode_uri_component(id)));
-        }
-        // auto_fix モードの場合はフラグを追加
-        if auto_fix {
-            check_url.push_str("&autoFix=true");
-        }
-        check_url
+        let check_url = EditorUrl::new(EditorMode::SpreadsheetCheck)
+            .spreadsheet(id, gid.as_deref())
+            .doc(&contractor, &contractor_id, &doc_type, &doc_key)
+            .gas_url(Some(&gas_url))
+            .project_name(&project_name)
+            .auto_fix(auto_fix);
+        // Excelファイルの場合はDriveのファイルIDで取得
+        if is_excel_compat { check_url.excel(id) } else { check_url }.build()
     });
 
     let can_ai_check = spreadsheet_info.is_some() && !gas_url.is_empty();
     let ai_check_url_clone = ai_check_url.clone();
 
-    // postMessageハンドラ（spreadsheet-check-cancel: AIチェック画面から戻る）
-    {
-        let set_ai_check_mode = set_ai_check_mode.clone();
-
-        create_effect(move |_| {
-            let set_ai_check_mode = set_ai_check_mode.clone();
-
-            let handler = Closure::wrap(Box::new(move |event: web_sys::MessageEvent| {
-                if let Ok(data) = event.data().dyn_into::<js_sys::Object>() {
-                    if let Some(msg_type) = js_sys::Reflect::get(&data, &"type".into())
-                        .ok()
-                        .and_then(|v| v.as_string())
-                    {
-                        if msg_type == "spreadsheet-check-cancel" {
-                            set_ai_check_mode.set(false);
-                        }
-                    }
-                }
-            }) as Box<dyn FnMut(_)>);
-
-            let window = web_sys::window().unwrap();
-            let _ = window.add_event_listener_with_callback("message", handler.as_ref().unchecked_ref());
-            handler.forget();
-        });
-    }
+    // AIチェック画面から戻る（spreadsheet-check-cancel）
+    on_editor_message(move |message| {
+        if matches!(message, EditorMessage::SpreadsheetCheckCancel) {
+            set_ai_check_mode.set(false);
+        }
+    });
 
     view! {
         <div class="viewer-container spreadsheet-viewer">
@@ -195,7 +159,7 @@ pub fn SpreadsheetViewer(
             {move || if !ai_check_mode.get() {
                 view! {
                     <div class="viewer-toolbar">
-                        <button class="back-btn" on:click=on_back.clone()>
+                        <button class="back-btn" on:click=on_back>
                             "← 戻る"
                         </button>
                         <span class="doc-info">{contractor.clone()}" / "{doc_type.clone()}</span>